@@ -5,20 +5,6 @@ use strum_macros::EnumIter;
 
 #[derive(Debug, Hash, Copy, Serialize, Deserialize, Clone, EnumIter, Eq, PartialEq, Display)]
 pub enum Sound {
-    MauserRiffleFire1,
-    MauserRiffleFire2,
-    MauserRiffleFire3,
-    MauserRiffleReload1,
-    MauserRiffleReload2,
-    MosinNagantFire1,
-    MosinNagantFire2,
-    MosinNagantFire3,
-    MosinNagantFire4,
-    MosinNagantFire5,
-    MosinNagantReload1,
-    MosinNagantReload2,
-    MosinNagantReload3,
-    MosinNagantReload4,
     CannonFire1,
     MaleScreaming1,
     MaleScreaming2,
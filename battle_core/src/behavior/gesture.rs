@@ -2,25 +2,35 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     entity::soldier::WeaponClass,
-    types::{Precision, SoldierIndex, WorldPoint},
+    types::{Angle, Precision, SoldierIndex, WorldPoint},
 };
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Gesture {
     Idle,
-    // Covering,
     Reloading(u64, WeaponClass),
     Aiming(u64, WeaponClass),
     Firing(u64, WeaponClass),
-    // Firing,
     // ReloadingOwn,
-    // ReloadingAsAssistant,
+    // Crew served weapon gunner set up its weapon in given direction
+    SettingUp(u64, Angle),
+    // Crew served weapon gunner tear down its weapon
+    TearingDown(u64),
+    // Crew served weapon assistant watch for its gunner
+    Covering,
+    // Crew served weapon assistant load a magazine into its gunner weapon
+    ReloadingAsAssistant(u64, SoldierIndex),
 }
 impl Gesture {
     pub fn next(&self, frame_i: u64, next: Gesture) -> Gesture {
         match self {
-            Gesture::Idle => next,
-            Gesture::Reloading(end, _) | Gesture::Aiming(end, _) | Gesture::Firing(end, _) => {
+            Gesture::Idle | Gesture::Covering => next,
+            Gesture::Reloading(end, _)
+            | Gesture::Aiming(end, _)
+            | Gesture::Firing(end, _)
+            | Gesture::SettingUp(end, _)
+            | Gesture::TearingDown(end)
+            | Gesture::ReloadingAsAssistant(end, _) => {
                 if end <= &frame_i {
                     next
                 } else {
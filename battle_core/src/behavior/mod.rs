@@ -57,6 +57,16 @@ pub enum Behavior {
     // Combat
     SuppressFire(WorldPoint),
     EngageSoldier(SoldierIndex),
+    // Crew served weapon must be teared down before moving
+    TearDownWeapon,
+    // Indirect fire on point, with rounds already fired on it (ranging-in)
+    IndirectFire(WorldPoint, u32),
+    // Walk to the vehicle and take a free place on board
+    Board(VehicleIndex),
+    // Leave the vehicle to stand around its hull
+    Dismount,
+    // Drive in reverse gear, vehicle rear toward the next point
+    ReverseTo(WorldPaths),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -81,9 +91,17 @@ impl Behavior {
             Order::SneakTo(path, _) => Behavior::SneakTo(path.clone()),
             Order::Defend(angle) => Behavior::Defend(*angle),
             Order::Hide(angle) => Behavior::Hide(*angle),
-            // default_behavior should never be called for EngageSquad & SuppressFire
+            // default_behavior should never be called for EngageSquad, SuppressFire & IndirectFire
             Order::EngageSquad(_squad_id) => unreachable!(),
             Order::SuppressFire(_point) => unreachable!(),
+            Order::IndirectFire(_point) => unreachable!(),
+            Order::Board(vehicle_index) => Behavior::Board(*vehicle_index),
+            Order::Dismount => Behavior::Dismount,
+            // Only vehicles can reverse
+            Order::ReverseTo(path, _) => match battle_state.soldier_behavior_mode(soldier) {
+                BehaviorMode::Ground => Behavior::MoveTo(path.clone()),
+                BehaviorMode::Vehicle => Behavior::ReverseTo(path.clone()),
+            },
         }
     }
 
@@ -93,6 +111,7 @@ impl Behavior {
                 BehaviorPropagation::Regularly
             }
             Behavior::DriveTo(_) => BehaviorPropagation::Never,
+            Behavior::ReverseTo(_) => BehaviorPropagation::Never,
             Behavior::RotateTo(_) => BehaviorPropagation::Never,
             Behavior::Idle(_) => BehaviorPropagation::OnChange,
             Behavior::Defend(_) => BehaviorPropagation::OnChange,
@@ -101,6 +120,10 @@ impl Behavior {
             Behavior::Dead => BehaviorPropagation::Never,
             Behavior::Unconscious => BehaviorPropagation::Never,
             Behavior::EngageSoldier(_) => BehaviorPropagation::OnChange,
+            Behavior::TearDownWeapon => BehaviorPropagation::Never,
+            Behavior::IndirectFire(_, _) => BehaviorPropagation::OnChange,
+            Behavior::Board(_) => BehaviorPropagation::OnChange,
+            Behavior::Dismount => BehaviorPropagation::OnChange,
         }
     }
 
@@ -109,7 +132,8 @@ impl Behavior {
             Behavior::MoveTo(paths)
             | Behavior::MoveFastTo(paths)
             | Behavior::SneakTo(paths)
-            | Behavior::DriveTo(paths) => {
+            | Behavior::DriveTo(paths)
+            | Behavior::ReverseTo(paths) => {
                 paths
                     .remove_next_point()
                     .expect("Reach a move behavior implies containing point");
@@ -124,6 +148,10 @@ impl Behavior {
             Behavior::Unconscious => {}
             Behavior::SuppressFire(_) => {}
             Behavior::EngageSoldier(_) => {}
+            Behavior::TearDownWeapon => {}
+            Behavior::IndirectFire(_, _) => {}
+            Behavior::Board(_) => {}
+            Behavior::Dismount => {}
         }
 
         false
@@ -134,7 +162,8 @@ impl Behavior {
             Behavior::MoveTo(world_paths)
             | Behavior::MoveFastTo(world_paths)
             | Behavior::SneakTo(world_paths)
-            | Behavior::DriveTo(world_paths) => Some(world_paths),
+            | Behavior::DriveTo(world_paths)
+            | Behavior::ReverseTo(world_paths) => Some(world_paths),
             Behavior::RotateTo(_)
             | Behavior::Idle(_)
             | Behavior::Defend(_)
@@ -142,23 +171,57 @@ impl Behavior {
             | Behavior::Dead
             | Behavior::Unconscious
             | Behavior::SuppressFire(_)
-            | Behavior::EngageSoldier(_) => None,
+            | Behavior::EngageSoldier(_)
+            | Behavior::TearDownWeapon
+            | Behavior::IndirectFire(_, _)
+            | Behavior::Board(_)
+            | Behavior::Dismount => None,
+        }
+    }
+
+    /// Behavior as an opponent may know it : move paths are reduced to their next point
+    pub fn without_route(&self) -> Self {
+        match self {
+            Behavior::MoveTo(paths) => Behavior::MoveTo(paths.next_step()),
+            Behavior::MoveFastTo(paths) => Behavior::MoveFastTo(paths.next_step()),
+            Behavior::SneakTo(paths) => Behavior::SneakTo(paths.next_step()),
+            Behavior::DriveTo(paths) => Behavior::DriveTo(paths.next_step()),
+            Behavior::ReverseTo(paths) => Behavior::ReverseTo(paths.next_step()),
+            Behavior::RotateTo(_)
+            | Behavior::Idle(_)
+            | Behavior::Defend(_)
+            | Behavior::Hide(_)
+            | Behavior::Dead
+            | Behavior::Unconscious
+            | Behavior::SuppressFire(_)
+            | Behavior::EngageSoldier(_)
+            | Behavior::TearDownWeapon
+            | Behavior::IndirectFire(_, _)
+            | Behavior::Board(_)
+            | Behavior::Dismount => self.clone(),
         }
     }
 
     pub fn posture(&self) -> Posture {
         // TODO : posture can be different on same behavior (like with SuppressFire, EngageSoldier)
         match self {
-            Behavior::MoveTo(_) | Behavior::MoveFastTo(_) | Behavior::Idle(_) => Posture::StandUp,
+            Behavior::MoveTo(_)
+            | Behavior::MoveFastTo(_)
+            | Behavior::Idle(_)
+            | Behavior::Board(_)
+            | Behavior::Dismount => Posture::StandUp,
             Behavior::Defend(_)
             | Behavior::SneakTo(_)
             | Behavior::DriveTo(_)
+            | Behavior::ReverseTo(_)
             | Behavior::RotateTo(_)
             | Behavior::Hide(_)
             | Behavior::Dead
             | Behavior::Unconscious
             | Behavior::SuppressFire(_)
-            | Behavior::EngageSoldier(_) => Posture::Flat,
+            | Behavior::EngageSoldier(_)
+            | Behavior::TearDownWeapon
+            | Behavior::IndirectFire(_, _) => Posture::Flat,
         }
     }
 
@@ -198,6 +261,11 @@ impl Display for Behavior {
             Behavior::Unconscious => f.write_str("Unconscious"),
             Behavior::SuppressFire(_) => f.write_str("SuppressFire"),
             Behavior::EngageSoldier(_) => f.write_str("EngageSquad"),
+            Behavior::TearDownWeapon => f.write_str("TearDownWeapon"),
+            Behavior::IndirectFire(_, _) => f.write_str("IndirectFire"),
+            Behavior::Board(_) => f.write_str("Board"),
+            Behavior::Dismount => f.write_str("Dismount"),
+            Behavior::ReverseTo(_) => f.write_str("ReverseTo"),
         }
     }
 }
@@ -9,8 +9,11 @@ use crate::{
 use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 
+pub mod file;
+
 pub const DEFAULT_SERVER_REP_ADDRESS: &str = "tcp://0.0.0.0:4255";
 pub const DEFAULT_SERVER_PUB_ADDRESS: &str = "tcp://0.0.0.0:4256";
+pub const DEFAULT_SERVER_CONFIG_FILE: &str = "server_config.toml";
 ///
 pub const TARGET_FPS: u64 = 60;
 pub const SOLDIER_UPDATE_FREQ: u64 = 1;
@@ -58,6 +61,8 @@ pub const TILE_TYPE_OPACITY_MIDDLE_ROCK: f32 = 0.15;
 //
 pub const VISIBILITY_BY_LAST_FRAME_SHOOT: u64 = TARGET_FPS * 15;
 pub const VISIBILITY_BY_LAST_FRAME_SHOOT_DISTANCE: usize = 4;
+// World pixels around a known soldier in which a shot origin is considered as his one
+pub const KNOWN_SHOT_ORIGIN_DISTANCE: f32 = 5.0;
 
 // Target FPS
 // Velocity of move vector
@@ -80,6 +85,22 @@ pub const PENDING_ORDER_PATH_FINDING_DRAW_FRAMES: u64 = TARGET_FPS / 3;
 pub const VEHICLE_DRIVE_ORIENTATION_TARGET_TOLERANCE_COEFFICIENT: f32 = 100.;
 pub const VEHICLE_DRIVE_ORIENTATION_ADVANCE_TOLERANCE_COEFFICIENT: f32 = 100.;
 pub const VEHICLE_DRIVE_ORIENTATION_ADVANCE_TOLERANCE_DIFF: f32 = 50.;
+// Vehicle speed factor when driving in reverse gear
+pub const VEHICLE_REVERSE_SPEED_COEFFICIENT: f32 = 0.5;
+// Meters under which a move order behind the vehicle is driven in reverse gear
+pub const VEHICLE_AUTO_REVERSE_DISTANCE: i64 = 15;
+// Radians between main turret orientation and target under which main gun can fire
+pub const VEHICLE_MAIN_TURRET_AIM_TOLERANCE: f32 = 0.02;
+// Chance a penetrating hit immobilize the vehicle (hull hit) or jam its turret (turret hit)
+pub const VEHICLE_PENETRATION_COMPONENT_DAMAGE_CHANCE: f32 = 0.4;
+// Chance a penetrating hit knock out the vehicle
+pub const VEHICLE_PENETRATION_KNOCK_OUT_CHANCE: f32 = 0.3;
+// Chance a knocked out vehicle burn (killing its crew)
+pub const VEHICLE_KNOCKED_OUT_BURNING_CHANCE: f32 = 0.5;
+// Chance a penetrating hit kill (else wound) each crew member of the hit part
+pub const VEHICLE_PENETRATION_CREW_KILL_CHANCE: f32 = 0.5;
+// How many meters from the hull dismounted soldiers stand
+pub const VEHICLE_DISMOUNT_DISTANCE: i64 = 2;
 
 // Grid distance to search cover point
 pub const COVER_DISTANCE: i32 = 6;
@@ -99,8 +120,13 @@ pub const HIDE_MAXIMUM_RAYON: i64 = 50;
 pub const CAN_CROUCH_AFTER: u64 = TARGET_FPS * 60 * 5;
 // How many frames after last proximity shoot needed before soldier go from crouch to standup when idle
 pub const CAN_STANDUP_AFTER: u64 = TARGET_FPS * 60 * 10;
+// How many meters maximum squad member can be from a crew served weapon gunner to assist him
+pub const CREW_ASSISTANT_DISTANCE: i64 = 5;
 
-#[derive(Debug, Clone)]
+/// Server tunables. Can be read from a file (see `file::ServerConfigReader`) where missing
+/// fields take their default value.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
 pub struct ServerConfig {
     pub send_debug_points: bool,
     pub flags_update_freq: u64,
@@ -288,9 +314,14 @@ impl ServerConfig {
             Behavior::MoveFastTo(_) => self.visibility_move_fast_to_modifier,
             Behavior::SneakTo(_) => self.visibility_sneak_to_modifier,
             Behavior::DriveTo(_) => self.visibility_in_vehicle_modifier,
+            Behavior::ReverseTo(_) => self.visibility_in_vehicle_modifier,
             Behavior::RotateTo(_) => self.visibility_in_vehicle_modifier,
             Behavior::SuppressFire(_) => self.visibility_suppress_fire_modifier,
             Behavior::EngageSoldier(_) => self.visibility_engage_modifier,
+            Behavior::TearDownWeapon => self.visibility_defend_modifier,
+            Behavior::IndirectFire(_, _) => self.visibility_suppress_fire_modifier,
+            Behavior::Board(_) => self.visibility_move_to_modifier,
+            Behavior::Dismount => self.visibility_idle_standup_modifier,
             Behavior::Dead => self.visibility_dead_modifier,
             Behavior::Unconscious => self.visibility_unconscious_modifier,
         }
@@ -305,11 +336,16 @@ impl ServerConfig {
             Behavior::Defend(_) => None,
             Behavior::Hide(_) => None,
             Behavior::DriveTo(_) => None,
+            Behavior::ReverseTo(_) => None,
             Behavior::RotateTo(_) => None,
             Behavior::Dead => None,
             Behavior::Unconscious => None,
             Behavior::SuppressFire(_) => None,
             Behavior::EngageSoldier(_) => None,
+            Behavior::TearDownWeapon => None,
+            Behavior::IndirectFire(_, _) => None,
+            Behavior::Board(_) => Some(MOVE_VELOCITY),
+            Behavior::Dismount => None,
         }
     }
 
@@ -374,6 +410,7 @@ impl ServerConfig {
             ChangeConfigMessage::TileTypeOpacityMiddleRock(v) => self.tile_type_opacity_middle_rock = *v,
             ChangeConfigMessage::VisibilityByLastFrameShot(v) => self.visibility_by_last_frame_shoot = *v,
             ChangeConfigMessage::VisibilityByLastFrameShotDistance(v) => self.visibility_by_last_frame_shoot_distance = *v,
+            ChangeConfigMessage::Replace(config) => *self = *config.clone(),
             ChangeConfigMessage::ExplosiveDirectDeathRayon(explosive, new_distance) => {
                 if let Some(distance) = self.explosive_direct_death_rayon.get_mut(explosive) {
                     distance.millimeters = new_distance.millimeters()
@@ -450,4 +487,6 @@ pub enum ChangeConfigMessage {
     ExplosiveDirectDeathRayon(ExplosiveType, Distance),
     ExplosiveRegressiveDeathRayon(ExplosiveType, Distance),
     ExplosiveRegressiveInjuredRayon(ExplosiveType, Distance),
+    // Whole config (ex. reloaded from a file)
+    Replace(Box<ServerConfig>),
 }
@@ -0,0 +1,368 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+use strum::IntoEnumIterator;
+use thiserror::Error;
+
+use crate::game::explosive::ExplosiveType;
+
+use super::ServerConfig;
+
+/// Config file format, given by file extension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    pub fn from_path(path: &Path) -> Result<Self, ConfigError> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Ok(Self::Toml),
+            Some("json") => Ok(Self::Json),
+            _ => Err(ConfigError::UnknownFormat(path.to_path_buf())),
+        }
+    }
+}
+
+pub struct ServerConfigReader;
+
+impl ServerConfigReader {
+    /// Read and validate config file (fields not present in file take their default value)
+    pub fn from_file(path: &Path) -> Result<ServerConfig, ConfigError> {
+        Self::from_str(&fs::read_to_string(path)?, ConfigFormat::from_path(path)?)
+    }
+
+    pub fn from_str(content: &str, format: ConfigFormat) -> Result<ServerConfig, ConfigError> {
+        let config: ServerConfig =
+            match format {
+                // Toml deserializer can't read enums map keys (ex. explosives), json can
+                ConfigFormat::Toml => serde_json::from_value(serde_json::to_value(
+                    toml::from_str::<toml::Value>(content)?,
+                )?)?,
+                ConfigFormat::Json => serde_json::from_str(content)?,
+            };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+pub struct ServerConfigWriter;
+
+impl ServerConfigWriter {
+    pub fn to_file(destination: &Path, config: &ServerConfig) -> Result<(), ConfigError> {
+        fs::write(
+            destination,
+            Self::to_string(config, ConfigFormat::from_path(destination)?)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn to_string(config: &ServerConfig, format: ConfigFormat) -> Result<String, ConfigError> {
+        match format {
+            // Use a toml value to write tables after values whatever the fields order
+            ConfigFormat::Toml => Ok(toml::to_string_pretty(&toml::Value::try_from(config)?)?),
+            ConfigFormat::Json => Ok(serde_json::to_string_pretty(config)?),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Change one value from a `key=value` expression (ex. `visibility_firsts=4` or
+    /// `explosive_direct_death_rayon.FA19241927.millimeters=1500`). Value is read as json,
+    /// or as string if it is not valid json.
+    pub fn override_(&mut self, expression: &str) -> Result<(), ConfigError> {
+        let (key, raw_value) = expression
+            .split_once('=')
+            .ok_or_else(|| ConfigError::Override(expression.to_string()))?;
+        let new_value = serde_json::from_str(raw_value.trim())
+            .unwrap_or_else(|_| Value::String(raw_value.trim().to_string()));
+
+        let mut config = serde_json::to_value(&*self)?;
+        let mut value = &mut config;
+        for part in key.trim().split('.') {
+            value = value
+                .get_mut(part)
+                .ok_or_else(|| ConfigError::UnknownKey(key.to_string()))?;
+        }
+        *value = new_value;
+
+        let config: ServerConfig = serde_json::from_value(config)?;
+        config.validate()?;
+        *self = config;
+        Ok(())
+    }
+
+    /// Refuse values which would break the simulation
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (name, frequency) in [
+            ("flags_update_freq", self.flags_update_freq),
+            ("soldier_update_freq", self.soldier_update_freq),
+            ("soldier_animate_freq", self.soldier_animate_freq),
+            ("squad_leaders_update_freq", self.squad_leaders_update_freq),
+            ("interiors_update_freq", self.interiors_update_freq),
+            ("visibility_update_freq", self.visibility_update_freq),
+            ("morale_update_freq", self.morale_update_freq),
+            ("victory_update_freq", self.victory_update_freq),
+            ("physics_update_freq", self.physics_update_freq),
+            ("feeling_decreasing_freq", self.feeling_decreasing_freq),
+        ] {
+            if frequency == 0 {
+                return Err(ConfigError::Invalid(name.to_string(), "must be > 0".into()));
+            }
+        }
+
+        for (name, opacity) in [
+            ("visible_starts_at", self.visible_starts_at),
+            (
+                "tile_type_opacity_short_grass",
+                self.tile_type_opacity_short_grass,
+            ),
+            (
+                "tile_type_opacity_middle_grass",
+                self.tile_type_opacity_middle_grass,
+            ),
+            (
+                "tile_type_opacity_high_grass",
+                self.tile_type_opacity_high_grass,
+            ),
+            ("tile_type_opacity_dirt", self.tile_type_opacity_dirt),
+            (
+                "tile_type_opacity_concrete",
+                self.tile_type_opacity_concrete,
+            ),
+            ("tile_type_opacity_mud", self.tile_type_opacity_mud),
+            (
+                "tile_type_opacity_brick_wall",
+                self.tile_type_opacity_brick_wall,
+            ),
+            ("tile_type_opacity_trunk", self.tile_type_opacity_trunk),
+            ("tile_type_opacity_water", self.tile_type_opacity_water),
+            (
+                "tile_type_opacity_deep_water",
+                self.tile_type_opacity_deep_water,
+            ),
+            (
+                "tile_type_opacity_underbrush",
+                self.tile_type_opacity_underbrush,
+            ),
+            (
+                "tile_type_opacity_light_underbrush",
+                self.tile_type_opacity_light_underbrush,
+            ),
+            (
+                "tile_type_opacity_middle_wood_logs",
+                self.tile_type_opacity_middle_wood_logs,
+            ),
+            ("tile_type_opacity_hedge", self.tile_type_opacity_hedge),
+            (
+                "tile_type_opacity_middle_rock",
+                self.tile_type_opacity_middle_rock,
+            ),
+        ] {
+            if !opacity.is_finite() || opacity < 0. {
+                return Err(ConfigError::Invalid(
+                    name.to_string(),
+                    "must be >= 0".into(),
+                ));
+            }
+        }
+
+        for (name, modifier) in [
+            (
+                "visibility_idle_standup_modifier",
+                self.visibility_idle_standup_modifier,
+            ),
+            (
+                "visibility_idle_crouch_modifier",
+                self.visibility_idle_crouch_modifier,
+            ),
+            (
+                "visibility_idle_lying_modifier",
+                self.visibility_idle_lying_modifier,
+            ),
+            (
+                "visibility_move_to_modifier",
+                self.visibility_move_to_modifier,
+            ),
+            (
+                "visibility_move_fast_to_modifier",
+                self.visibility_move_fast_to_modifier,
+            ),
+            (
+                "visibility_sneak_to_modifier",
+                self.visibility_sneak_to_modifier,
+            ),
+            (
+                "visibility_defend_modifier",
+                self.visibility_defend_modifier,
+            ),
+            ("visibility_hide_modifier", self.visibility_hide_modifier),
+            (
+                "visibility_in_vehicle_modifier",
+                self.visibility_in_vehicle_modifier,
+            ),
+            (
+                "visibility_suppress_fire_modifier",
+                self.visibility_suppress_fire_modifier,
+            ),
+            (
+                "visibility_engage_modifier",
+                self.visibility_engage_modifier,
+            ),
+            ("visibility_dead_modifier", self.visibility_dead_modifier),
+            (
+                "visibility_unconscious_modifier",
+                self.visibility_unconscious_modifier,
+            ),
+        ] {
+            if !modifier.is_finite() {
+                return Err(ConfigError::Invalid(
+                    name.to_string(),
+                    "must be a number".into(),
+                ));
+            }
+        }
+
+        if self.hide_maximum_rayon.millimeters() < 0 {
+            return Err(ConfigError::Invalid(
+                "hide_maximum_rayon".to_string(),
+                "must be >= 0".into(),
+            ));
+        }
+
+        for (name, rayons) in [
+            (
+                "explosive_direct_death_rayon",
+                &self.explosive_direct_death_rayon,
+            ),
+            (
+                "explosive_regressive_death_rayon",
+                &self.explosive_regressive_death_rayon,
+            ),
+            (
+                "explosive_regressive_injured_rayon",
+                &self.explosive_regressive_injured_rayon,
+            ),
+        ] {
+            for explosive in ExplosiveType::iter() {
+                match rayons.get(&explosive) {
+                    None => {
+                        return Err(ConfigError::Invalid(
+                            name.to_string(),
+                            format!("miss {} explosive", explosive),
+                        ))
+                    }
+                    Some(rayon) if rayon.millimeters() < 0 => {
+                        return Err(ConfigError::Invalid(
+                            name.to_string(),
+                            format!("{} explosive rayon must be >= 0", explosive),
+                        ))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Disk error : {0}")]
+    Disk(#[from] io::Error),
+    #[error("Config file must be .toml or .json : {0}")]
+    UnknownFormat(PathBuf),
+    #[error("Toml read error : {0}")]
+    TomlRead(#[from] toml::de::Error),
+    #[error("Toml write error : {0}")]
+    TomlWrite(#[from] toml::ser::Error),
+    #[error("Json error : {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Override must be written key=value : {0}")]
+    Override(String),
+    #[error("Unknown config key : {0}")]
+    UnknownKey(String),
+    #[error("Invalid {0} : {1}")]
+    Invalid(String, String),
+}
+
+#[cfg(test)]
+pub mod test {
+    use rstest::*;
+
+    use crate::types::Distance;
+
+    use super::*;
+
+    #[rstest]
+    #[case(ConfigFormat::Toml)]
+    #[case(ConfigFormat::Json)]
+    fn dumped_config_can_be_reloaded(#[case] format: ConfigFormat) {
+        // Given
+        let mut config = ServerConfig {
+            visibility_firsts: 3,
+            ..Default::default()
+        };
+        config
+            .explosive_direct_death_rayon
+            .insert(ExplosiveType::FA19241927, Distance::from_meters(42));
+
+        // When
+        let dumped = ServerConfigWriter::to_string(&config, format).unwrap();
+        let reloaded = ServerConfigReader::from_str(&dumped, format).unwrap();
+
+        // Then
+        assert_eq!(reloaded, config);
+    }
+
+    #[rstest]
+    fn missing_fields_are_defaults() {
+        // When
+        let config =
+            ServerConfigReader::from_str("visibility_firsts = 2", ConfigFormat::Toml).unwrap();
+
+        // Then
+        assert_eq!(config.visibility_firsts, 2);
+        assert_eq!(
+            config.flags_update_freq,
+            ServerConfig::default().flags_update_freq
+        );
+    }
+
+    #[rstest]
+    #[case("visibility_firsts=4", None)]
+    #[case("tile_type_opacity_mud = 0.5", None)]
+    #[case("explosive_direct_death_rayon.FA19241927.millimeters=10", None)]
+    #[case("visibility_firstz=4", Some("Unknown config key : visibility_firstz"))]
+    #[case(
+        "visibility_firsts",
+        Some("Override must be written key=value : visibility_firsts")
+    )]
+    #[case(
+        "morale_update_freq=0",
+        Some("Invalid morale_update_freq : must be > 0")
+    )]
+    fn override_config(#[case] expression: &str, #[case] expected_error: Option<&str>) {
+        // Given
+        let mut config = ServerConfig::default();
+
+        // When
+        let result = config.override_(expression);
+
+        // Then
+        assert_eq!(
+            result.err().map(|error| error.to_string()).as_deref(),
+            expected_error
+        );
+    }
+
+    #[rstest]
+    fn unknown_file_field_is_refused() {
+        assert!(ServerConfigReader::from_str("unknown = 1", ConfigFormat::Toml).is_err());
+    }
+}
@@ -1,4 +1,8 @@
-use std::{fs, io, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::PathBuf,
+};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -13,7 +17,7 @@ use crate::{
         Side,
     },
     state::battle::BattleState,
-    types::{SoldierIndex, SoldiersOnBoard, SquadUuid, VehicleIndex, WorldPoint},
+    types::{SoldierIndex, SoldiersOnBoard, SquadUuid, SquadsOwners, VehicleIndex, WorldPoint},
 };
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -21,6 +25,9 @@ pub struct Deployment {
     soldiers: Vec<SoldierDeployment>,
     vehicles: Vec<VehicleDeployment>,
     boards: SoldiersOnBoard,
+    // Deployments without owners let any player of a side control all its squads
+    #[serde(default)]
+    squads_owners: SquadsOwners,
 }
 
 impl Deployment {
@@ -40,6 +47,7 @@ impl Deployment {
             soldiers,
             vehicles,
             boards: battle_state.soldier_on_board().clone(),
+            squads_owners: battle_state.squads_owners().clone(),
         }
     }
 
@@ -54,6 +62,105 @@ impl Deployment {
     pub fn boards(&self) -> &SoldiersOnBoard {
         &self.boards
     }
+
+    pub fn squads_owners(&self) -> &SquadsOwners {
+        &self.squads_owners
+    }
+
+    pub fn sides(&self) -> HashSet<Side> {
+        self.soldiers.iter().map(|soldier| soldier.side()).collect()
+    }
+
+    /// Part of the deployment concerning given side : its soldiers, their squads owners and
+    /// vehicles they are on board
+    pub fn side(&self, side: &Side) -> Self {
+        let soldiers: Vec<SoldierDeployment> = self
+            .soldiers
+            .iter()
+            .filter(|soldier| &soldier.side() == side)
+            .cloned()
+            .collect();
+        let uuids: HashSet<SoldierIndex> = soldiers.iter().map(|soldier| soldier.uuid()).collect();
+        let squads: HashSet<SquadUuid> = soldiers
+            .iter()
+            .map(|soldier| soldier.squad_uuid())
+            .collect();
+        let boards: SoldiersOnBoard = self
+            .boards
+            .iter()
+            .filter(|(soldier_index, _)| uuids.contains(soldier_index))
+            .map(|(soldier_index, board)| (*soldier_index, board.clone()))
+            .collect();
+        let vehicles = self
+            .vehicles
+            .iter()
+            .filter(|vehicle| {
+                boards
+                    .values()
+                    .any(|(vehicle_index, _)| vehicle_index == &vehicle.uuid())
+            })
+            .cloned()
+            .collect();
+        let squads_owners = self
+            .squads_owners
+            .iter()
+            .filter(|(squad_uuid, _)| squads.contains(squad_uuid))
+            .map(|(squad_uuid, owner)| (*squad_uuid, owner.clone()))
+            .collect();
+
+        Self {
+            soldiers,
+            vehicles,
+            boards,
+            squads_owners,
+        }
+    }
+
+    /// Same deployment where soldiers and vehicles indexes start at given ones
+    pub fn shifted(&self, soldiers_start: usize, vehicles_start: usize) -> Self {
+        let soldiers_indexes: HashMap<SoldierIndex, SoldierIndex> = self
+            .soldiers
+            .iter()
+            .enumerate()
+            .map(|(i, soldier)| (soldier.uuid(), SoldierIndex(soldiers_start + i)))
+            .collect();
+        let vehicles_indexes: HashMap<VehicleIndex, VehicleIndex> = self
+            .vehicles
+            .iter()
+            .enumerate()
+            .map(|(i, vehicle)| (vehicle.uuid(), VehicleIndex(vehicles_start + i)))
+            .collect();
+
+        Self {
+            soldiers: self
+                .soldiers
+                .iter()
+                .map(|soldier| SoldierDeployment {
+                    uuid: soldiers_indexes[&soldier.uuid()],
+                    ..soldier.clone()
+                })
+                .collect(),
+            vehicles: self
+                .vehicles
+                .iter()
+                .map(|vehicle| VehicleDeployment {
+                    uuid: vehicles_indexes[&vehicle.uuid()],
+                    ..vehicle.clone()
+                })
+                .collect(),
+            boards: self
+                .boards
+                .iter()
+                .filter_map(|(soldier_index, (vehicle_index, place))| {
+                    Some((
+                        *soldiers_indexes.get(soldier_index)?,
+                        (*vehicles_indexes.get(vehicle_index)?, place.clone()),
+                    ))
+                })
+                .collect(),
+            squads_owners: self.squads_owners.clone(),
+        }
+    }
 }
 
 impl
@@ -74,6 +181,7 @@ impl
             soldiers: value.0,
             vehicles: value.1,
             boards: value.2,
+            squads_owners: SquadsOwners::new(),
         }
     }
 }
@@ -2,6 +2,7 @@ use crate::{
     behavior::{feeling::Feeling, gesture::Gesture, Behavior, Body},
     deployment::SoldierDeployment,
     game::{
+        catalog::Catalog,
         weapon::{Magazine, Weapon},
         Side,
     },
@@ -28,6 +29,8 @@ pub struct Soldier {
     magazines: Vec<Magazine>,
     last_shoot_frame_i: u64,
     last_shot_frame_i: u64,
+    // Direction of the set up crew served weapon
+    weapon_setup: Option<Angle>,
 }
 
 impl Soldier {
@@ -55,6 +58,7 @@ impl Soldier {
             magazines,
             last_shot_frame_i: 0,
             last_shoot_frame_i: 0,
+            weapon_setup: None,
         }
     }
 
@@ -225,6 +229,14 @@ impl Soldier {
         &self.last_shot_frame_i
     }
 
+    pub fn weapon_setup(&self) -> &Option<Angle> {
+        &self.weapon_setup
+    }
+
+    pub fn set_weapon_setup(&mut self, value: Option<Angle>) {
+        self.weapon_setup = value
+    }
+
     pub fn weapon(&self, class: &WeaponClass) -> &Option<Weapon> {
         match class {
             WeaponClass::Main => &self.main_weapon,
@@ -237,13 +249,15 @@ impl Soldier {
         }
     }
 
-    pub fn reload_weapon(&mut self, class: &WeaponClass) {
+    pub fn reload_weapon(&mut self, class: &WeaponClass, catalog: &Catalog) {
         let mut magazines = self.magazines.clone();
         if let Some(weapon) = self.weapon_mut(class) {
             weapon.reload();
             if weapon.magazine().is_none() {
+                let definition = catalog.weapon(weapon.type_());
                 while let Some(magazine) = magazines.pop() {
-                    if weapon.accepted_magazine(&magazine) {
+                    if definition.is_some_and(|definition| definition.accepted_magazine(&magazine))
+                    {
                         weapon.set_magazine(magazine);
                         break;
                     }
@@ -253,9 +267,29 @@ impl Soldier {
         self.magazines = magazines;
     }
 
-    pub fn weapon_shot(&mut self, class: &WeaponClass) {
+    /// Load given magazine (ex. given by a crew served weapon assistant) and chamber a round
+    pub fn load_weapon(&mut self, class: &WeaponClass, magazine: Magazine) {
+        if let Some(weapon) = self.weapon_mut(class) {
+            weapon.set_magazine(magazine);
+            weapon.reload();
+        }
+    }
+
+    pub fn remove_magazine(&mut self, magazine: &Magazine) {
+        if let Some(position) = self.magazines.iter().position(|m| m == magazine) {
+            self.magazines.remove(position);
+        }
+    }
+
+    pub fn weapon_shot(&mut self, class: &WeaponClass, catalog: &Catalog, frame_i: u64) {
         if let Some(weapon) = self.weapon_mut(class) {
             weapon.shot();
+            if let Some(definition) = catalog.weapon(weapon.type_()) {
+                weapon.heat_up(definition, frame_i);
+                if definition.automatic() {
+                    weapon.reload();
+                }
+            }
         }
     }
 
@@ -285,12 +319,17 @@ impl Soldier {
             Behavior::Defend(_) => SoldierAnimationType::LyingDown,
             Behavior::Hide(_) => SoldierAnimationType::LyingDown,
             Behavior::DriveTo(_) => SoldierAnimationType::Idle,
+            Behavior::ReverseTo(_) => SoldierAnimationType::Idle,
             Behavior::RotateTo(_) => SoldierAnimationType::Idle,
             // TODO : Different animation according to death type
             Behavior::Dead => SoldierAnimationType::DeadWithSideBlood,
             Behavior::Unconscious => SoldierAnimationType::LyingDown,
             Behavior::SuppressFire(_) => SoldierAnimationType::LyingDown,
             Behavior::EngageSoldier(_) => SoldierAnimationType::LyingDown,
+            Behavior::TearDownWeapon => SoldierAnimationType::LyingDown,
+            Behavior::IndirectFire(_, _) => SoldierAnimationType::LyingDown,
+            Behavior::Board(_) => SoldierAnimationType::Walking,
+            Behavior::Dismount => SoldierAnimationType::Idle,
         };
 
         let weapon_animation_type = WeaponAnimationType::from(&animation_type);
@@ -1,49 +1,231 @@
+use std::{
+    f32::consts::{FRAC_PI_4, PI, TAU},
+    fmt::Display,
+};
+
 use crate::{
-    config::{TARGET_FPS, VEHICLE_DRIVE_ORIENTATION_TARGET_TOLERANCE_COEFFICIENT},
+    config::{
+        TARGET_FPS, VEHICLE_DISMOUNT_DISTANCE,
+        VEHICLE_DRIVE_ORIENTATION_TARGET_TOLERANCE_COEFFICIENT, VEHICLE_MAIN_TURRET_AIM_TOLERANCE,
+    },
     deployment::VehicleDeployment,
+    game::weapon::WeaponType,
     graphics::vehicle::VehicleGraphicInfos,
+    physics::utils::{distance_to_world, DISTANCE_TO_METERS_COEFFICIENT},
     types::*,
-    utils::WorldShape,
+    utils::{angle, apply_angle_on_point, WorldShape},
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
-pub enum VehicleType {
-    T26,
+/// Vehicle identifier, key of `resources/vehicles.toml` `vehicles` table
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct VehicleType(pub String);
+
+impl Display for VehicleType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VehicleDefinition {
+    name: String,
+    sprites: VehicleSprites,
+    // Chassis physics space width and length (meters)
+    width: f32,
+    length: f32,
+    // Grid tiles count occupied around the vehicle position, used for collisions
+    size: usize,
+    // On board places, taken in this order by boarding soldiers
+    seats: Vec<VehicleSeat>,
+    // Meters per second when driving
+    speed: f32,
+    // Chassis rotation degrees per second
+    rotation_speed: f32,
+    // Main turret traverse degrees per second
+    #[serde(default)]
+    main_turret_rotation_speed: f32,
+    #[serde(default)]
+    armor: Armor,
+    // Weapon given to the `MainTurretGunner` deployed without weapon
+    #[serde(default)]
+    main_gun: Option<WeaponType>,
 }
 
-impl VehicleType {
+impl VehicleDefinition {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn sprites(&self) -> &VehicleSprites {
+        &self.sprites
+    }
+
+    pub fn chassis_physics(&self) -> WorldShape {
+        WorldShape::from_distance(
+            Distance::from_millimeters((self.width * 1000.) as i64),
+            Distance::from_millimeters((self.length * 1000.) as i64),
+        )
+    }
+
+    pub fn size(&self) -> VehicleSize {
+        VehicleSize(self.size)
+    }
+
+    pub fn seats(&self) -> &Vec<VehicleSeat> {
+        &self.seats
+    }
+
     pub fn board_composition(&self) -> BoardComposition {
-        match self {
-            VehicleType::T26 => vec![OnBoardPlace::Driver, OnBoardPlace::MainTurretGunner],
-        }
+        self.seats.iter().map(|seat| seat.place.clone()).collect()
+    }
+
+    /// Scene distance by frame
+    pub fn drive_speed(&self) -> f32 {
+        self.speed / DISTANCE_TO_METERS_COEFFICIENT / TARGET_FPS as f32
     }
 
+    /// Chassis rotation by frame
     pub fn chassis_rotation_speed(&self) -> Angle {
-        match self {
-            VehicleType::T26 => Angle(0.00125),
-        }
+        Angle(self.rotation_speed.to_radians() / TARGET_FPS as f32)
     }
 
-    pub fn _main_turret_rotation_speed(&self) -> Angle {
-        match self {
-            VehicleType::T26 => Angle(0.00255),
+    /// Main turret rotation by frame
+    pub fn main_turret_rotation_speed(&self) -> Angle {
+        Angle(self.main_turret_rotation_speed.to_radians() / TARGET_FPS as f32)
+    }
+
+    pub fn armor(&self) -> &Armor {
+        &self.armor
+    }
+
+    pub fn main_gun(&self) -> &Option<WeaponType> {
+        &self.main_gun
+    }
+
+    /// Describe the first value which would break the simulation
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("width", self.width),
+            ("length", self.length),
+            ("speed", self.speed),
+            ("rotation_speed", self.rotation_speed),
+        ] {
+            if !value.is_finite() || value <= 0. {
+                return Err(format!("{} must be > 0", name));
+            }
+        }
+
+        if self.size == 0 {
+            return Err("size must be > 0".into());
         }
+
+        let composition = self.board_composition();
+        if !composition.contains(&OnBoardPlace::Driver) {
+            return Err("must have a Driver seat".into());
+        }
+        for (i, place) in composition.iter().enumerate() {
+            if composition[..i].contains(place) {
+                return Err(format!("seat {:?} is given twice", place));
+            }
+        }
+
+        let has_turret_place = composition.iter().any(|place| place.in_turret());
+        if has_turret_place && self.sprites.main_turret.is_none() {
+            return Err("turret seats require a main_turret sprite".into());
+        }
+        if self.main_gun.is_some() && !composition.contains(&OnBoardPlace::MainTurretGunner) {
+            return Err("main_gun requires a MainTurretGunner seat".into());
+        }
+
+        Ok(())
     }
+}
 
-    pub fn drive_speed(&self) -> f32 {
-        match self {
-            VehicleType::T26 => 5.0 / TARGET_FPS as f32,
+/// Sprites positions (pixels) in `resources/vehicles.png`, shadow of each sprite is the tile at
+/// its right
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VehicleSprites {
+    pub chassis: VehicleSprite,
+    #[serde(default)]
+    pub main_turret: Option<VehicleSprite>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VehicleSprite {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    // Rotation center decal from chassis sprite center, relative to sprite size
+    #[serde(default)]
+    pub offset: [f32; 2],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VehicleSeat {
+    pub place: OnBoardPlace,
+    // Place decal (pixels) from chassis sprite center
+    pub offset: [f32; 2],
+}
+
+/// Armor thickness (millimeters) of each vehicle facing
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Armor {
+    pub front: f32,
+    pub side: f32,
+    pub rear: f32,
+}
+
+impl Armor {
+    pub fn facing(&self, facing: &VehicleFacing) -> f32 {
+        match facing {
+            VehicleFacing::Front => self.front,
+            VehicleFacing::Side => self.side,
+            VehicleFacing::Rear => self.rear,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VehicleFacing {
+    Front,
+    Side,
+    Rear,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum VehicleDamage {
+    // Can't move anymore (ex. track or engine hit)
+    Immobilized,
+    // Main turret can't traverse anymore
+    TurretJammed,
+    // Can't move nor fire anymore
+    KnockedOut,
+    Burning,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Eq, Hash)]
 pub enum OnBoardPlace {
     Driver,
     MainTurretGunner,
     MainCommandment,
-    Passenger1,
+    Passenger(u8),
+}
+
+impl OnBoardPlace {
+    /// Place is in the turret (others are in the hull)
+    pub fn in_turret(&self) -> bool {
+        match self {
+            OnBoardPlace::MainTurretGunner | OnBoardPlace::MainCommandment => true,
+            OnBoardPlace::Driver | OnBoardPlace::Passenger(_) => false,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -53,6 +235,7 @@ pub struct Vehicle {
     world_point: WorldPoint,
     chassis_orientation: Angle,
     main_turret_relative_orientation: Angle,
+    damages: Vec<VehicleDamage>,
 }
 
 impl Vehicle {
@@ -63,6 +246,7 @@ impl Vehicle {
             world_point,
             chassis_orientation: Angle(0.),
             main_turret_relative_orientation: Angle(0.),
+            damages: vec![],
         }
     }
 
@@ -73,6 +257,7 @@ impl Vehicle {
             world_point: vehicle.world_point(),
             chassis_orientation: *vehicle.chassis_orientation(),
             main_turret_relative_orientation: *vehicle.main_turret_relative_orientation(),
+            damages: vehicle.damages().clone(),
         }
     }
 
@@ -109,6 +294,16 @@ impl Vehicle {
         rounded_chassis_orientation != target_vehicle_orientation
     }
 
+    /// Shortest (signed) chassis rotation to point to given angle
+    pub fn chassis_rotation(&self, angle: &Angle) -> Angle {
+        let difference = (angle.0 - self.chassis_orientation.0).rem_euclid(TAU);
+        if difference > PI {
+            Angle(difference - TAU)
+        } else {
+            Angle(difference)
+        }
+    }
+
     pub fn main_turret_relative_orientation(&self) -> &Angle {
         &self.main_turret_relative_orientation
     }
@@ -117,17 +312,107 @@ impl Vehicle {
         self.chassis_orientation = orientation
     }
 
-    pub fn _set_main_turret_relative_orientation(&mut self, orientation: Angle) {
+    pub fn set_main_turret_relative_orientation(&mut self, orientation: Angle) {
         self.main_turret_relative_orientation = orientation
     }
 
-    pub fn chassis_shape(&self) -> WorldShape {
-        VehicleGraphicInfos::from_type(&self.type_)
+    /// Main turret orientation on the map (chassis orientation plus turret relative one)
+    pub fn main_turret_orientation(&self) -> Angle {
+        self.chassis_orientation + self.main_turret_relative_orientation
+    }
+
+    /// Shortest (signed) turret rotation to point to given angle
+    pub fn main_turret_traverse(&self, angle: &Angle) -> Angle {
+        let difference = (angle.0 - self.main_turret_orientation().0).rem_euclid(TAU);
+        if difference > PI {
+            Angle(difference - TAU)
+        } else {
+            Angle(difference)
+        }
+    }
+
+    pub fn main_turret_aimed(&self, angle: &Angle) -> bool {
+        self.main_turret_traverse(angle).0.abs() <= VEHICLE_MAIN_TURRET_AIM_TOLERANCE
+    }
+
+    pub fn damages(&self) -> &Vec<VehicleDamage> {
+        &self.damages
+    }
+
+    pub fn damaged(&self, damage: &VehicleDamage) -> bool {
+        self.damages.contains(damage)
+    }
+
+    pub fn add_damage(&mut self, damage: VehicleDamage) {
+        if !self.damaged(&damage) {
+            self.damages.push(damage)
+        }
+    }
+
+    pub fn can_move(&self) -> bool {
+        !self.damaged(&VehicleDamage::Immobilized) && !self.damaged(&VehicleDamage::KnockedOut)
+    }
+
+    pub fn can_traverse(&self) -> bool {
+        !self.damaged(&VehicleDamage::TurretJammed) && !self.damaged(&VehicleDamage::KnockedOut)
+    }
+
+    pub fn can_fire(&self) -> bool {
+        !self.damaged(&VehicleDamage::KnockedOut)
+    }
+
+    /// Vehicle facing exposed to something coming from given point
+    pub fn facing(&self, from: &WorldPoint) -> VehicleFacing {
+        let relative =
+            (angle(from, &self.world_point).0 - self.chassis_orientation.0).rem_euclid(TAU);
+        let relative = if relative > PI {
+            TAU - relative
+        } else {
+            relative
+        };
+        if relative <= FRAC_PI_4 {
+            VehicleFacing::Front
+        } else if relative >= PI - FRAC_PI_4 {
+            VehicleFacing::Rear
+        } else {
+            VehicleFacing::Side
+        }
+    }
+
+    pub fn chassis_shape(&self, definition: &VehicleDefinition) -> WorldShape {
+        definition
             .chassis_physics()
             .from_point(self.world_point)
             .centered()
             .rotate(&self.chassis_orientation)
     }
+
+    /// Point, beside the hull on the side of given place, where its occupant stand after
+    /// dismount
+    pub fn dismount_point(
+        &self,
+        definition: &VehicleDefinition,
+        place: &OnBoardPlace,
+    ) -> WorldPoint {
+        let graphic_infos = VehicleGraphicInfos::from_definition(definition);
+        let place_offset = graphic_infos
+            .places()
+            .get(place)
+            .expect("Vehicle place position coherence must be check at startup");
+        let shape = graphic_infos.chassis_physics();
+        let distance = (shape.top_right.x - shape.top_left.x) / 2.
+            + distance_to_world(&Distance::from_meters(VEHICLE_DISMOUNT_DISTANCE));
+        let x = if place_offset.x < 0. {
+            -distance
+        } else {
+            distance
+        };
+        let point = WorldPoint::from_vec2(
+            self.world_point.to_vec2() + Offset::new(x, place_offset.y).to_vec2(),
+        );
+
+        apply_angle_on_point(&point, &self.world_point, &self.chassis_orientation)
+    }
 }
 
 impl From<&VehicleDeployment> for Vehicle {
@@ -0,0 +1,560 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::entity::vehicle::{VehicleDefinition, VehicleType};
+
+use super::weapon::{
+    AmmunitionDefinition, AmmunitionType, Magazine, MagazineDefinition, MagazineType, Weapon,
+    WeaponDefinition, WeaponSound, WeaponSprite, WeaponType,
+};
+
+/// Weapons definitions file, in resources folder
+pub const WEAPONS_FILE: &str = "weapons.toml";
+/// Vehicles definitions file, in resources folder
+pub const VEHICLES_FILE: &str = "vehicles.toml";
+
+/// Game data definitions (ammunitions, magazines, weapons and vehicles) read from resources
+/// files. Battle entities only refer to them by their type (ex. `WeaponType`), so new weapons
+/// or vehicles can be added by editing these files.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Catalog {
+    ammunitions: HashMap<AmmunitionType, AmmunitionDefinition>,
+    magazines: HashMap<MagazineType, MagazineDefinition>,
+    weapons: HashMap<WeaponType, WeaponDefinition>,
+    // Weapons sprites and sounds files, in resources folder
+    weapon_sprites: HashMap<WeaponSprite, String>,
+    weapon_sounds: HashMap<WeaponSound, String>,
+    vehicles: HashMap<VehicleType, VehicleDefinition>,
+}
+
+impl Catalog {
+    pub fn ammunition(&self, type_: &AmmunitionType) -> Option<&AmmunitionDefinition> {
+        self.ammunitions.get(type_)
+    }
+
+    pub fn magazine(&self, type_: &MagazineType) -> Option<&MagazineDefinition> {
+        self.magazines.get(type_)
+    }
+
+    pub fn weapon(&self, type_: &WeaponType) -> Option<&WeaponDefinition> {
+        self.weapons.get(type_)
+    }
+
+    pub fn vehicle(&self, type_: &VehicleType) -> Option<&VehicleDefinition> {
+        self.vehicles.get(type_)
+    }
+
+    pub fn weapon_sprites(&self) -> &HashMap<WeaponSprite, String> {
+        &self.weapon_sprites
+    }
+
+    pub fn weapon_sounds(&self) -> &HashMap<WeaponSound, String> {
+        &self.weapon_sounds
+    }
+
+    pub fn full_magazine(&self, type_: &MagazineType) -> Option<Magazine> {
+        self.magazine(type_)
+            .map(|definition| Magazine::full(type_.clone(), definition))
+    }
+
+    /// Ammunition fired by the weapon : the one of its magazine, or of its first accepted one
+    pub fn weapon_ammunition(&self, weapon: &Weapon) -> Option<AmmunitionType> {
+        let magazine_type = match weapon.magazine() {
+            Some(magazine) => magazine.type_(),
+            None => self.weapon(weapon.type_())?.magazines().first()?,
+        };
+        self.magazine(magazine_type)
+            .map(|magazine| magazine.ammunition().clone())
+    }
+
+    /// Refuse definitions referring to unknown ones or with values which would break the
+    /// simulation
+    pub fn validate(&self) -> Result<(), CatalogError> {
+        for (type_, magazine) in &self.magazines {
+            if self.ammunition(magazine.ammunition()).is_none() {
+                return Err(CatalogError::UnknownReference(
+                    type_.to_string(),
+                    "ammunition",
+                    magazine.ammunition().to_string(),
+                ));
+            }
+            if magazine.capacity() == 0 {
+                return Err(CatalogError::Invalid(
+                    type_.to_string(),
+                    "capacity must be > 0".into(),
+                ));
+            }
+        }
+
+        for (type_, weapon) in &self.weapons {
+            if weapon.magazines().is_empty() {
+                return Err(CatalogError::Invalid(
+                    type_.to_string(),
+                    "must accept at least one magazine".into(),
+                ));
+            }
+            for magazine_type in weapon.magazines() {
+                if self.magazine(magazine_type).is_none() {
+                    return Err(CatalogError::UnknownReference(
+                        type_.to_string(),
+                        "magazine",
+                        magazine_type.to_string(),
+                    ));
+                }
+            }
+            if !self.weapon_sprites.contains_key(weapon.sprite()) {
+                return Err(CatalogError::UnknownReference(
+                    type_.to_string(),
+                    "sprite",
+                    weapon.sprite().to_string(),
+                ));
+            }
+            for sound in weapon.sounds() {
+                if !self.weapon_sounds.contains_key(sound) {
+                    return Err(CatalogError::UnknownReference(
+                        type_.to_string(),
+                        "sound",
+                        sound.to_string(),
+                    ));
+                }
+            }
+            weapon
+                .validate()
+                .map_err(|reason| CatalogError::Invalid(type_.to_string(), reason))?;
+        }
+
+        for (type_, vehicle) in &self.vehicles {
+            if let Some(main_gun) = vehicle.main_gun() {
+                if self.weapon(main_gun).is_none() {
+                    return Err(CatalogError::UnknownReference(
+                        type_.to_string(),
+                        "weapon",
+                        main_gun.to_string(),
+                    ));
+                }
+            }
+            vehicle
+                .validate()
+                .map_err(|reason| CatalogError::Invalid(type_.to_string(), reason))?;
+        }
+
+        Ok(())
+    }
+
+    /// Refuse sprites and sounds files missing from given resources folder
+    pub fn validate_files(&self, resources: &Path) -> Result<(), CatalogError> {
+        for file in self
+            .weapon_sprites
+            .values()
+            .chain(self.weapon_sounds.values())
+        {
+            if !resources.join(file.trim_start_matches('/')).is_file() {
+                return Err(CatalogError::MissingFile(file.clone()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct CatalogReader;
+
+impl CatalogReader {
+    /// Read and validate definitions files of given resources folder
+    pub fn from_resources(resources: &Path) -> Result<Catalog, CatalogError> {
+        // Files fill distinct tables, so they can be read as one
+        let weapons = fs::read_to_string(resources.join(WEAPONS_FILE))?;
+        let vehicles = fs::read_to_string(resources.join(VEHICLES_FILE))?;
+        let catalog = Self::from_toml(&format!("{}\n{}", weapons, vehicles))?;
+        catalog.validate_files(resources)?;
+        Ok(catalog)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Catalog, CatalogError> {
+        // Toml deserializer can't read types (ex. `WeaponType`) map keys, json can
+        let value = toml::from_str::<toml::Value>(content)?;
+        let catalog: Catalog = serde_json::from_value(serde_json::to_value(value)?)?;
+        catalog.validate()?;
+        Ok(catalog)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CatalogError {
+    #[error("Disk error : {0}")]
+    Disk(#[from] io::Error),
+    #[error("Toml read error : {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Definition error : {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("{0} refers to unknown {1} {2}")]
+    UnknownReference(String, &'static str, String),
+    #[error("Invalid {0} : {1}")]
+    Invalid(String, String),
+    #[error("Missing resources file {0}")]
+    MissingFile(String),
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::path::PathBuf;
+
+    use rstest::*;
+
+    use crate::{
+        config::TARGET_FPS,
+        entity::vehicle::OnBoardPlace,
+        game::explosive::ExplosiveType,
+        types::{Angle, VehicleSize},
+    };
+
+    use super::*;
+
+    #[rstest]
+    fn resources_catalog_is_valid() {
+        // Given
+        let resources = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources");
+
+        // When
+        let catalog = CatalogReader::from_resources(&resources).unwrap();
+
+        // Then
+        let weapon = Weapon::new(WeaponType("MosinNagantM1924".to_string()), false, None);
+        assert_eq!(
+            catalog.weapon_ammunition(&weapon),
+            Some(AmmunitionType("x762x54R".to_string()))
+        );
+        assert_eq!(
+            catalog.full_magazine(&MagazineType("MosinNagant".to_string())),
+            Some(Magazine::new(MagazineType("MosinNagant".to_string()), 5))
+        );
+    }
+
+    #[rstest]
+    fn automatic_weapon_heat_up_and_cool_down() {
+        // Given
+        let resources = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources");
+        let catalog = CatalogReader::from_resources(&resources).unwrap();
+        let type_ = WeaponType("MG34".to_string());
+        let definition = catalog.weapon(&type_).unwrap();
+        let magazine = catalog
+            .full_magazine(&MagazineType("MG34Belt".to_string()))
+            .unwrap();
+        let mut weapon = Weapon::new(type_, true, Some(magazine));
+        assert!(definition.automatic());
+
+        // When
+        while weapon.rounds_before_overheat(definition, 0) > 0 {
+            weapon.shot();
+            weapon.heat_up(definition, 0);
+            weapon.set_magazine(Magazine::new(MagazineType("MG34Belt".to_string()), 50));
+            weapon.reload();
+        }
+
+        // Then
+        assert!(weapon.heat(definition, 0) >= 1. - definition.heat_per_shot());
+        let cooling_frames = weapon.cooling_frames(definition, 0);
+        assert!(cooling_frames > 0);
+        assert_eq!(weapon.rounds_before_overheat(definition, cooling_frames), 1);
+    }
+
+    #[rstest]
+    #[case(0., true)]
+    #[case(44., true)]
+    #[case(-44., true)]
+    #[case(46., false)]
+    #[case(180., false)]
+    #[case(315., false)]
+    #[case(355., true)]
+    fn crew_served_weapon_fire_in_its_arc(#[case] degrees: f32, #[case] expected: bool) {
+        // Given
+        let resources = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources");
+        let catalog = CatalogReader::from_resources(&resources).unwrap();
+        let definition = catalog
+            .weapon(&WeaponType("MaximM1910".to_string()))
+            .unwrap();
+        let setup = Angle(1.);
+
+        // When
+        let in_arc = definition.in_firing_arc(&setup, &Angle(setup.0 + degrees.to_radians()));
+
+        // Then
+        assert!(definition.crew_served());
+        assert_eq!(in_arc, expected);
+    }
+
+    #[rstest]
+    fn indirect_fire_weapon_range_flight_and_ranging() {
+        // Given
+        let resources = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources");
+        let catalog = CatalogReader::from_resources(&resources).unwrap();
+
+        // When
+        let definition = catalog
+            .weapon(&WeaponType("BrandtMle2731".to_string()))
+            .unwrap();
+
+        // Then
+        assert!(definition.indirect());
+        assert_eq!(definition.explosive(), Some(&ExplosiveType::FA19241927));
+        assert!(!definition.in_range(50.));
+        assert!(definition.in_range(500.));
+        assert!(!definition.in_range(5000.));
+        assert_eq!(definition.flight_time(500.), 5000);
+        assert_eq!(definition.ranging_factor(0), 4.);
+        assert_eq!(definition.ranging_factor(1), 2.);
+        assert_eq!(definition.ranging_factor(5), 1.);
+        let rifle = catalog
+            .weapon(&WeaponType("MosinNagantM1924".to_string()))
+            .unwrap();
+        assert!(!rifle.indirect());
+        assert!(rifle.in_range(5000.));
+    }
+
+    #[rstest]
+    #[case("T26", 6, true)]
+    #[case("GAZAA", 10, false)]
+    #[case("SdKfz251", 10, false)]
+    fn resources_vehicles(#[case] type_: &str, #[case] seats: usize, #[case] main_turret: bool) {
+        // Given
+        let resources = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources");
+        let catalog = CatalogReader::from_resources(&resources).unwrap();
+
+        // When
+        let definition = catalog.vehicle(&VehicleType(type_.to_string())).unwrap();
+
+        // Then
+        let board_composition = definition.board_composition();
+        assert_eq!(board_composition.len(), seats);
+        assert_eq!(board_composition[0], OnBoardPlace::Driver);
+        assert_eq!(definition.main_gun().is_some(), main_turret);
+        assert_eq!(definition.sprites().main_turret.is_some(), main_turret);
+        assert_eq!(definition.size(), VehicleSize(10));
+    }
+
+    #[rstest]
+    fn vehicle_speeds_are_given_by_frame() {
+        // Given
+        let resources = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources");
+        let catalog = CatalogReader::from_resources(&resources).unwrap();
+
+        // When
+        let definition = catalog.vehicle(&VehicleType("T26".to_string())).unwrap();
+
+        // Then
+        // 1.5 meters by second are 5 scene pixels by second
+        assert!((definition.drive_speed() - 5. / TARGET_FPS as f32).abs() < 0.0001);
+        assert!((definition.chassis_rotation_speed().0 - 0.00125).abs() < 0.00001);
+        assert!((definition.main_turret_rotation_speed().0 - 0.00255).abs() < 0.00001);
+        assert_eq!(definition.armor().front, 15.);
+    }
+
+    #[rstest]
+    #[case(
+        r#"
+        [magazines.Drum]
+        name = "Drum"
+        ammunition = "x762x25"
+        capacity = 71
+        "#,
+        "Drum refers to unknown ammunition x762x25"
+    )]
+    #[case(
+        r#"
+        [ammunitions.x762x25]
+        name = "7.62x25mm"
+        [magazines.Drum]
+        name = "Drum"
+        ammunition = "x762x25"
+        capacity = 0
+        "#,
+        "Invalid Drum : capacity must be > 0"
+    )]
+    #[case(
+        r#"
+        [vehicles.Truck]
+        name = "Truck"
+        width = 9.0
+        length = 22.0
+        size = 10
+        speed = 4.0
+        rotation_speed = 8.0
+        main_gun = "Cannon"
+        seats = [{ place = "Driver", offset = [0.0, 0.0] }]
+        sprites = { chassis = { x = 0.0, y = 0.0, width = 64.0, height = 96.0 } }
+        "#,
+        "Truck refers to unknown weapon Cannon"
+    )]
+    #[case(
+        r#"
+        [vehicles.Truck]
+        name = "Truck"
+        width = 9.0
+        length = 22.0
+        size = 10
+        speed = 4.0
+        rotation_speed = 8.0
+        seats = [{ place = { Passenger = 0 }, offset = [0.0, 0.0] }]
+        sprites = { chassis = { x = 0.0, y = 0.0, width = 64.0, height = 96.0 } }
+        "#,
+        "Invalid Truck : must have a Driver seat"
+    )]
+    #[case(
+        r#"
+        [ammunitions.x762x54R]
+        name = "7.62x54mmR"
+        [magazines.MosinNagant]
+        name = "Mosin Nagant"
+        ammunition = "x762x54R"
+        capacity = 5
+        [weapon_sprites]
+        Riffle = "/weapon_riffle.png"
+        [weapon_sounds]
+        Fire = "/audio/Fire.ogg"
+        [weapons.Riffle]
+        name = "Riffle"
+        magazines = ["MosinNagant"]
+        ok_count_magazines = 5
+        rate_of_fire = 60
+        reload_time = 2000
+        aiming_time = 500
+        dispersion = 0.1
+        effective_range = 300.0
+        range_falloff = 0.5
+        fire_sounds = ["Fire"]
+        reload_sounds = []
+        sprite = "Rifle"
+        "#,
+        "Riffle refers to unknown sprite Rifle"
+    )]
+    #[case(
+        r#"
+        [ammunitions.x762x54R]
+        name = "7.62x54mmR"
+        [magazines.MosinNagant]
+        name = "Mosin Nagant"
+        ammunition = "x762x54R"
+        capacity = 5
+        [weapon_sprites]
+        Riffle = "/weapon_riffle.png"
+        [weapon_sounds]
+        Fire = "/audio/Fire.ogg"
+        [weapons.Riffle]
+        name = "Riffle"
+        magazines = ["MosinNagant"]
+        ok_count_magazines = 5
+        rate_of_fire = 60
+        reload_time = 2000
+        aiming_time = 500
+        dispersion = 0.1
+        effective_range = 300.0
+        range_falloff = 0.5
+        fire_sounds = ["Fire"]
+        reload_sounds = ["Reload"]
+        sprite = "Riffle"
+        "#,
+        "Riffle refers to unknown sound Reload"
+    )]
+    #[case(
+        r#"
+        [ammunitions.x762x54R]
+        name = "7.62x54mmR"
+        [magazines.MosinNagant]
+        name = "Mosin Nagant"
+        ammunition = "x762x54R"
+        capacity = 5
+        [weapon_sprites]
+        Riffle = "/weapon_riffle.png"
+        [weapon_sounds]
+        Fire = "/audio/Fire.ogg"
+        [weapons.Riffle]
+        name = "Riffle"
+        magazines = ["MosinNagant"]
+        ok_count_magazines = 5
+        rate_of_fire = 60
+        reload_time = 2000
+        aiming_time = 500
+        dispersion = 0.1
+        effective_range = 300.0
+        range_falloff = 0.5
+        fire_sounds = ["Fire"]
+        reload_sounds = []
+        sprite = "Riffle"
+        [weapons.Riffle.automatic]
+        burst = 0
+        "#,
+        "Invalid Riffle : automatic burst must be > 0"
+    )]
+    #[case(
+        r#"
+        [ammunitions.x762x54R]
+        name = "7.62x54mmR"
+        [magazines.MosinNagant]
+        name = "Mosin Nagant"
+        ammunition = "x762x54R"
+        capacity = 5
+        [weapon_sprites]
+        Riffle = "/weapon_riffle.png"
+        [weapon_sounds]
+        Fire = "/audio/Fire.ogg"
+        [weapons.Riffle]
+        name = "Riffle"
+        magazines = ["MosinNagant"]
+        ok_count_magazines = 5
+        rate_of_fire = 60
+        reload_time = 2000
+        aiming_time = 500
+        dispersion = 0.1
+        effective_range = 300.0
+        range_falloff = 0.5
+        fire_sounds = ["Fire"]
+        reload_sounds = []
+        sprite = "Riffle"
+        [weapons.Riffle.crew_served]
+        setup_time = 1000
+        teardown_time = 1000
+        firing_arc = 400.0
+        "#,
+        "Invalid Riffle : crew_served firing_arc must be between 0 and 360"
+    )]
+    #[case(
+        r#"
+        [ammunitions.x762x54R]
+        name = "7.62x54mmR"
+        [magazines.MosinNagant]
+        name = "Mosin Nagant"
+        ammunition = "x762x54R"
+        capacity = 5
+        [weapon_sprites]
+        Riffle = "/weapon_riffle.png"
+        [weapon_sounds]
+        Fire = "/audio/Fire.ogg"
+        [weapons.Riffle]
+        name = "Riffle"
+        magazines = ["MosinNagant"]
+        ok_count_magazines = 5
+        rate_of_fire = 60
+        reload_time = 2000
+        aiming_time = 500
+        dispersion = 0.1
+        effective_range = 300.0
+        range_falloff = 0.5
+        fire_sounds = ["Fire"]
+        reload_sounds = []
+        sprite = "Riffle"
+        [weapons.Riffle.indirect]
+        explosive = "FA19241927"
+        round_velocity = 0.0
+        "#,
+        "Invalid Riffle : indirect round_velocity must be > 0"
+    )]
+    fn invalid_catalog_is_refused(#[case] content: &str, #[case] expected_error: &str) {
+        assert_eq!(
+            CatalogReader::from_toml(content).unwrap_err().to_string(),
+            expected_error
+        );
+    }
+}
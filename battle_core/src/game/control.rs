@@ -1,7 +1,7 @@
 use oc_core::spawn::SpawnZoneName;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct MapControl {
     spawn_zone_names: Vec<SpawnZoneName>,
 }
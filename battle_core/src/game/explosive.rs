@@ -1,4 +1,4 @@
-use rand::seq::SliceRandom;
+use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 use strum_macros::Display;
 use strum_macros::EnumIter;
@@ -15,13 +15,11 @@ pub enum ExplosiveType {
 }
 
 impl ExplosiveType {
-    pub fn sounds(&self) -> Vec<Sound> {
+    pub fn sounds<R: Rng>(&self, rng: &mut R) -> Vec<Sound> {
         let pick_from = match self {
             ExplosiveType::FA19241927 => vec![Sound::CannonFire1],
         };
-        let sound = *pick_from
-            .choose(&mut rand::thread_rng())
-            .expect("Must one be chosen");
+        let sound = *pick_from.choose(rng).expect("Must one be chosen");
 
         vec![sound]
     }
@@ -50,4 +48,11 @@ impl ExplosiveType {
             ExplosiveType::FA19241927 => Distance::from_meters(6),
         }
     }
+
+    /// Armor thickness (millimeters) pierced by an explosion on the vehicle
+    pub fn armor_penetration(&self) -> f32 {
+        match self {
+            ExplosiveType::FA19241927 => 12.,
+        }
+    }
 }
@@ -143,6 +143,7 @@ pub mod test {
             1,
             Decor::new(vec![], vec![]),
             flags,
+            0,
         )
     }
 
@@ -1,19 +1,23 @@
 use serde::{Deserialize, Serialize};
 
+pub mod catalog;
 pub mod control;
 pub mod cover;
 pub mod explosive;
 pub mod flag;
 pub mod health;
+pub mod player;
 pub mod posture;
 pub mod squad;
 pub mod weapon;
 
-#[derive(Debug, Copy, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Copy, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub enum Side {
     All,
     A,
     B,
+    // Client role which see everything but can't play
+    Observer,
 }
 impl Side {
     pub fn opposite(&self) -> Side {
@@ -21,6 +25,7 @@ impl Side {
             Side::All => Side::All,
             Side::A => Side::B,
             Side::B => Side::A,
+            Side::Observer => Side::Observer,
         }
     }
 }
@@ -36,6 +41,7 @@ impl FromStr for Side {
         match day {
             "a" => Ok(Side::A),
             "b" => Ok(Side::B),
+            "observer" => Ok(Side::Observer),
             _ => Err("Could not parse a side"),
         }
     }
@@ -47,6 +53,7 @@ impl Display for Side {
             Side::All => f.write_str("All"),
             Side::A => f.write_str("A"),
             Side::B => f.write_str("B"),
+            Side::Observer => f.write_str("Observer"),
         }
     }
 }
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::PlayerName;
+
+use super::Side;
+
+/// Identity of a client : the side it plays and its name (several players can play same side)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct Player {
+    side: Side,
+    name: PlayerName,
+}
+
+impl Player {
+    pub fn new(side: Side, name: PlayerName) -> Self {
+        Self { side, name }
+    }
+
+    pub fn side(&self) -> &Side {
+        &self.side
+    }
+
+    pub fn name(&self) -> &PlayerName {
+        &self.name
+    }
+}
@@ -11,6 +11,7 @@ use crate::{
 };
 
 use super::{
+    catalog::Catalog,
     health::SoldierHealthBuilder,
     weapon::{Magazine, Weapon},
     Side,
@@ -165,8 +166,12 @@ pub struct SquadMemberStatus {
 
 // FIXME : this func is here because AmmunitionReserveStatus, Soldier, etc will have to move
 // into oc_core ...
-fn ammunition_reserve_status(soldier: &Soldier) -> AmmunitionReserveStatus {
-    if let Some(weapon) = soldier.main_weapon() {
+fn ammunition_reserve_status(catalog: &Catalog, soldier: &Soldier) -> AmmunitionReserveStatus {
+    if let Some(weapon) = soldier
+        .main_weapon()
+        .as_ref()
+        .and_then(|weapon| catalog.weapon(weapon.type_()))
+    {
         let ok_magazines_len = soldier
             .magazines()
             .iter()
@@ -196,7 +201,7 @@ impl SquadMemberStatus {
             health: SoldierHealthBuilder::new(soldier).build(),
             main_weapon: soldier.main_weapon().clone(),
             magazines: soldier.magazines().clone(),
-            ammunition_reserve: ammunition_reserve_status(soldier),
+            ammunition_reserve: ammunition_reserve_status(battle_state.catalog(), soldier),
             under_fire_coefficient: (*soldier.under_fire().value() as f32 / UNDER_FIRE_MAX as f32),
             current: CurrentAction::from_soldier(battle_state, squad, soldier),
             leader: battle_state.squad(soldier.squad_uuid()).leader() == soldier.uuid(),
@@ -251,6 +256,9 @@ pub enum CurrentAction {
     Hiding,
     Driving,
     Rotating,
+    SettingUp,
+    TearingDown,
+    Covering,
     // ...
 }
 
@@ -261,26 +269,32 @@ impl CurrentAction {
         soldier: &Soldier,
     ) -> Self {
         match soldier.behavior() {
-            Behavior::MoveTo(_) => Self::Walking,
+            Behavior::MoveTo(_) | Behavior::Board(_) => Self::Walking,
             Behavior::MoveFastTo(_) => Self::Running,
             Behavior::SneakTo(_) => Self::Crawling,
-            Behavior::DriveTo(_) => Self::Driving,
+            Behavior::DriveTo(_) | Behavior::ReverseTo(_) => Self::Driving,
             Behavior::RotateTo(_) => Self::Rotating,
             Behavior::Defend(_) => Self::Defending,
             Behavior::Hide(_) => Self::Hiding,
-            Behavior::SuppressFire(_) => match soldier.gesture() {
+            Behavior::TearDownWeapon => Self::TearingDown,
+            Behavior::Dead | Behavior::Unconscious | Behavior::Dismount => Self::Idle,
+            Behavior::Idle(_)
+            | Behavior::SuppressFire(_)
+            | Behavior::EngageSoldier(_)
+            | Behavior::IndirectFire(_, _) => match soldier.gesture() {
                 Gesture::Idle => Self::Idle,
-                Gesture::Reloading(_, _) => Self::Reloading,
+                Gesture::Reloading(_, _) | Gesture::ReloadingAsAssistant(_, _) => Self::Reloading,
                 Gesture::Aiming(_, _) => Self::Aiming,
-                Gesture::Firing(_, _) => Self::SuppressFiring,
+                Gesture::Firing(_, _) => match soldier.behavior() {
+                    Behavior::SuppressFire(_) | Behavior::IndirectFire(_, _) => {
+                        Self::SuppressFiring
+                    }
+                    _ => Self::TargetFiring,
+                },
+                Gesture::SettingUp(_, _) => Self::SettingUp,
+                Gesture::TearingDown(_) => Self::TearingDown,
+                Gesture::Covering => Self::Covering,
             },
-            Behavior::EngageSoldier(_) => match soldier.gesture() {
-                Gesture::Idle => Self::Idle,
-                Gesture::Reloading(_, _) => Self::Reloading,
-                Gesture::Aiming(_, _) => Self::Aiming,
-                Gesture::Firing(_, _) => Self::TargetFiring,
-            },
-            Behavior::Idle(_) | Behavior::Dead | Behavior::Unconscious => Self::Idle,
         }
     }
 
@@ -298,6 +312,9 @@ impl CurrentAction {
             CurrentAction::Hiding => "hiding",
             CurrentAction::Driving => "driving",
             CurrentAction::Rotating => "rotating",
+            CurrentAction::SettingUp => "setting up",
+            CurrentAction::TearingDown => "tearing down",
+            CurrentAction::Covering => "covering",
         }
     }
 }
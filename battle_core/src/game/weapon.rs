@@ -1,252 +1,575 @@
-use rand::seq::SliceRandom;
+use std::{f32::consts::TAU, fmt::Display};
+
+use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 
-use crate::{
-    audio::Sound,
-    graphics::{cannon_blast::CannonBlastAnimationType, Sprite},
-};
+use crate::{config::TARGET_FPS, game::explosive::ExplosiveType, types::Angle};
+
+/// Ammunition identifier, key of `resources/weapons.toml` `ammunitions` table
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct AmmunitionType(pub String);
+
+/// Magazine identifier, key of `resources/weapons.toml` `magazines` table
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct MagazineType(pub String);
+
+/// Weapon identifier, key of `resources/weapons.toml` `weapons` table
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct WeaponType(pub String);
+
+/// Weapon sprite identifier, key of `resources/weapons.toml` `weapon_sprites` table
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct WeaponSprite(pub String);
+
+/// Weapon sound identifier, key of `resources/weapons.toml` `weapon_sounds` table
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct WeaponSound(pub String);
 
-#[allow(non_camel_case_types)]
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
-pub enum Ammunition {
-    x762x54R,
-    x792x57,
+impl Display for AmmunitionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
-pub enum Magazine {
-    MosinNagant(usize),
-    Mauser(usize),
+impl Display for MagazineType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
-impl Magazine {
-    pub fn name(&self) -> &str {
-        match self {
-            Magazine::MosinNagant(_) => "Mosin Nagant",
-            Magazine::Mauser(_) => "Mauser",
-        }
+impl Display for WeaponType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
     }
+}
 
-    pub fn full(magazine: Self) -> Self {
-        match magazine {
-            Magazine::MosinNagant(_) => Magazine::MosinNagant(5),
-            Magazine::Mauser(_) => Magazine::Mauser(5),
-        }
+impl Display for WeaponSprite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
     }
+}
 
-    pub fn ammunition(&self) -> Ammunition {
-        match self {
-            Magazine::MosinNagant(_) => Ammunition::x762x54R,
-            Magazine::Mauser(_) => Ammunition::x792x57,
-        }
+impl Display for WeaponSound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
     }
+}
 
-    pub fn filled(&self) -> bool {
-        match self {
-            Magazine::MosinNagant(fill) => *fill > 0,
-            Magazine::Mauser(fill) => *fill > 0,
-        }
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AmmunitionDefinition {
+    name: String,
+    // High explosive rounds (ex. tank gun HE shells) explode where they hit
+    #[serde(default)]
+    explosive: Option<ExplosiveType>,
+    // Armor thickness (millimeters) the round can pierce
+    #[serde(default)]
+    penetration: f32,
+}
+
+impl AmmunitionDefinition {
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
-    fn remove_one(&mut self) {
-        match self {
-            Magazine::MosinNagant(fill) => {
-                if *fill > 0 {
-                    *fill -= 1;
-                }
-            }
-            Magazine::Mauser(fill) => {
-                if *fill > 0 {
-                    *fill -= 1;
-                }
-            }
-        }
+    pub fn explosive(&self) -> &Option<ExplosiveType> {
+        &self.explosive
+    }
+
+    pub fn penetration(&self) -> f32 {
+        self.penetration
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum WeaponSprite {
-    Riffle,
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MagazineDefinition {
+    name: String,
+    ammunition: AmmunitionType,
+    capacity: usize,
 }
 
-impl WeaponSprite {
-    pub fn prefix(&self) -> &str {
-        match self {
-            WeaponSprite::Riffle => "/weapon_riffle",
-        }
+impl MagazineDefinition {
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
-    pub fn sprite(&self) -> Box<dyn Sprite> {
-        let animation_type = match self {
-            WeaponSprite::Riffle => CannonBlastAnimationType::RiffleOneShotOnLying,
-        };
-        Box::new(animation_type)
+    pub fn ammunition(&self) -> &AmmunitionType {
+        &self.ammunition
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum GunFireSoundType {
-    MosinNagant,
-    MauserRiffle,
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WeaponDefinition {
+    name: String,
+    // Accepted magazines, the first one give the default ammunition
+    magazines: Vec<MagazineType>,
+    // Magazines count under which ammunition reserve is low
+    ok_count_magazines: usize,
+    // Rounds per minute
+    rate_of_fire: u32,
+    // Milliseconds to replace an empty magazine
+    reload_time: u64,
+    // Milliseconds to aim before each shot
+    aiming_time: u64,
+    // Shot spread (meters) at 100 meters
+    dispersion: f32,
+    // Meters from which the spread grows faster
+    effective_range: f32,
+    // Spread increase ratio for each 100 meters beyond effective range
+    range_falloff: f32,
+    // Meters beyond which the weapon can't fire (no limit when not given)
+    #[serde(default)]
+    maximum_range: Option<f32>,
+    #[serde(default)]
+    automatic: Option<AutomaticDefinition>,
+    #[serde(default)]
+    crew_served: Option<CrewServedDefinition>,
+    #[serde(default)]
+    indirect: Option<IndirectDefinition>,
+    fire_sounds: Vec<WeaponSound>,
+    reload_sounds: Vec<WeaponSound>,
+    sprite: WeaponSprite,
 }
 
-impl GunFireSoundType {
-    pub fn fire_sounds(&self) -> Vec<Sound> {
-        let pick_from = match self {
-            GunFireSoundType::MosinNagant => vec![
-                Sound::MosinNagantFire1,
-                Sound::MosinNagantFire2,
-                Sound::MosinNagantFire3,
-                Sound::MosinNagantFire4,
-                Sound::MosinNagantFire5,
-            ],
-            GunFireSoundType::MauserRiffle => vec![
-                Sound::MauserRiffleFire1,
-                Sound::MauserRiffleFire2,
-                Sound::MauserRiffleFire3,
-            ],
-        };
-        let sound = *pick_from
-            .choose(&mut rand::thread_rng())
-            .expect("Must one be chosen");
+/// Automatic weapons (`[weapons.X.automatic]` table) fire bursts and heat up
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AutomaticDefinition {
+    // Rounds fired by each firing gesture, automatic weapons chamber next round themselves
+    burst: usize,
+    // Barrel heat added by each shot, weapon can't fire once heat reach 1.0
+    #[serde(default)]
+    heat_per_shot: f32,
+    // Barrel heat lost per second
+    #[serde(default)]
+    cooling: f32,
+}
+
+impl AutomaticDefinition {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.burst == 0 {
+            return Err("burst must be > 0".into());
+        }
+
+        for (name, value) in [
+            ("heat_per_shot", self.heat_per_shot),
+            ("cooling", self.cooling),
+        ] {
+            if !value.is_finite() || value < 0. {
+                return Err(format!("{} must be >= 0", name));
+            }
+        }
+        if self.heat_per_shot > 0. && self.cooling == 0. {
+            return Err("cooling must be > 0 when heat_per_shot is".into());
+        }
 
-        vec![sound]
+        Ok(())
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
-pub enum Weapon {
-    // ready bullet, filled magazine
-    MosinNagantM1924(bool, Option<Magazine>),
-    MauserG41(bool, Option<Magazine>),
+/// Crew served weapons (`[weapons.X.crew_served]` table) are set up by a gunner before firing,
+/// and reloaded by an assistant
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CrewServedDefinition {
+    // Milliseconds to set up the weapon before firing
+    setup_time: u64,
+    // Milliseconds to tear down the weapon before moving
+    teardown_time: u64,
+    // Degrees of the arc, centered on set up direction, where set up weapon can fire (0 for
+    // no restriction)
+    #[serde(default)]
+    firing_arc: f32,
 }
 
-impl Weapon {
-    pub fn name(&self) -> &str {
-        match self {
-            Weapon::MosinNagantM1924(_, _) => "Mosin Nagant M1924",
-            Weapon::MauserG41(_, _) => "Mauser G41",
+impl CrewServedDefinition {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.firing_arc.is_finite() || !(0. ..=360.).contains(&self.firing_arc) {
+            return Err("firing_arc must be between 0 and 360".into());
         }
+
+        Ok(())
     }
+}
+
+/// Indirect fire weapons (`[weapons.X.indirect]` table, ex. mortars) fire rounds exploding on
+/// impact, without needing to see their target
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct IndirectDefinition {
+    explosive: ExplosiveType,
+    // Meters under which the weapon can't fire
+    #[serde(default)]
+    minimum_range: f32,
+    // Rounds mean velocity (meters per second), giving their flight time
+    round_velocity: f32,
+    // First round dispersion multiplier, halved for each round fired on the same point until
+    // it reaches 1.0 (ranging-in)
+    #[serde(default = "default_ranging")]
+    ranging: f32,
+}
 
-    pub fn gun_fire_sound_type(&self) -> GunFireSoundType {
-        match self {
-            Weapon::MosinNagantM1924(_, _) => GunFireSoundType::MosinNagant,
-            Weapon::MauserG41(_, _) => GunFireSoundType::MauserRiffle,
+fn default_ranging() -> f32 {
+    1.
+}
+
+impl IndirectDefinition {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.minimum_range.is_finite() || self.minimum_range < 0. {
+            return Err("minimum_range must be >= 0".into());
+        }
+        if !self.round_velocity.is_finite() || self.round_velocity <= 0. {
+            return Err("round_velocity must be > 0".into());
         }
+        if !self.ranging.is_finite() || self.ranging < 1. {
+            return Err("ranging must be >= 1".into());
+        }
+
+        Ok(())
     }
+}
 
-    pub fn reload_sounds(&self) -> Vec<Sound> {
-        let pick_from = match self {
-            Weapon::MosinNagantM1924(_, _) => vec![
-                Sound::MosinNagantReload1,
-                Sound::MosinNagantReload2,
-                Sound::MosinNagantReload3,
-                Sound::MosinNagantReload4,
-            ],
-            Weapon::MauserG41(_, _) => vec![Sound::MauserRiffleReload1, Sound::MauserRiffleReload2],
-        };
-        let sound = *pick_from
-            .choose(&mut rand::thread_rng())
-            .expect("Must one be chosen");
+impl WeaponDefinition {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 
-        vec![sound]
+    pub fn magazines(&self) -> &[MagazineType] {
+        &self.magazines
     }
 
-    pub fn magazine(&self) -> &Option<Magazine> {
-        match self {
-            Weapon::MosinNagantM1924(_, magazine) => magazine,
-            Weapon::MauserG41(_, magazine) => magazine,
+    pub fn accepted_magazine(&self, magazine: &Magazine) -> bool {
+        self.magazines.contains(magazine.type_())
+    }
+
+    pub fn ok_count_magazines(&self) -> usize {
+        self.ok_count_magazines
+    }
+
+    pub fn rate_of_fire(&self) -> u32 {
+        self.rate_of_fire
+    }
+
+    /// Milliseconds between two shots
+    pub fn shot_interval(&self) -> u64 {
+        60_000 / self.rate_of_fire.max(1) as u64
+    }
+
+    pub fn reload_time(&self) -> u64 {
+        self.reload_time
+    }
+
+    pub fn aiming_time(&self) -> u64 {
+        self.aiming_time
+    }
+
+    /// Shot spread (meters) at given distance (meters)
+    pub fn dispersion_at(&self, meters: f32) -> f32 {
+        let beyond = (meters - self.effective_range).max(0.);
+        self.dispersion * (meters / 100.) * (1. + self.range_falloff * beyond / 100.)
+    }
+
+    pub fn burst(&self) -> usize {
+        self.automatic
+            .as_ref()
+            .map_or(1, |automatic| automatic.burst)
+    }
+
+    pub fn automatic(&self) -> bool {
+        self.automatic.is_some()
+    }
+
+    pub fn heat_per_shot(&self) -> f32 {
+        self.automatic
+            .as_ref()
+            .map_or(0., |automatic| automatic.heat_per_shot)
+    }
+
+    pub fn cooling(&self) -> f32 {
+        self.automatic
+            .as_ref()
+            .map_or(0., |automatic| automatic.cooling)
+    }
+
+    pub fn crew_served(&self) -> bool {
+        self.crew_served.is_some()
+    }
+
+    pub fn setup_time(&self) -> u64 {
+        self.crew_served
+            .as_ref()
+            .map_or(0, |crew_served| crew_served.setup_time)
+    }
+
+    pub fn teardown_time(&self) -> u64 {
+        self.crew_served
+            .as_ref()
+            .map_or(0, |crew_served| crew_served.teardown_time)
+    }
+
+    /// Is given angle reachable by the weapon set up in given direction
+    pub fn in_firing_arc(&self, setup: &Angle, angle: &Angle) -> bool {
+        let firing_arc = self
+            .crew_served
+            .as_ref()
+            .map_or(0., |crew_served| crew_served.firing_arc);
+        if firing_arc <= 0. {
+            return true;
         }
+
+        let difference = (angle.0 - setup.0).rem_euclid(TAU);
+        let difference = difference.min(TAU - difference);
+        difference <= firing_arc.to_radians() / 2.
     }
 
-    pub fn accepted_magazine(&self, magazine: &Magazine) -> bool {
-        match self {
-            Weapon::MosinNagantM1924(_, _) => matches!(magazine, Magazine::MosinNagant(_)),
-            Weapon::MauserG41(_, _) => matches!(magazine, Magazine::Mauser(_)),
+    pub fn explosive(&self) -> Option<&ExplosiveType> {
+        self.indirect.as_ref().map(|indirect| &indirect.explosive)
+    }
+
+    /// Indirect fire weapons don't need to see their target
+    pub fn indirect(&self) -> bool {
+        self.indirect.is_some()
+    }
+
+    /// Can the weapon fire at given distance (meters)
+    pub fn in_range(&self, meters: f32) -> bool {
+        let minimum_range = self
+            .indirect
+            .as_ref()
+            .map_or(0., |indirect| indirect.minimum_range);
+        meters >= minimum_range
+            && self
+                .maximum_range
+                .is_none_or(|maximum_range| meters <= maximum_range)
+    }
+
+    /// Milliseconds of an indirect fire round flight to given distance (meters)
+    pub fn flight_time(&self, meters: f32) -> u64 {
+        match &self.indirect {
+            Some(indirect) => (meters / indirect.round_velocity * 1000.) as u64,
+            None => 0,
         }
     }
 
-    pub fn ammunition(&self) -> Ammunition {
-        if let Some(magazine) = self.magazine() {
-            return magazine.ammunition();
+    /// Dispersion multiplier of an indirect fire round after given rounds fired on the same point
+    pub fn ranging_factor(&self, ranging_rounds: u32) -> f32 {
+        let ranging = self
+            .indirect
+            .as_ref()
+            .map_or(1., |indirect| indirect.ranging);
+        (ranging / 2_f32.powi(ranging_rounds.min(32) as i32)).max(1.)
+    }
+
+    pub fn fire_sounds<R: Rng>(&self, rng: &mut R) -> Vec<WeaponSound> {
+        self.fire_sounds.choose(rng).into_iter().cloned().collect()
+    }
+
+    pub fn reload_sounds<R: Rng>(&self, rng: &mut R) -> Vec<WeaponSound> {
+        self.reload_sounds
+            .choose(rng)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Every sound the weapon can play
+    pub fn sounds(&self) -> impl Iterator<Item = &WeaponSound> {
+        self.fire_sounds.iter().chain(self.reload_sounds.iter())
+    }
+
+    pub fn sprite(&self) -> &WeaponSprite {
+        &self.sprite
+    }
+
+    /// Describe the first value which would break the simulation
+    pub fn validate(&self) -> Result<(), String> {
+        if self.rate_of_fire == 0 {
+            return Err("rate_of_fire must be > 0".into());
         }
 
-        // Default value
-        match self {
-            Weapon::MosinNagantM1924(_, _) => Ammunition::x762x54R,
-            Weapon::MauserG41(_, _) => Ammunition::x792x57,
+        for (name, value) in [
+            ("dispersion", self.dispersion),
+            ("effective_range", self.effective_range),
+            ("range_falloff", self.range_falloff),
+        ] {
+            if !value.is_finite() || value < 0. {
+                return Err(format!("{} must be >= 0", name));
+            }
+        }
+
+        if let Some(maximum_range) = self.maximum_range {
+            if !maximum_range.is_finite() || maximum_range < 0. {
+                return Err("maximum_range must be >= 0".into());
+            }
+        }
+
+        if let Some(automatic) = &self.automatic {
+            automatic
+                .validate()
+                .map_err(|reason| format!("automatic {}", reason))?;
+        }
+        if let Some(crew_served) = &self.crew_served {
+            crew_served
+                .validate()
+                .map_err(|reason| format!("crew_served {}", reason))?;
+        }
+        if let Some(indirect) = &self.indirect {
+            indirect
+                .validate()
+                .map_err(|reason| format!("indirect {}", reason))?;
+            if self
+                .maximum_range
+                .is_some_and(|maximum_range| maximum_range < indirect.minimum_range)
+            {
+                return Err("maximum_range must be >= indirect minimum_range".into());
+            }
+        }
+
+        if self.fire_sounds.is_empty() {
+            return Err("fire_sounds must not be empty".into());
         }
+
+        Ok(())
     }
+}
 
-    pub fn can_fire(&self) -> bool {
-        match self {
-            Weapon::MosinNagantM1924(ammunition, _) => *ammunition,
-            Weapon::MauserG41(ammunition, _) => *ammunition,
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Magazine {
+    type_: MagazineType,
+    fill: usize,
+}
+
+impl Magazine {
+    pub fn new(type_: MagazineType, fill: usize) -> Self {
+        Self { type_, fill }
+    }
+
+    pub fn full(type_: MagazineType, definition: &MagazineDefinition) -> Self {
+        Self::new(type_, definition.capacity())
+    }
+
+    pub fn type_(&self) -> &MagazineType {
+        &self.type_
+    }
+
+    pub fn fill(&self) -> usize {
+        self.fill
+    }
+
+    pub fn filled(&self) -> bool {
+        self.fill > 0
+    }
+
+    fn remove_one(&mut self) {
+        if self.fill > 0 {
+            self.fill -= 1;
         }
     }
+}
 
-    pub fn can_reload(&self) -> bool {
-        match self {
-            Weapon::MosinNagantM1924(_, magazine) => {
-                if let Some(magazine) = magazine {
-                    return magazine.filled();
-                }
-            }
-            Weapon::MauserG41(_, magazine) => {
-                if let Some(magazine) = magazine {
-                    return magazine.filled();
-                }
-            }
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Weapon {
+    type_: WeaponType,
+    ready_bullet: bool,
+    magazine: Option<Magazine>,
+    // Barrel heat at `heat_frame_i` (cold when not given, ex. in deployment files)
+    #[serde(default)]
+    heat: f32,
+    #[serde(default)]
+    heat_frame_i: u64,
+}
+
+impl Weapon {
+    pub fn new(type_: WeaponType, ready_bullet: bool, magazine: Option<Magazine>) -> Self {
+        Self {
+            type_,
+            ready_bullet,
+            magazine,
+            heat: 0.,
+            heat_frame_i: 0,
         }
+    }
+
+    pub fn type_(&self) -> &WeaponType {
+        &self.type_
+    }
+
+    pub fn magazine(&self) -> &Option<Magazine> {
+        &self.magazine
+    }
 
-        false
+    pub fn can_fire(&self) -> bool {
+        self.ready_bullet
+    }
+
+    /// Rounds which can be fired without replacing the magazine
+    pub fn rounds(&self) -> usize {
+        self.ready_bullet as usize + self.magazine.as_ref().map_or(0, |magazine| magazine.fill())
+    }
+
+    pub fn can_reload(&self) -> bool {
+        self.magazine
+            .as_ref()
+            .map(|magazine| magazine.filled())
+            .unwrap_or(false)
     }
 
     pub fn reload(&mut self) {
-        match self {
-            Weapon::MosinNagantM1924(ready_bullet, magazine)
-            | Weapon::MauserG41(ready_bullet, magazine) => {
-                if !*ready_bullet {
-                    if let Some(magazine_) = magazine {
-                        if magazine_.filled() {
-                            magazine_.remove_one();
-                            *ready_bullet = true;
-                        }
-
-                        if !magazine_.filled() {
-                            *magazine = None;
-                        }
-                    }
+        if !self.ready_bullet {
+            if let Some(magazine) = &mut self.magazine {
+                if magazine.filled() {
+                    magazine.remove_one();
+                    self.ready_bullet = true;
+                }
+
+                if !magazine.filled() {
+                    self.magazine = None;
                 }
             }
         }
     }
 
     pub fn shot(&mut self) {
-        match self {
-            Weapon::MosinNagantM1924(ready_bullet, _) => *ready_bullet = false,
-            Weapon::MauserG41(ready_bullet, _) => *ready_bullet = false,
-        }
+        self.ready_bullet = false;
     }
 
     pub fn set_magazine(&mut self, new_magazine: Magazine) {
-        match self {
-            Weapon::MosinNagantM1924(_, magazine) => *magazine = Some(new_magazine),
-            Weapon::MauserG41(_, magazine) => *magazine = Some(new_magazine),
-        }
+        self.magazine = Some(new_magazine);
     }
 
-    pub fn ok_count_magazines(&self) -> usize {
-        match self {
-            Weapon::MosinNagantM1924(_, _) => 5,
-            Weapon::MauserG41(_, _) => 5,
+    /// Barrel heat at given frame, decreased since last shot by definition cooling
+    pub fn heat(&self, definition: &WeaponDefinition, frame_i: u64) -> f32 {
+        let elapsed = frame_i.saturating_sub(self.heat_frame_i) as f32 / TARGET_FPS as f32;
+        (self.heat - definition.cooling() * elapsed).max(0.)
+    }
+
+    pub fn heat_up(&mut self, definition: &WeaponDefinition, frame_i: u64) {
+        self.heat = self.heat(definition, frame_i) + definition.heat_per_shot();
+        self.heat_frame_i = frame_i;
+    }
+
+    /// Rounds which can be fired at given frame before the barrel is too hot
+    pub fn rounds_before_overheat(&self, definition: &WeaponDefinition, frame_i: u64) -> usize {
+        if definition.heat_per_shot() == 0. {
+            return usize::MAX;
         }
+
+        ((1. - self.heat(definition, frame_i)) / definition.heat_per_shot()).max(0.) as usize
     }
 
-    pub fn sprite_type(&self) -> WeaponSprite {
-        match self {
-            Weapon::MosinNagantM1924(_, _) | Weapon::MauserG41(_, _) => WeaponSprite::Riffle,
+    /// Frames to wait from given frame before the barrel is cool enough to fire one round
+    pub fn cooling_frames(&self, definition: &WeaponDefinition, frame_i: u64) -> u64 {
+        let excess = self.heat(definition, frame_i) + definition.heat_per_shot() - 1.;
+        if excess <= 0. || definition.cooling() == 0. {
+            return 0;
         }
+
+        (excess / definition.cooling() * TARGET_FPS as f32).ceil() as u64
     }
 }
@@ -1,4 +1,6 @@
-use crate::game::{weapon::WeaponSprite, Side};
+use serde::{Deserialize, Serialize};
+
+use crate::game::Side;
 
 use super::{soldier::SoldierAnimationType, Sprite};
 
@@ -7,21 +9,19 @@ const SPRITE_SHEET_HEIGHT: usize = 192;
 pub const TILE_WIDTH: usize = 32;
 pub const TILE_HEIGHT: usize = 32;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum CannonBlastAnimationType {
     RiffleOneShotOnLying,
 }
 
-impl From<(WeaponSprite, SoldierAnimationType)> for CannonBlastAnimationType {
-    fn from(value: (WeaponSprite, SoldierAnimationType)) -> Self {
+impl From<&SoldierAnimationType> for CannonBlastAnimationType {
+    fn from(value: &SoldierAnimationType) -> Self {
         match value {
-            (WeaponSprite::Riffle, SoldierAnimationType::Idle)
-            | (WeaponSprite::Riffle, SoldierAnimationType::Walking)
-            | (WeaponSprite::Riffle, SoldierAnimationType::Crawling)
-            | (WeaponSprite::Riffle, SoldierAnimationType::LyingDown)
-            | (WeaponSprite::Riffle, SoldierAnimationType::DeadWithSideBlood) => {
-                Self::RiffleOneShotOnLying
-            }
+            SoldierAnimationType::Idle
+            | SoldierAnimationType::Walking
+            | SoldierAnimationType::Crawling
+            | SoldierAnimationType::LyingDown
+            | SoldierAnimationType::DeadWithSideBlood => Self::RiffleOneShotOnLying,
         }
     }
 }
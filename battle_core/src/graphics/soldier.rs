@@ -9,7 +9,7 @@ const SPRITE_SHEET_HEIGHT: usize = 384;
 pub const TILE_WIDTH: usize = 32;
 pub const TILE_HEIGHT: usize = 32;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum SoldierAnimationType {
     Idle,
     Walking,
@@ -1,5 +1,5 @@
 use crate::{
-    entity::vehicle::{OnBoardPlace, VehicleType},
+    entity::vehicle::{VehicleDefinition, VehicleSprite},
     types::*,
     utils::WorldShape,
 };
@@ -7,9 +7,7 @@ use crate::{
 use super::SpriteInfo;
 
 const SPRITE_SHEET_WIDTH: f32 = 256.;
-const SPRITE_SHEET_HEIGHT: f32 = 96.;
-pub const TILE_WIDTH: f32 = 64.;
-pub const TILE_HEIGHT: f32 = 96.;
+const SPRITE_SHEET_HEIGHT: f32 = 192.;
 
 pub struct VehicleGraphicInfos {
     chassis: SpriteInfo,
@@ -24,56 +22,28 @@ pub struct VehicleGraphicInfos {
 }
 
 impl VehicleGraphicInfos {
-    pub fn tank(
-        chassis: SpriteInfo,
-        main_turret: (RelativeOffset, SpriteInfo),
-        places: Vec<(OnBoardPlace, Offset)>,
-        size: VehicleSize,
-        chassis_physics: WorldShape,
-    ) -> Self {
-        let places = places.into_iter().collect();
+    pub fn from_definition(definition: &VehicleDefinition) -> Self {
+        let sprites = definition.sprites();
         Self {
-            chassis,
-            main_turret: Some(main_turret),
-            places,
-            size,
-            chassis_physics,
-        }
-    }
-
-    pub fn from_type(type_: &VehicleType) -> VehicleGraphicInfos {
-        match type_ {
-            VehicleType::T26 => {
-                VehicleGraphicInfos::tank(
-                    SpriteInfo::new(
-                        0.,
-                        0.,
-                        TILE_WIDTH,
-                        TILE_HEIGHT,
-                        SPRITE_SHEET_WIDTH,
-                        SPRITE_SHEET_HEIGHT,
-                    ),
-                    (
-                        RelativeOffset::new(0.05, 0.),
-                        SpriteInfo::new(
-                            128.,
-                            0.,
-                            TILE_WIDTH,
-                            TILE_HEIGHT,
-                            SPRITE_SHEET_WIDTH,
-                            SPRITE_SHEET_HEIGHT,
-                        ),
-                    ),
-                    // TODO : These positions must strictly match with board_composition (check it at startup ?)
-                    vec![
-                        (OnBoardPlace::Driver, Offset::new(8., -16.)),
-                        (OnBoardPlace::MainTurretGunner, Offset::new(-3., 0.)),
-                    ],
-                    // TODO : compute this value according to map grid size (meters)
-                    VehicleSize(10),
-                    WorldShape::from_distance(Distance::from_meters(11), Distance::from_meters(21)),
+            chassis: sprite_info(&sprites.chassis),
+            main_turret: sprites.main_turret.as_ref().map(|sprite| {
+                (
+                    RelativeOffset::new(sprite.offset[0], sprite.offset[1]),
+                    sprite_info(sprite),
                 )
-            }
+            }),
+            places: definition
+                .seats()
+                .iter()
+                .map(|seat| {
+                    (
+                        seat.place.clone(),
+                        Offset::new(seat.offset[0], seat.offset[1]),
+                    )
+                })
+                .collect(),
+            size: definition.size(),
+            chassis_physics: definition.chassis_physics(),
         }
     }
 
@@ -97,3 +67,14 @@ impl VehicleGraphicInfos {
         &self.chassis_physics
     }
 }
+
+fn sprite_info(sprite: &VehicleSprite) -> SpriteInfo {
+    SpriteInfo::new(
+        sprite.x,
+        sprite.y,
+        sprite.width,
+        sprite.height,
+        SPRITE_SHEET_WIDTH,
+        SPRITE_SHEET_HEIGHT,
+    )
+}
@@ -11,6 +11,8 @@ pub mod message;
 pub mod network;
 pub mod order;
 pub mod physics;
+pub mod replay;
+pub mod save;
 pub mod state;
 pub mod sync;
 pub mod types;
@@ -37,6 +37,7 @@ pub struct Map {
     tile_height: u32,
     decor: Decor,
     flags: Vec<Flag>,
+    hash: u64,
 }
 
 impl Map {
@@ -55,6 +56,7 @@ impl Map {
         tile_height: u32,
         decor: Decor,
         flags: Vec<Flag>,
+        hash: u64,
     ) -> Self {
         Self {
             name,
@@ -72,6 +74,7 @@ impl Map {
             tile_height,
             decor,
             flags,
+            hash,
         }
     }
 
@@ -79,6 +82,11 @@ impl Map {
         &self.name
     }
 
+    /// Map file content hash, to ensure client and server use the same map
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
     pub fn background_image_path(&self) -> &PathBuf {
         &self.background_image_path
     }
@@ -189,19 +197,23 @@ impl Map {
                         continue;
                     }
 
-                    if let PathMode::Drive(size) = path_mode {
+                    if let PathMode::Drive(size) | PathMode::Reverse(size) = path_mode {
                         if !self.point_allow_vehicle(&GridPoint::new(new_x, new_y), size) {
                             continue;
                         }
                     }
                 }
 
-                let cost = match path_mode {
-                    PathMode::Walk => next_tile.type_().pedestrian_cost(),
-                    PathMode::Drive(_size) => from.1.angle_cost(&direction),
+                // Node direction is the vehicle orientation after the move
+                let (cost, orientation) = match path_mode {
+                    PathMode::Walk => (next_tile.type_().pedestrian_cost(), direction),
+                    PathMode::Drive(_size) => (from.1.angle_cost(&direction), direction),
+                    PathMode::Reverse(_size) => {
+                        (from.1.reverse_angle_cost(&direction), direction.opposite())
+                    }
                 };
 
-                successors.push(((GridPoint::new(new_x, new_y), direction), cost))
+                successors.push(((GridPoint::new(new_x, new_y), orientation), cost))
             }
         }
 
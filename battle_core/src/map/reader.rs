@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
     fmt::Display,
+    fs,
     path::{Path, PathBuf},
     str::FromStr,
     sync::Arc,
@@ -87,6 +88,7 @@ pub struct MapReader {
     resources: PathBuf,
     name: String,
     map: TiledMap,
+    hash: u64,
 }
 
 impl MapReader {
@@ -103,11 +105,21 @@ impl MapReader {
                 )))
             }
         };
+        let hash = match fs::read(&map_file_path) {
+            Ok(bytes) => content_hash(&bytes),
+            Err(error) => {
+                return Result::Err(MapReaderError::MapNotFound(format!(
+                    "Failed to read map {} : {}",
+                    map_file_path, error
+                )))
+            }
+        };
 
         Ok(Self {
             resources: resources.to_path_buf(),
             name: name.to_string(),
             map,
+            hash,
         })
     }
 
@@ -581,6 +593,14 @@ impl MapReader {
             tile_height,
             decor,
             flags,
+            self.hash,
         ))
     }
 }
+
+/// FNV-1a hash : must give same result on any platform and build
+fn content_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
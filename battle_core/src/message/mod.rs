@@ -3,14 +3,18 @@ use serde::{Deserialize, Serialize};
 use crate::{
     config::ChangeConfigMessage,
     deployment::Deployment,
-    game::control::MapControl,
+    game::{control::MapControl, player::Player, Side},
     state::{battle::message::BattleStateMessage, client::ClientStateMessage},
     sync::BattleStateCopy,
 };
 
-use self::network::NetworkMessage;
+use self::{
+    network::{Handshake, NetworkMessage},
+    rejection::InputRejection,
+};
 
 pub mod network;
+pub mod rejection;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum Message {
@@ -23,16 +27,36 @@ pub enum Message {
 pub enum InputMessage {
     LoadDeployment(Deployment),
     LoadControl((MapControl, MapControl)),
-    RequireCompleteSync,
-    SetBattleState(BattleStateCopy),
+    // Requester side, which receive a complete state filtered for it
+    RequireCompleteSync(Side),
+    SetBattleState(Box<BattleStateCopy>),
     BattleState(BattleStateMessage),
     ChangeConfig(ChangeConfigMessage),
+    // Ask server to send the save of its battle state to given side
+    RequestSave(Side),
+    // Client introduction, answered by the network server (see `network::server`)
+    Handshake(Handshake),
+    // Messages sent by a remote player, only accepted if this player can send them
+    FromPlayer(Player, Vec<InputMessage>),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum OutputMessage {
-    LoadFromCopy(BattleStateCopy),
+    LoadFromCopy(Box<BattleStateCopy>),
     BattleState(BattleStateMessage),
     ClientState(ClientStateMessage),
     ChangeConfig(ChangeConfigMessage),
+    // Save file content (see `crate::save`)
+    SaveData(Vec<u8>),
+    // A message sent by this player has been refused by the server
+    InputRejected(Player, InputRejection),
+}
+
+/// Output messages destined to given side (messages destined to all sides included)
+pub fn side_outputs(outputs: &[(Side, OutputMessage)], side: &Side) -> Vec<OutputMessage> {
+    outputs
+        .iter()
+        .filter(|(side_, _)| side_ == &Side::All || side_ == side)
+        .map(|(_, message)| message.clone())
+        .collect()
 }
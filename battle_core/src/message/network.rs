@@ -1,6 +1,181 @@
+use std::fmt::Display;
+
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    game::{player::Player, Side},
+    types::{PlayerName, PlayerToken},
+};
+
+/// Must be incremented each time messages exchanged between client and server change
+pub const PROTOCOL_VERSION: u32 = 2;
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub enum NetworkMessage {
     Acknowledge,
+    // Token to join to next requests
+    HandshakeAccepted(PlayerToken),
+    HandshakeRejected(HandshakeRejection),
+    // Request token is not one of current match players (ex. match closed by server),
+    // client must handshake again
+    NotJoined,
+}
+
+/// First message sent by a client to ensure it can play with the server battle
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Handshake {
+    protocol_version: u32,
+    map_name: String,
+    map_hash: u64,
+    player: Player,
+    // Token previously given by the server, when joining again after a lost connection
+    token: Option<PlayerToken>,
+}
+
+impl Handshake {
+    pub fn new(map_name: &str, map_hash: u64, player: Player) -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            map_name: map_name.to_string(),
+            map_hash,
+            player,
+            token: None,
+        }
+    }
+
+    pub fn rejoin(mut self, token: PlayerToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    pub fn protocol_version(&self) -> u32 {
+        self.protocol_version
+    }
+
+    pub fn map_name(&self) -> &str {
+        &self.map_name
+    }
+
+    pub fn map_hash(&self) -> u64 {
+        self.map_hash
+    }
+
+    pub fn player(&self) -> &Player {
+        &self.player
+    }
+
+    pub fn side(&self) -> &Side {
+        self.player.side()
+    }
+
+    pub fn token(&self) -> Option<&PlayerToken> {
+        self.token.as_ref()
+    }
+
+    /// Check this (client) handshake against server protocol and map
+    pub fn check(&self, map_name: &str, map_hash: u64) -> Result<(), HandshakeRejection> {
+        if self.protocol_version != PROTOCOL_VERSION {
+            return Err(HandshakeRejection::ProtocolVersion(
+                PROTOCOL_VERSION,
+                self.protocol_version,
+            ));
+        }
+
+        if self.map_name != map_name {
+            return Err(HandshakeRejection::MapName(
+                map_name.to_string(),
+                self.map_name.clone(),
+            ));
+        }
+
+        if self.map_hash != map_hash {
+            return Err(HandshakeRejection::MapHash(map_name.to_string()));
+        }
+
+        if self.side() == &Side::All {
+            return Err(HandshakeRejection::Side(*self.side()));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub enum HandshakeRejection {
+    // Server and client protocol versions
+    ProtocolVersion(u32, u32),
+    // Server and client map names
+    MapName(String, String),
+    // Client map content differs from the server one
+    MapHash(String),
+    // Client map can't be played on this server
+    Map(String),
+    // Requested side can't be played
+    Side(Side),
+    // A player with this name already joined the match
+    PlayerName(PlayerName),
+    // Server can't register players (internal error)
+    Unavailable,
+}
+
+impl Display for HandshakeRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandshakeRejection::ProtocolVersion(server, client) => f.write_str(&format!(
+                "Server protocol version is {} but client one is {}",
+                server, client
+            )),
+            HandshakeRejection::MapName(server, client) => f.write_str(&format!(
+                "Server map is {} but client one is {}",
+                server, client
+            )),
+            HandshakeRejection::MapHash(map_name) => f.write_str(&format!(
+                "Client map {} differs from the server one",
+                map_name
+            )),
+            HandshakeRejection::Map(map_name) => {
+                f.write_str(&format!("Map {} is not available on this server", map_name))
+            }
+            HandshakeRejection::Side(side) => {
+                f.write_str(&format!("Side {} can't be played", side))
+            }
+            HandshakeRejection::PlayerName(name) => {
+                f.write_str(&format!("Player {} already joined the match", name))
+            }
+            HandshakeRejection::Unavailable => f.write_str("Server can't accept players"),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use rstest::*;
+
+    use super::*;
+
+    fn player(side: Side) -> Player {
+        Player::new(side, PlayerName("Player1".to_string()))
+    }
+
+    #[rstest]
+    #[case(Handshake::new("Map1", 42, player(Side::A)), None)]
+    #[case(
+        Handshake::new("Map2", 42, player(Side::A)),
+        Some(HandshakeRejection::MapName("Map1".to_string(), "Map2".to_string()))
+    )]
+    #[case(
+        Handshake::new("Map1", 43, player(Side::B)),
+        Some(HandshakeRejection::MapHash("Map1".to_string()))
+    )]
+    #[case(
+        Handshake::new("Map1", 42, player(Side::All)),
+        Some(HandshakeRejection::Side(Side::All))
+    )]
+    #[case(
+        Handshake { protocol_version: 0, ..Handshake::new("Map1", 42, player(Side::A)) },
+        Some(HandshakeRejection::ProtocolVersion(PROTOCOL_VERSION, 0))
+    )]
+    fn check_handshake(#[case] handshake: Handshake, #[case] expected: Option<HandshakeRejection>) {
+        assert_eq!(handshake.check("Map1", 42).err(), expected);
+    }
 }
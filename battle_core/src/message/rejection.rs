@@ -0,0 +1,54 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    state::battle::phase::Phase,
+    types::{SoldierIndex, SquadUuid, VehicleIndex},
+};
+
+/// Why the server refused a message sent by a player
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub enum InputRejection {
+    // Soldier don't exist or is not controlled by the player
+    Soldier(SoldierIndex),
+    // Vehicle don't exist or its crew is not controlled by the player
+    Vehicle(VehicleIndex),
+    // Squad don't exist or is not controlled by the player
+    Squad(SquadUuid),
+    // Position is out of player side spawn zones
+    SpawnZone,
+    // Behavior can't be set during placement
+    Behavior,
+    // Message can't be sent during this phase
+    Phase(Phase),
+    // Message concern the other side
+    Side,
+    // Players are never allowed to send this message
+    Forbidden,
+}
+
+impl Display for InputRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputRejection::Soldier(soldier_index) => f.write_str(&format!(
+                "Soldier {} is not controlled by player",
+                soldier_index
+            )),
+            InputRejection::Vehicle(vehicle_index) => f.write_str(&format!(
+                "Vehicle {} is not controlled by player",
+                vehicle_index
+            )),
+            InputRejection::Squad(squad_uuid) => {
+                f.write_str(&format!("Squad {} is not controlled by player", squad_uuid))
+            }
+            InputRejection::SpawnZone => f.write_str("Position is out of side spawn zones"),
+            InputRejection::Behavior => f.write_str("Behavior not allowed during placement"),
+            InputRejection::Phase(phase) => {
+                f.write_str(&format!("Not allowed during {} phase", phase))
+            }
+            InputRejection::Side => f.write_str("Concern the other side"),
+            InputRejection::Forbidden => f.write_str("Not allowed for players"),
+        }
+    }
+}
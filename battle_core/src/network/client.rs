@@ -1,17 +1,33 @@
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
 use serde::{Deserialize, Serialize};
 
 use std::{
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
-    thread,
+    thread::{self, JoinHandle},
+    time::Duration,
 };
 
-use crate::message::{InputMessage, OutputMessage};
+use crate::{
+    game::player::Player,
+    map::Map,
+    message::{
+        network::{Handshake, NetworkMessage},
+        InputMessage, Message, OutputMessage,
+    },
+    types::PlayerToken,
+};
+
+use super::{error::NetworkError, side_topic, RECEIVE_TIMEOUT};
 
-use super::error::NetworkError;
+/// Server response waiting duration (milliseconds), server is considered as lost after it
+const REPLY_TIMEOUT: i32 = 2000;
+/// Without messages to send during this duration, an empty request is sent to check server
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+/// Waiting duration between two reconnection attempts
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Network exchange logic
 /// Important note : zmq PUB socket have a limited buffer size,
@@ -19,11 +35,15 @@ use super::error::NetworkError;
 pub struct Client {
     req_address: String,
     sub_address: String,
+    player: Player,
     input_sender: Sender<Vec<InputMessage>>,
     input_receiver: Receiver<Vec<InputMessage>>,
     output_sender: Sender<Vec<OutputMessage>>,
     output_receiver: Receiver<Vec<OutputMessage>>,
     sync_required: Arc<AtomicBool>,
+    stop_required: Arc<AtomicBool>,
+    stats: Arc<ClientStats>,
+    handles: Vec<JoinHandle<()>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -32,34 +52,134 @@ struct Envelope {
     messages: Vec<OutputMessage>,
 }
 
-// TODO : When server/client is closing : end threads properly
+#[derive(Debug, Serialize)]
+struct Request<'a> {
+    token: Option<&'a PlayerToken>,
+    messages: &'a [InputMessage],
+}
+
+/// Connection state and counters about server messages received by client
+#[derive(Debug, Default)]
+pub struct ClientStats {
+    connected: AtomicBool,
+    reconnections: AtomicU64,
+    received: AtomicU64,
+    lost: AtomicU64,
+    sync_requests: AtomicU64,
+}
+
+impl ClientStats {
+    /// False when server don't respond anymore (client is trying to reconnect)
+    pub fn connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// Successful reconnections after server has been lost
+    pub fn reconnections(&self) -> u64 {
+        self.reconnections.load(Ordering::Relaxed)
+    }
+
+    /// Received envelopes (group of messages)
+    pub fn received(&self) -> u64 {
+        self.received.load(Ordering::Relaxed)
+    }
+
+    /// Envelopes sent by server but never received
+    pub fn lost(&self) -> u64 {
+        self.lost.load(Ordering::Relaxed)
+    }
+
+    /// Complete sync required because of lost envelopes
+    pub fn sync_requests(&self) -> u64 {
+        self.sync_requests.load(Ordering::Relaxed)
+    }
+
+    pub fn debug_lines(&self) -> Vec<(String, String)> {
+        vec![
+            ("Connected".to_string(), self.connected().to_string()),
+            (
+                "Reconnections".to_string(),
+                self.reconnections().to_string(),
+            ),
+            (
+                "Received envelopes".to_string(),
+                self.received().to_string(),
+            ),
+            ("Lost envelopes".to_string(), self.lost().to_string()),
+            (
+                "Sync requests".to_string(),
+                self.sync_requests().to_string(),
+            ),
+        ]
+    }
+}
+
+/// Count of envelopes lost between the last received id and the current one. First received
+/// envelope is not considered (client can connect after server started to publish).
+fn lost_envelopes(last_id: u64, id: u64) -> u64 {
+    if last_id == 0 {
+        return 0;
+    }
+
+    id.saturating_sub(last_id + 1)
+}
+
 impl Client {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         req_address: String,
         sub_address: String,
+        player: Player,
         input_sender: Sender<Vec<InputMessage>>,
         input_receiver: Receiver<Vec<InputMessage>>,
         output_sender: Sender<Vec<OutputMessage>>,
         output_receiver: Receiver<Vec<OutputMessage>>,
         sync_required: Arc<AtomicBool>,
+        stop_required: Arc<AtomicBool>,
     ) -> Self {
         Self {
             req_address,
             sub_address,
+            player,
             input_sender,
             input_receiver,
             output_sender,
             output_receiver,
             sync_required,
+            stop_required,
+            stats: Arc::new(ClientStats::default()),
+            handles: vec![],
         }
     }
 
-    pub fn connect(&mut self) -> Result<(), NetworkError> {
-        self.start_req()?;
-        self.start_sub()?;
+    pub fn stats(&self) -> Arc<ClientStats> {
+        self.stats.clone()
+    }
+
+    /// Introduce client to server (which can reject it) then start to exchange messages
+    pub fn connect(&mut self, map: &Map) -> Result<(), NetworkError> {
+        let handshake = Handshake::new(map.name(), map.hash(), self.player.clone());
+        let socket = req_socket(&self.req_address)?;
+        let token = self::handshake(&socket, &handshake)?;
+        self.stats.connected.store(true, Ordering::Relaxed);
+
+        let req_handle = self.start_req(socket, handshake, token)?;
+        let sub_handle = self.start_sub()?;
+        self.handles.extend([req_handle, sub_handle]);
         Ok(())
     }
 
+    /// Stop threads and wait for them
+    pub fn shutdown(&mut self) {
+        self.stop_required.store(true, Ordering::Relaxed);
+        for handle in self.handles.drain(..) {
+            if handle.join().is_err() {
+                println!("Error while waiting client thread end");
+            }
+        }
+        self.stats.connected.store(false, Ordering::Relaxed);
+    }
+
     /// Return received messages from remote :
     ///  - As server : messages from clients
     ///  - As client : messages from server
@@ -71,57 +191,85 @@ impl Client {
         messages
     }
 
-    fn start_req(&self) -> Result<(), NetworkError> {
+    fn start_req(
+        &self,
+        socket: zmq::Socket,
+        handshake: Handshake,
+        token: PlayerToken,
+    ) -> Result<JoinHandle<()>, NetworkError> {
         let thread_send_receiver = self.input_receiver.clone();
         let server_rep_address = self.req_address.clone();
+        let sync_required_ = self.sync_required.clone();
+        let stop_required_ = self.stop_required.clone();
+        let stats = self.stats.clone();
 
-        let zmq_context = zmq::Context::new();
-        let socket = zmq_context.socket(zmq::REQ)?;
-        socket.connect(&server_rep_address)?;
-
-        thread::Builder::new()
+        let handle = thread::Builder::new()
             .name("client_req".to_string())
             .spawn(move || {
-                loop {
-                    // Wait messages to send
-                    let messages: Vec<InputMessage> = thread_send_receiver
-                        .recv()
-                        .expect("Channel was closed when try to receive messages to send");
-
-                    // Encode messages to send
-                    let messages_bytes = match bincode::serialize(&messages) {
-                        Ok(messages_bytes) => messages_bytes,
-                        Err(error) => {
-                            println!("Error while encoding messages to send : {}", error);
-                            continue;
+                let mut socket = socket;
+                let mut token = token;
+
+                while !stop_required_.load(Ordering::Relaxed) {
+                    // Wait messages to send (or send an empty request to check server is alive)
+                    let messages: Vec<InputMessage> =
+                        match thread_send_receiver.recv_timeout(HEARTBEAT_INTERVAL) {
+                            Ok(messages) => messages,
+                            Err(RecvTimeoutError::Timeout) => vec![],
+                            Err(RecvTimeoutError::Disconnected) => break,
+                        };
+
+                    // Send messages to server. The server ACK is only required here.
+                    let (rejoin, refused) = match request(&socket, Some(&token), &messages) {
+                        // Server closed the match this client was part of, join the next one
+                        // (messages have been ignored, so send them again once joined)
+                        Ok(Message::Network(NetworkMessage::NotJoined)) => {
+                            println!("Match left by server, join the next one");
+                            (handshake.clone(), messages)
                         }
-                    };
+                        Ok(_) => continue,
+                        Err(error) => {
+                            println!("Connection with server lost : {}", error);
+                            stats.connected.store(false, Ordering::Relaxed);
 
-                    // Send messages to server
-                    if let Err(error) = socket.send(messages_bytes, 0) {
-                        println!("Error while sending messages : {}", error);
-                        // Don't expect a response if send error
-                        continue;
+                            // Same token is given back if server still know this client
+                            (handshake.clone().rejoin(token), vec![])
+                        }
                     };
 
-                    let _response = match socket.recv_bytes(0) {
-                        Ok(response) => response,
+                    (socket, token) = match reconnect(&server_rep_address, &rejoin, &stop_required_)
+                    {
+                        Ok(Some(connection)) => connection,
+                        Ok(None) => break,
                         Err(error) => {
-                            println!("Error while receiving server REP : {}", error);
-                            // Don't expect a response if send error
-                            continue;
+                            println!("Error while reconnecting : {}", error);
+                            break;
                         }
                     };
+                    stats.connected.store(true, Ordering::Relaxed);
+                    stats.reconnections.fetch_add(1, Ordering::Relaxed);
 
-                    // Don't check the response content. The server ACK is only required here.
+                    // Server state can be another one (ex. restarted server or new match)
+                    sync_required_.swap(true, Ordering::Relaxed);
+                    let messages = [
+                        refused,
+                        vec![InputMessage::RequireCompleteSync(
+                            *handshake.player().side(),
+                        )],
+                    ]
+                    .concat();
+                    if let Err(error) = request(&socket, Some(&token), &messages) {
+                        println!("Error while requiring sync : {}", error);
+                    }
                 }
+
+                println!("Client REQ finished");
             })
-            .unwrap();
+            .map_err(|error| NetworkError::NetworkError(error.to_string()))?;
 
-        Ok(())
+        Ok(handle)
     }
 
-    fn start_sub(&self) -> Result<(), NetworkError> {
+    fn start_sub(&self) -> Result<JoinHandle<()>, NetworkError> {
         let thread_receive_sender = self.output_sender.clone();
         let thread_input_sender = self.input_sender.clone();
         let server_pub_address = self.sub_address.clone();
@@ -130,17 +278,26 @@ impl Client {
         let zmq_context = zmq::Context::new();
         let socket = zmq_context.socket(zmq::SUB)?;
         socket.connect(&server_pub_address)?;
-        // TODO : subscribe with client ID and ALL (to receive all messages except global sync of other clients)
-        socket.set_subscribe(b"")?;
+        socket.set_rcvtimeo(RECEIVE_TIMEOUT)?;
+        // TODO : subscribe with client ID (to receive all messages except global sync of other clients)
+        socket.set_subscribe(side_topic(self.player.side()))?;
 
+        let side = *self.player.side();
         let sync_required_ = self.sync_required.clone();
-        thread::Builder::new()
+        let stop_required_ = self.stop_required.clone();
+        let stats = self.stats.clone();
+        let handle = thread::Builder::new()
             .name("client_sub".to_string())
             .spawn(move || {
-                loop {
-                    // Receive server messages
-                    let envelope_bytes = match socket.recv_bytes(0) {
-                        Ok(envelope_bytes) => envelope_bytes,
+                while !stop_required_.load(Ordering::Relaxed) {
+                    // Receive server messages (first part is the topic)
+                    let envelope_bytes = match socket.recv_multipart(0) {
+                        Ok(mut parts) => match parts.pop() {
+                            Some(envelope_bytes) => envelope_bytes,
+                            None => continue,
+                        },
+                        // Nothing received, give a chance to check stop requirement
+                        Err(zmq::Error::EAGAIN) => continue,
                         Err(error) => {
                             println!("Error while receiving server messages : {}", error);
                             // Waiting again if receive error
@@ -158,25 +315,122 @@ impl Client {
                     };
 
                     // Send through channel the decoded messages
-                    thread_receive_sender
-                        .send(envelope.messages)
-                        .expect("Channel was closed when try to send received messages");
+                    if thread_receive_sender.send(envelope.messages).is_err() {
+                        println!("Channel was closed when try to send received messages");
+                        break;
+                    }
 
                     // Check no message(s) was lost, if yes, require sync from server
-                    if last_counter != 0 && last_counter + 1 != envelope.id {
-                        println!("WARNING :: Network :: message(s) lost, require global Sync");
+                    stats.received.fetch_add(1, Ordering::Relaxed);
+                    let lost = lost_envelopes(last_counter, envelope.id);
+                    if lost > 0 {
+                        println!(
+                            "WARNING :: Network :: {} message(s) lost, require global Sync",
+                            lost
+                        );
+                        stats.lost.fetch_add(lost, Ordering::Relaxed);
+                        stats.sync_requests.fetch_add(1, Ordering::Relaxed);
                         sync_required_.swap(true, Ordering::Relaxed);
-                        thread_input_sender
-                            .send(vec![InputMessage::RequireCompleteSync])
-                            .expect("Channel was closed when try to send server sync requirement");
+                        if thread_input_sender
+                            .send(vec![InputMessage::RequireCompleteSync(side)])
+                            .is_err()
+                        {
+                            println!("Channel was closed when try to send server sync requirement");
+                            break;
+                        }
                     }
 
                     // Update the last counter
                     last_counter = envelope.id;
                 }
+
+                println!("Client SUB finished");
             })
-            .unwrap();
+            .map_err(|error| NetworkError::NetworkError(error.to_string()))?;
 
-        Ok(())
+        Ok(handle)
+    }
+}
+
+fn req_socket(address: &str) -> Result<zmq::Socket, NetworkError> {
+    let zmq_context = zmq::Context::new();
+    let socket = zmq_context.socket(zmq::REQ)?;
+    // Don't keep unsent messages of a lost connection
+    socket.set_linger(0)?;
+    socket.set_rcvtimeo(REPLY_TIMEOUT)?;
+    socket.connect(address)?;
+    Ok(socket)
+}
+
+/// Send messages and wait server response. On error, socket can't be used anymore.
+fn request(
+    socket: &zmq::Socket,
+    token: Option<&PlayerToken>,
+    messages: &[InputMessage],
+) -> Result<Message, NetworkError> {
+    let request = Request { token, messages };
+    let messages_bytes =
+        bincode::serialize(&request).map_err(|error| NetworkError::SendError(error.to_string()))?;
+    socket
+        .send(messages_bytes, 0)
+        .map_err(|error| NetworkError::SendError(error.to_string()))?;
+    let response_bytes = socket
+        .recv_bytes(0)
+        .map_err(|error| NetworkError::ReceiveError(error.to_string()))?;
+
+    bincode::deserialize(&response_bytes)
+        .map_err(|error| NetworkError::ReceiveError(error.to_string()))
+}
+
+/// Introduce client to server and return the token to join to next requests
+fn handshake(socket: &zmq::Socket, handshake: &Handshake) -> Result<PlayerToken, NetworkError> {
+    match request(socket, None, &[InputMessage::Handshake(handshake.clone())])? {
+        Message::Network(NetworkMessage::HandshakeAccepted(token)) => Ok(token),
+        Message::Network(NetworkMessage::HandshakeRejected(rejection)) => {
+            Err(NetworkError::Rejected(rejection))
+        }
+        message => Err(NetworkError::ReceiveError(format!(
+            "Unexpected handshake response : {:?}",
+            message
+        ))),
+    }
+}
+
+/// Try to handshake with server until it accepts (or rejects) client. Return None if
+/// stop is required before.
+fn reconnect(
+    address: &str,
+    handshake: &Handshake,
+    stop_required: &AtomicBool,
+) -> Result<Option<(zmq::Socket, PlayerToken)>, NetworkError> {
+    while !stop_required.load(Ordering::Relaxed) {
+        let socket = req_socket(address)?;
+        match self::handshake(&socket, handshake) {
+            Ok(token) => return Ok(Some((socket, token))),
+            Err(NetworkError::Rejected(rejection)) => {
+                return Err(NetworkError::Rejected(rejection))
+            }
+            Err(_) => thread::sleep(RECONNECT_INTERVAL),
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+pub mod test {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case(0, 5, 0)]
+    #[case(4, 5, 0)]
+    #[case(4, 6, 1)]
+    #[case(4, 10, 5)]
+    // Server restarted
+    #[case(10, 1, 0)]
+    fn count_lost_envelopes(#[case] last_id: u64, #[case] id: u64, #[case] expected: u64) {
+        assert_eq!(lost_envelopes(last_id, id), expected);
     }
 }
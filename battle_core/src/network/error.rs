@@ -1,10 +1,13 @@
 use std::fmt::Display;
 
+use crate::message::network::HandshakeRejection;
+
 #[derive(Debug)]
 pub enum NetworkError {
     NetworkError(String),
     ReceiveError(String),
     SendError(String),
+    Rejected(HandshakeRejection),
 }
 
 impl From<zmq::Error> for NetworkError {
@@ -23,6 +26,7 @@ impl Display for NetworkError {
                 f.write_str(&format!("ReceiveError: {}", message))
             }
             NetworkError::SendError(message) => f.write_str(&format!("SendError: {}", message)),
+            NetworkError::Rejected(rejection) => f.write_str(&format!("Rejected: {}", rejection)),
         }
     }
 }
@@ -1,3 +1,19 @@
 pub mod client;
 pub mod error;
 pub mod server;
+pub mod session;
+
+use crate::game::Side;
+
+/// Sockets receive timeout (milliseconds), permit threads to regularly check if they must stop
+pub const RECEIVE_TIMEOUT: i32 = 250;
+
+/// Server PUB socket topic where are published messages destined to given side
+pub fn side_topic(side: &Side) -> &'static [u8] {
+    match side {
+        Side::A => b"side_a",
+        Side::B => b"side_b",
+        Side::All => b"all",
+        Side::Observer => b"observer",
+    }
+}
@@ -1,27 +1,44 @@
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
 use serde::{Deserialize, Serialize};
 
 use std::{
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex,
     },
-    thread,
+    thread::{self, JoinHandle},
+    time::Duration,
 };
 
-use crate::message::{network::NetworkMessage, InputMessage, Message, OutputMessage};
+use crate::{
+    game::{player::Player, Side},
+    message::{
+        network::{Handshake, HandshakeRejection, NetworkMessage},
+        side_outputs, InputMessage, Message, OutputMessage,
+    },
+    state::battle::message::BattleStateMessage,
+    types::PlayerToken,
+};
 
-use super::error::NetworkError;
+use super::{error::NetworkError, session::Session, side_topic, RECEIVE_TIMEOUT};
 
 /// Network exchange logic
 /// Important note : zmq PUB socket have a limited buffer size,
 /// so we need to send messages by group instead one by one.
+/// Messages are published on one topic by side, each client only receive
+/// messages destined to its side.
+/// Clients must first send an `InputMessage::Handshake` which is checked against
+/// server protocol version and session map. Accepted clients receive a token which
+/// identifies them in their next requests, messages without a token of the current session
+/// match are ignored and answered with `NetworkMessage::NotJoined`. Observers clients can only require complete sync.
 pub struct Server {
     rep_address: String,
     pub_address: String,
-    output_receiver: Receiver<Vec<OutputMessage>>,
+    session: Arc<Mutex<Session>>,
+    output_receiver: Receiver<Vec<(Side, OutputMessage)>>,
     input_sender: Sender<Vec<InputMessage>>,
     stop_required: Arc<AtomicBool>,
+    handles: Vec<JoinHandle<()>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -36,104 +53,171 @@ impl Envelope {
     }
 }
 
-// TODO : When server/client is closing : end threads properly
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct Request {
+    // Given by the server when client handshake is accepted
+    token: Option<PlayerToken>,
+    messages: Vec<InputMessage>,
+}
+
 impl Server {
     pub fn new(
         rep_address: String,
         pub_address: String,
-        output_receiver: Receiver<Vec<OutputMessage>>,
+        session: Arc<Mutex<Session>>,
+        output_receiver: Receiver<Vec<(Side, OutputMessage)>>,
         input_sender: Sender<Vec<InputMessage>>,
         stop_required: Arc<AtomicBool>,
     ) -> Self {
         Self {
             rep_address,
             pub_address,
+            session,
             output_receiver,
             input_sender,
             stop_required,
+            handles: vec![],
         }
     }
 
-    pub fn serve(&self) -> Result<(), NetworkError> {
-        self.start_rep()?;
-        self.start_pub()?;
+    pub fn serve(&mut self) -> Result<(), NetworkError> {
+        let rep_handle = self.start_rep()?;
+        let pub_handle = self.start_pub()?;
+        self.handles.extend([rep_handle, pub_handle]);
         Ok(())
     }
 
-    fn start_rep(&self) -> Result<(), NetworkError> {
+    /// Stop threads and wait for them
+    pub fn shutdown(&mut self) {
+        self.stop_required.store(true, Ordering::Relaxed);
+        for handle in self.handles.drain(..) {
+            if handle.join().is_err() {
+                println!("Error while waiting server thread end");
+            }
+        }
+    }
+
+    fn start_rep(&self) -> Result<JoinHandle<()>, NetworkError> {
         let thread_input_sender = self.input_sender.clone();
         let server_rep_address = self.rep_address.clone();
 
         let zmq_context = zmq::Context::new();
         let socket = zmq_context.socket(zmq::REP)?;
+        socket.set_rcvtimeo(RECEIVE_TIMEOUT)?;
         socket.bind(&server_rep_address)?;
 
-        let ok = bincode::serialize(&Message::Network(NetworkMessage::Acknowledge)).unwrap();
+        let session = self.session.clone();
         let stop_required_ = self.stop_required.clone();
-        thread::Builder::new()
+        let handle = thread::Builder::new()
             .name("server_rep".to_string())
             .spawn(move || {
-                loop {
+                while !stop_required_.load(Ordering::Relaxed) {
                     // Receive client REQ messages bytes
                     let messages_bytes = match socket.recv_bytes(0) {
                         Ok(message_bytes) => message_bytes,
+                        // Nothing received, give a chance to check stop requirement
+                        Err(zmq::Error::EAGAIN) => continue,
                         Err(error) => {
-                            if stop_required_.load(Ordering::Relaxed) {
-                                break;
-                            }
-
                             println!("Error while receiving bytes : {}", error);
                             continue;
                         }
                     };
 
-                    // Decode received bytes into collection of messages
-                    let messages: Vec<InputMessage> = match bincode::deserialize(&messages_bytes) {
-                        Ok(messages) => messages,
+                    // Decode received bytes into collection of messages (REP socket must
+                    // respond anyway before receive again)
+                    let request = match bincode::deserialize::<Request>(&messages_bytes) {
+                        Ok(request) => request,
                         Err(error) => {
                             println!("Error while decoding received bytes : {}", error);
-                            continue;
+                            Request::default()
                         }
                     };
 
-                    // Send client expected acknowledgement
-                    socket.send(&ok, 0).unwrap_or_else(|e| {
-                        println!("Error while sending acknowledgement : {}", e)
+                    // Clients are identified by the token they received, not by what they claim
+                    let player = request.token.and_then(|token| {
+                        session
+                            .lock()
+                            .ok()
+                            .and_then(|session| session.player(&token).cloned())
                     });
 
-                    // Send through channel the decoded messages
-                    thread_input_sender.send(messages).unwrap_or_else(|_| {
-                        panic!("Channel was closed when try to send received messages")
-                    });
+                    // Send client expected acknowledgement (or handshake response)
+                    let (handshakes, messages): (Vec<InputMessage>, Vec<InputMessage>) = request
+                        .messages
+                        .into_iter()
+                        .partition(|message| matches!(message, InputMessage::Handshake(_)));
+                    let response = response(&handshakes, player.as_ref(), &session);
+                    // Server (not clients) tell to the battle which sides are connected
+                    if let (
+                        NetworkMessage::HandshakeAccepted(_),
+                        Some(InputMessage::Handshake(handshake)),
+                    ) = (&response, handshakes.first())
+                    {
+                        if let Some(connected) = connected_message(handshake.side()) {
+                            if thread_input_sender.send(vec![connected]).is_err() {
+                                println!("Channel was closed when try to send connected side");
+                                break;
+                            }
+                        }
+                    }
+                    match bincode::serialize(&Message::Network(response)) {
+                        Ok(response_bytes) => socket.send(&response_bytes, 0).unwrap_or_else(|e| {
+                            println!("Error while sending acknowledgement : {}", e)
+                        }),
+                        Err(error) => println!("Error while encoding acknowledgement : {}", error),
+                    };
+
+                    if messages.is_empty() {
+                        continue;
+                    }
+                    let Some(player) = player else {
+                        println!("Ignore messages of a client which didn't join current match");
+                        continue;
+                    };
+                    let messages = allowed_messages(player.side(), messages);
+                    if messages.is_empty() {
+                        continue;
+                    }
+
+                    // Send through channel the decoded messages (runner check player rights)
+                    if thread_input_sender
+                        .send(vec![InputMessage::FromPlayer(player, messages)])
+                        .is_err()
+                    {
+                        println!("Channel was closed when try to send received messages");
+                        break;
+                    }
                 }
 
                 println!("Server REP finished")
             })
-            .unwrap();
+            .map_err(|error| NetworkError::NetworkError(error.to_string()))?;
 
-        Ok(())
+        Ok(handle)
     }
 
-    fn start_pub(&self) -> Result<(), NetworkError> {
+    fn start_pub(&self) -> Result<JoinHandle<()>, NetworkError> {
         let thread_output_receiver = self.output_receiver.clone();
         let server_pub_address = self.pub_address.clone();
 
-        let mut pub_counter: u64 = 0;
+        let mut a_counter: u64 = 0;
+        let mut b_counter: u64 = 0;
+        let mut observer_counter: u64 = 0;
         let zmq_context = zmq::Context::new();
         let socket = zmq_context.socket(zmq::PUB)?;
         socket.bind(&server_pub_address)?;
 
         let stop_required_ = self.stop_required.clone();
-        thread::Builder::new()
+        let handle = thread::Builder::new()
             .name("server_pub".to_string())
             .spawn(move || {
-                loop {
-                    // Increment counter to permit client to know if some messages have been lost
-                    pub_counter += 1;
-
+                while !stop_required_.load(Ordering::Relaxed) {
                     // Retrieve messages to sent to clients
-                    let messages: Vec<OutputMessage> = match thread_output_receiver.recv() {
+                    let messages: Vec<(Side, OutputMessage)> = match thread_output_receiver
+                        .recv_timeout(Duration::from_millis(RECEIVE_TIMEOUT as u64))
+                    {
                         Ok(messages) => messages,
+                        Err(RecvTimeoutError::Timeout) => continue,
                         Err(error) => {
                             if !stop_required_.load(Ordering::Relaxed) {
                                 println!(
@@ -145,25 +229,215 @@ impl Server {
                         }
                     };
 
-                    // Prepare the data to send to clients
-                    let envelope = Envelope::new(pub_counter, messages);
-                    let messages_bytes = match bincode::serialize(&envelope) {
-                        Ok(messages_bytes) => messages_bytes,
-                        Err(error) => {
-                            println!("Error while encoding messages to send : {}", error);
+                    for (side, counter) in [
+                        (Side::A, &mut a_counter),
+                        (Side::B, &mut b_counter),
+                        (Side::Observer, &mut observer_counter),
+                    ] {
+                        let side_messages = side_outputs(&messages, &side);
+                        if side_messages.is_empty() {
                             continue;
                         }
-                    };
 
-                    // Finally send messages to clients
-                    if let Err(error) = socket.send(&messages_bytes, 0) {
-                        println!("Error while sending messages : {}", error);
-                    };
+                        // Increment counter to permit client to know if some messages have been lost
+                        *counter += 1;
+
+                        // Prepare the data to send to clients
+                        let envelope = Envelope::new(*counter, side_messages);
+                        let messages_bytes = match bincode::serialize(&envelope) {
+                            Ok(messages_bytes) => messages_bytes,
+                            Err(error) => {
+                                println!("Error while encoding messages to send : {}", error);
+                                continue;
+                            }
+                        };
+
+                        // Finally send messages to side clients
+                        if let Err(error) =
+                            socket.send_multipart([side_topic(&side), &messages_bytes], 0)
+                        {
+                            println!("Error while sending messages : {}", error);
+                        };
+                    }
                 }
                 println!("Server PUB finished");
             })
-            .unwrap();
+            .map_err(|error| NetworkError::NetworkError(error.to_string()))?;
 
-        Ok(())
+        Ok(handle)
+    }
+}
+
+/// Handshake result, or if request comes from a player of the current match
+fn response(
+    handshakes: &[InputMessage],
+    player: Option<&Player>,
+    session: &Mutex<Session>,
+) -> NetworkMessage {
+    match handshakes.first() {
+        Some(InputMessage::Handshake(handshake)) => handshake_response(handshake, session),
+        _ if player.is_some() => NetworkMessage::Acknowledge,
+        _ => NetworkMessage::NotJoined,
+    }
+}
+
+fn handshake_response(handshake: &Handshake, session: &Mutex<Session>) -> NetworkMessage {
+    let joined = match session.lock() {
+        Ok(mut session) => session.join(handshake),
+        Err(error) => {
+            println!("Error while locking session : {}", error);
+            Err(HandshakeRejection::Unavailable)
+        }
+    };
+
+    match joined {
+        Ok(token) => {
+            println!(
+                "Client {} accepted for side {}",
+                handshake.player().name(),
+                handshake.side()
+            );
+            NetworkMessage::HandshakeAccepted(token)
+        }
+        Err(rejection) => {
+            println!("Client rejected : {}", rejection);
+            NetworkMessage::HandshakeRejected(rejection)
+        }
+    }
+}
+
+/// Battle message which mark given side as connected (observers are not part of the battle)
+pub fn connected_message(side: &Side) -> Option<InputMessage> {
+    match side {
+        Side::A => Some(InputMessage::BattleState(
+            BattleStateMessage::SetAConnected(true),
+        )),
+        Side::B => Some(InputMessage::BattleState(
+            BattleStateMessage::SetBConnected(true),
+        )),
+        Side::All | Side::Observer => None,
+    }
+}
+
+/// Observers can't act on the battle
+fn allowed_messages(side: &Side, messages: Vec<InputMessage>) -> Vec<InputMessage> {
+    if side != &Side::Observer {
+        return messages;
+    }
+
+    let count = messages.len();
+    let messages: Vec<InputMessage> = messages
+        .into_iter()
+        .filter(|message| {
+            matches!(
+                message,
+                InputMessage::Handshake(_) | InputMessage::RequireCompleteSync(_)
+            )
+        })
+        .collect();
+    if messages.len() != count {
+        println!(
+            "Ignore {} message(s) sent by observer",
+            count - messages.len()
+        );
+    }
+
+    messages
+}
+
+#[cfg(test)]
+pub mod test {
+    use rstest::*;
+
+    use crate::{
+        message::network::Handshake,
+        state::battle::message::SoldierMessage,
+        types::{PlayerName, SoldierIndex},
+    };
+
+    use super::*;
+
+    #[rstest]
+    #[case(Side::A, 4)]
+    #[case(Side::Observer, 2)]
+    fn observer_can_only_watch(#[case] side: Side, #[case] expected: usize) {
+        // Given
+        let messages = vec![
+            InputMessage::Handshake(Handshake::new(
+                "Map1",
+                42,
+                Player::new(side, PlayerName("Player1".to_string())),
+            )),
+            InputMessage::RequireCompleteSync(side),
+            InputMessage::BattleState(BattleStateMessage::SetAReady(true)),
+            InputMessage::BattleState(BattleStateMessage::Soldier(
+                SoldierIndex(0),
+                SoldierMessage::SetAlive(false),
+            )),
+        ];
+
+        // When
+        let messages = allowed_messages(&side, messages);
+
+        // Then
+        assert_eq!(messages.len(), expected);
+    }
+
+    #[rstest]
+    fn players_of_closed_match_must_join_again() {
+        // Given
+        let session = Mutex::new(Session::new("Map1", 42));
+        let handshake = Handshake::new(
+            "Map1",
+            42,
+            Player::new(Side::A, PlayerName("Player1".to_string())),
+        );
+        let NetworkMessage::HandshakeAccepted(token) =
+            response(&[InputMessage::Handshake(handshake)], None, &session)
+        else {
+            unreachable!()
+        };
+        let player = |session: &Mutex<Session>| session.lock().unwrap().player(&token).cloned();
+        assert_eq!(
+            response(&[], player(&session).as_ref(), &session),
+            NetworkMessage::Acknowledge
+        );
+
+        // When
+        session.lock().unwrap().reset();
+
+        // Then
+        assert_eq!(
+            response(&[], player(&session).as_ref(), &session),
+            NetworkMessage::NotJoined
+        );
+    }
+
+    #[rstest]
+    fn handshake_is_rejected_when_session_is_poisoned() {
+        // Given
+        let session = Mutex::new(Session::new("Map1", 42));
+        let _ = thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let _session = session.lock().unwrap();
+                    panic!("Poison session");
+                })
+                .join()
+        });
+        let handshake = Handshake::new(
+            "Map1",
+            42,
+            Player::new(Side::A, PlayerName("Player1".to_string())),
+        );
+
+        // When
+        let response = handshake_response(&handshake, &session);
+
+        // Then
+        assert_eq!(
+            response,
+            NetworkMessage::HandshakeRejected(HandshakeRejection::Unavailable)
+        );
     }
 }
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use crate::{
+    game::player::Player,
+    message::network::{Handshake, HandshakeRejection},
+    types::PlayerToken,
+};
+
+/// Map played by the server and players who joined the current match (by the token they
+/// received). In lobby mode, the map is chosen, among available ones, by the first accepted
+/// client. Players names are unique in a match.
+#[derive(Debug, Clone)]
+pub struct Session {
+    // Playable maps names and content hashes
+    maps: HashMap<String, u64>,
+    map: Option<String>,
+    lobby: bool,
+    players: HashMap<PlayerToken, Player>,
+}
+
+impl Session {
+    /// Session always playing given map
+    pub fn new(map_name: &str, map_hash: u64) -> Self {
+        Self {
+            maps: HashMap::from([(map_name.to_string(), map_hash)]),
+            map: Some(map_name.to_string()),
+            lobby: false,
+            players: HashMap::new(),
+        }
+    }
+
+    /// Session where clients choose the map among given ones (names and content hashes)
+    pub fn lobby(maps: HashMap<String, u64>) -> Self {
+        Self {
+            maps,
+            map: None,
+            lobby: true,
+            players: HashMap::new(),
+        }
+    }
+
+    pub fn map(&self) -> Option<&str> {
+        self.map.as_deref()
+    }
+
+    pub fn players(&self) -> Vec<&Player> {
+        self.players.values().collect()
+    }
+
+    /// Player who joined the current match with given token
+    pub fn player(&self, token: &PlayerToken) -> Option<&Player> {
+        self.players.get(token)
+    }
+
+    /// Accept client into the current match if its handshake is compatible with it. Return the
+    /// token the client must join to its next requests (the same one if it joins again).
+    pub fn join(&mut self, handshake: &Handshake) -> Result<PlayerToken, HandshakeRejection> {
+        let map_name = self.map.as_deref().unwrap_or(handshake.map_name());
+        let map_hash = *self
+            .maps
+            .get(map_name)
+            .ok_or_else(|| HandshakeRejection::Map(map_name.to_string()))?;
+        handshake.check(map_name, map_hash)?;
+
+        let name = handshake.player().name();
+        if let Some((token, player)) = self
+            .players
+            .iter()
+            .find(|(_, player)| player.name() == name)
+        {
+            if handshake.token() == Some(token) && player == handshake.player() {
+                return Ok(*token);
+            }
+            return Err(HandshakeRejection::PlayerName(name.clone()));
+        }
+
+        let token = PlayerToken(rand::random());
+        self.map = Some(map_name.to_string());
+        self.players.insert(token, handshake.player().clone());
+        Ok(token)
+    }
+
+    /// Forget current match players (and its map when chosen by players)
+    pub fn reset(&mut self) {
+        self.players.clear();
+        if self.lobby {
+            self.map = None;
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use rstest::*;
+
+    use crate::{game::Side, types::PlayerName};
+
+    use super::*;
+
+    fn handshake(map_name: &str, map_hash: u64, player_name: &str) -> Handshake {
+        Handshake::new(
+            map_name,
+            map_hash,
+            Player::new(Side::A, PlayerName(player_name.to_string())),
+        )
+    }
+
+    #[rstest]
+    fn first_lobby_client_choose_the_map() {
+        // Given
+        let mut session = Session::lobby(HashMap::from([
+            ("Map1".to_string(), 1),
+            ("Map2".to_string(), 2),
+        ]));
+
+        // When
+        let unknown = session.join(&handshake("Map3", 3, "Player1"));
+        let first = session.join(&handshake("Map2", 2, "Player1"));
+        let second = session.join(&handshake("Map1", 1, "Player2"));
+
+        // Then
+        assert_eq!(unknown, Err(HandshakeRejection::Map("Map3".to_string())));
+        assert!(first.is_ok());
+        assert_eq!(
+            second,
+            Err(HandshakeRejection::MapName(
+                "Map2".to_string(),
+                "Map1".to_string()
+            ))
+        );
+        assert_eq!(session.map(), Some("Map2"));
+        assert_eq!(session.players().len(), 1);
+    }
+
+    #[rstest]
+    fn player_name_is_unique_but_can_join_again() {
+        // Given
+        let mut session = Session::new("Map1", 1);
+        let token = session.join(&handshake("Map1", 1, "Player1")).unwrap();
+
+        // When
+        let duplicate = session.join(&handshake("Map1", 1, "Player1"));
+        let again = session.join(&handshake("Map1", 1, "Player1").rejoin(token));
+        let other = session.join(&handshake("Map1", 1, "Player2"));
+
+        // Then
+        assert_eq!(
+            duplicate,
+            Err(HandshakeRejection::PlayerName(PlayerName(
+                "Player1".to_string()
+            )))
+        );
+        assert_eq!(again, Ok(token));
+        assert!(other.is_ok_and(|other| other != token));
+        assert_eq!(session.players().len(), 2);
+        assert_eq!(
+            session.player(&token).map(|player| player.name()),
+            Some(&PlayerName("Player1".to_string()))
+        );
+    }
+
+    #[rstest]
+    #[case(Session::new("Map1", 1), Some("Map1"))]
+    #[case(Session::lobby(HashMap::from([("Map1".to_string(), 1)])), None)]
+    fn reset_forget_match(#[case] mut session: Session, #[case] expected: Option<&str>) {
+        // Given
+        let token = session.join(&handshake("Map1", 1, "Player1")).unwrap();
+
+        // When
+        session.reset();
+
+        // Then
+        assert_eq!(session.map(), expected);
+        assert_eq!(session.player(&token), None);
+    }
+}
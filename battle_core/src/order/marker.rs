@@ -22,12 +22,14 @@ pub enum OrderMarker {
     Hide,
     EngageSquad,
     SuppressFire,
+    ReverseTo,
 }
 
 impl OrderMarker {
     pub fn selectable(&self) -> Offset {
         match self {
             OrderMarker::MoveTo
+            | OrderMarker::ReverseTo
             | OrderMarker::MoveFastTo
             | OrderMarker::SneakTo
             | OrderMarker::SuppressFire
@@ -38,7 +40,7 @@ impl OrderMarker {
 
     pub fn sprite_info(&self) -> OrderMarkerSpriteInfo {
         match self {
-            OrderMarker::MoveTo => OrderMarkerSpriteInfo {
+            OrderMarker::MoveTo | OrderMarker::ReverseTo => OrderMarkerSpriteInfo {
                 relative_start_x: ORDER_MARKER_START_X / UI_SPRITE_SHEET_WIDTH,
                 relative_start_y: ORDER_MARKER_START_Y / UI_SPRITE_SHEET_HEIGHT,
                 relative_width: ORDER_MARKER_WIDTH / UI_SPRITE_SHEET_WIDTH,
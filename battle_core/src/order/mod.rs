@@ -15,6 +15,9 @@ pub enum PendingOrder {
     Defend(SquadUuid),
     Hide(SquadUuid),
     EngageOrFire(SquadUuid),
+    Board(SquadUuid),
+    Dismount(SquadUuid),
+    ReverseTo(SquadUuid, Option<OrderMarkerIndex>, Vec<WorldPoint>),
 }
 
 impl PendingOrder {
@@ -24,6 +27,7 @@ impl PendingOrder {
             PendingOrder::MoveTo(_, _, _)
                 | PendingOrder::MoveFastTo(_, _, _)
                 | PendingOrder::SneakTo(_, _, _)
+                | PendingOrder::ReverseTo(_, _, _)
         )
     }
 
@@ -35,6 +39,9 @@ impl PendingOrder {
             PendingOrder::Defend(squad_index) => squad_index,
             PendingOrder::Hide(squad_index) => squad_index,
             PendingOrder::EngageOrFire(squad_index) => squad_index,
+            PendingOrder::Board(squad_index) => squad_index,
+            PendingOrder::Dismount(squad_index) => squad_index,
+            PendingOrder::ReverseTo(squad_index, _, _) => squad_index,
         }
     }
 
@@ -46,6 +53,9 @@ impl PendingOrder {
             PendingOrder::Defend(_) => vec![],
             PendingOrder::Hide(_) => vec![],
             PendingOrder::EngageOrFire(_) => vec![],
+            PendingOrder::Board(_) => vec![],
+            PendingOrder::Dismount(_) => vec![],
+            PendingOrder::ReverseTo(_, _, cached_points) => cached_points.clone(),
         }
     }
 
@@ -57,6 +67,9 @@ impl PendingOrder {
             PendingOrder::Defend(_) => &None,
             PendingOrder::Hide(_) => &None,
             PendingOrder::EngageOrFire(_) => &None,
+            PendingOrder::Board(_) => &None,
+            PendingOrder::Dismount(_) => &None,
+            PendingOrder::ReverseTo(_, order_marker_index, _) => order_marker_index,
         }
     }
 
@@ -64,7 +77,8 @@ impl PendingOrder {
         match self {
             PendingOrder::MoveTo(_, _, points)
             | PendingOrder::MoveFastTo(_, _, points)
-            | PendingOrder::SneakTo(_, _, points) => points.push(new_point),
+            | PendingOrder::SneakTo(_, _, points)
+            | PendingOrder::ReverseTo(_, _, points) => points.push(new_point),
             _ => {}
         }
     }
@@ -72,6 +86,10 @@ impl PendingOrder {
     pub fn is_hide(&self) -> bool {
         matches!(self, Self::Hide(_))
     }
+
+    pub fn is_reverse(&self) -> bool {
+        matches!(self, Self::ReverseTo(_, _, _))
+    }
 }
 
 impl Display for PendingOrder {
@@ -83,6 +101,9 @@ impl Display for PendingOrder {
             PendingOrder::Defend(_) => f.write_str("Defend"),
             PendingOrder::Hide(_) => f.write_str("Hide"),
             PendingOrder::EngageOrFire(_) => f.write_str("EngageOrFire"),
+            PendingOrder::Board(_) => f.write_str("Board"),
+            PendingOrder::Dismount(_) => f.write_str("Dismount"),
+            PendingOrder::ReverseTo(_, _, _) => f.write_str("ReverseTo"),
         }
     }
 }
@@ -97,6 +118,11 @@ pub enum Order {
     Hide(Angle),
     EngageSquad(SquadUuid),
     SuppressFire(WorldPoint),
+    IndirectFire(WorldPoint),
+    Board(VehicleIndex),
+    Dismount,
+    // Drive in reverse gear, keeping the vehicle front toward the enemy
+    ReverseTo(WorldPaths, Option<Box<Order>>),
 }
 
 impl Order {
@@ -109,24 +135,37 @@ impl Order {
             Order::Hide(_) => Some(OrderMarker::Hide),
             Order::EngageSquad(_) => Some(OrderMarker::EngageSquad),
             Order::SuppressFire(_) => Some(OrderMarker::SuppressFire),
+            Order::IndirectFire(_) => Some(OrderMarker::SuppressFire),
             Order::Idle => None,
+            Order::Board(_) => None,
+            Order::Dismount => None,
+            Order::ReverseTo(_, _) => Some(OrderMarker::ReverseTo),
         }
     }
 
     pub fn angle(&self) -> Option<Angle> {
         match self {
-            Order::MoveTo(_, _) | Order::MoveFastTo(_, _) | Order::SneakTo(_, _) => None,
+            Order::MoveTo(_, _)
+            | Order::MoveFastTo(_, _)
+            | Order::SneakTo(_, _)
+            | Order::ReverseTo(_, _) => None,
             Order::Defend(angle) => Some(*angle),
             Order::Hide(angle) => Some(*angle),
             Order::SuppressFire(_) => None,
+            Order::IndirectFire(_) => None,
             Order::EngageSquad(_) => None,
             Order::Idle => None,
+            Order::Board(_) => None,
+            Order::Dismount => None,
         }
     }
 
     pub fn reach_step(&mut self) -> bool {
         match self {
-            Order::MoveTo(paths, _) | Order::MoveFastTo(paths, _) | Order::SneakTo(paths, _) => {
+            Order::MoveTo(paths, _)
+            | Order::MoveFastTo(paths, _)
+            | Order::SneakTo(paths, _)
+            | Order::ReverseTo(paths, _) => {
                 paths
                     .remove_next_point()
                     .expect("Reach a move behavior implies containing point");
@@ -140,6 +179,9 @@ impl Order {
             Order::Idle => {}
             Order::EngageSquad(_) => {}
             Order::SuppressFire(_) => {}
+            Order::IndirectFire(_) => {}
+            Order::Board(_) => {}
+            Order::Dismount => {}
         }
 
         false
@@ -150,6 +192,7 @@ impl Order {
             Self::MoveTo(_, then) => then,
             Self::MoveFastTo(_, then) => then,
             Self::SneakTo(_, then) => then,
+            Self::ReverseTo(_, then) => then,
             _ => &None,
         }
         .clone()
@@ -168,6 +211,10 @@ impl Display for Order {
             Order::Idle => f.write_str("Idle"),
             Order::EngageSquad(_) => f.write_str("Engage"),
             Order::SuppressFire(_) => f.write_str("SuppressFire"),
+            Order::IndirectFire(_) => f.write_str("IndirectFire"),
+            Order::Board(_) => f.write_str("Board"),
+            Order::Dismount => f.write_str("Dismount"),
+            Order::ReverseTo(_, _) => f.write_str("ReverseTo"),
         }
     }
 }
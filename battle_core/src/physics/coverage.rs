@@ -25,7 +25,7 @@ impl<'a> SoldierCovered<'a> {
         }
     }
 
-    pub fn compute(&self) -> bool {
+    pub fn compute<R: Rng>(&self, rng: &mut R) -> bool {
         let pixels = Bresenham::new(
             (
                 self.bullet_fire.from().x as isize,
@@ -48,7 +48,6 @@ impl<'a> SoldierCovered<'a> {
                 .get((grid_point.y * self.map.width() as i32 + grid_point.x) as usize)
             {
                 if let Some(coverage) = tile.type_().coverage(&self.soldier.behavior().posture()) {
-                    let mut rng = rand::thread_rng();
                     let value: f32 = rng.gen();
                     return value <= coverage.0;
                 }
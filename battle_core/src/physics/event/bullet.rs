@@ -1,18 +1,20 @@
 use crate::{
-    game::weapon::{Ammunition, GunFireSoundType},
+    game::weapon::{AmmunitionType, WeaponType},
     types::{Precision, SoldierIndex, WorldPoint},
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BulletFire {
+    // Frames between push and start (next rounds of a burst)
+    delay: u64,
     start: u64,
     end: u64,
     from: WorldPoint,
     to: WorldPoint,
     target: Option<(SoldierIndex, Precision)>,
-    ammunition: Ammunition,
-    gun_fire_sound_type: GunFireSoundType,
+    ammunition: AmmunitionType,
+    weapon: WeaponType,
 }
 
 impl BulletFire {
@@ -20,30 +22,40 @@ impl BulletFire {
         from: WorldPoint,
         to: WorldPoint,
         target: Option<(SoldierIndex, Precision)>,
-        ammunition: Ammunition,
-        gun_fire_sound_type: GunFireSoundType,
+        ammunition: AmmunitionType,
+        weapon: WeaponType,
     ) -> Self {
         Self {
+            delay: 0,
             start: 0,
             end: 0,
             from,
             to,
             target,
             ammunition,
-            gun_fire_sound_type,
+            weapon,
         }
     }
 
+    pub fn delayed(mut self, frames: u64) -> Self {
+        self.delay = frames;
+        self
+    }
+
     pub fn init(&mut self, start_frame_i: u64) {
-        self.start = start_frame_i;
-        self.end = start_frame_i + 2;
+        self.start = start_frame_i + self.delay;
+        self.end = self.start + 2;
+    }
+
+    pub fn started(&self, frame_i: u64) -> bool {
+        frame_i >= self.start
     }
 
     pub fn point(&self) -> &WorldPoint {
         &self.to
     }
 
-    pub fn ammunition(&self) -> &Ammunition {
+    pub fn ammunition(&self) -> &AmmunitionType {
         &self.ammunition
     }
 
@@ -63,6 +75,10 @@ impl BulletFire {
         &self.to
     }
 
+    pub fn delay(&self) -> u64 {
+        self.delay
+    }
+
     pub fn start(&self) -> u64 {
         self.start
     }
@@ -71,7 +87,8 @@ impl BulletFire {
         self.end
     }
 
-    pub fn gun_fire_sound_type(&self) -> &GunFireSoundType {
-        &self.gun_fire_sound_type
+    /// Weapon which fired, to play its sounds
+    pub fn weapon(&self) -> &WeaponType {
+        &self.weapon
     }
 }
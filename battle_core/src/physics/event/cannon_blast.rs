@@ -1,19 +1,18 @@
 use crate::{
     config::TARGET_FPS,
-    game::weapon::WeaponSprite,
-    graphics::soldier::SoldierAnimationType,
+    graphics::{cannon_blast::CannonBlastAnimationType, soldier::SoldierAnimationType, Sprite},
     types::{Angle, WorldPoint},
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CannonBlast {
     start: u64,
     end: u64,
     /// Soldier reference point
     point: WorldPoint,
     angle: Angle,
-    weapon_sprite_type: WeaponSprite,
+    animation_type: CannonBlastAnimationType,
     soldier_animation_type: SoldierAnimationType,
 }
 
@@ -21,7 +20,6 @@ impl CannonBlast {
     pub fn new(
         point: WorldPoint,
         angle: Angle,
-        type_: WeaponSprite,
         soldier_animation_type: SoldierAnimationType,
     ) -> Self {
         Self {
@@ -29,15 +27,14 @@ impl CannonBlast {
             end: 0,
             point,
             angle,
-            weapon_sprite_type: type_,
+            animation_type: CannonBlastAnimationType::from(&soldier_animation_type),
             soldier_animation_type,
         }
     }
 
     pub fn init(&mut self, start_frame_i: u64) {
         self.start = start_frame_i;
-        self.end = start_frame_i
-            + (self.weapon_sprite_type.sprite().duration() * TARGET_FPS as f32) as u64;
+        self.end = start_frame_i + (self.animation_type.duration() * TARGET_FPS as f32) as u64;
     }
 
     pub fn point(&self) -> &WorldPoint {
@@ -48,10 +45,6 @@ impl CannonBlast {
         &self.angle
     }
 
-    pub fn type_(&self) -> &WeaponSprite {
-        &self.weapon_sprite_type
-    }
-
     pub fn finished(&self, frame_i: u64) -> bool {
         frame_i >= self.end
     }
@@ -68,8 +61,8 @@ impl CannonBlast {
         self.end
     }
 
-    pub fn weapon_sprite_type(&self) -> &WeaponSprite {
-        &self.weapon_sprite_type
+    pub fn animation_type(&self) -> &CannonBlastAnimationType {
+        &self.animation_type
     }
 
     pub fn soldier_animation_type(&self) -> &SoldierAnimationType {
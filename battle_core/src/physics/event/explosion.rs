@@ -1,7 +1,7 @@
 use crate::{config::TARGET_FPS, game::explosive::ExplosiveType, types::WorldPoint};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Explosion {
     start: u64,
     end: u64,
@@ -0,0 +1,81 @@
+use crate::{
+    game::{explosive::ExplosiveType, weapon::WeaponType},
+    types::WorldPoint,
+};
+use serde::{Deserialize, Serialize};
+
+/// Indirect fire round (ex. mortar shell) in flight, exploding at its end
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IndirectFire {
+    // Frames between fire and impact
+    flight: u64,
+    start: u64,
+    end: u64,
+    from: WorldPoint,
+    to: WorldPoint,
+    explosive: ExplosiveType,
+    weapon: WeaponType,
+}
+
+impl IndirectFire {
+    pub fn new(
+        from: WorldPoint,
+        to: WorldPoint,
+        flight: u64,
+        explosive: ExplosiveType,
+        weapon: WeaponType,
+    ) -> Self {
+        Self {
+            flight,
+            start: 0,
+            end: 0,
+            from,
+            to,
+            explosive,
+            weapon,
+        }
+    }
+
+    pub fn init(&mut self, start_frame_i: u64) {
+        self.start = start_frame_i;
+        self.end = self.start + self.flight;
+    }
+
+    /// The round hits the ground at this frame
+    pub fn impact(&self, frame_i: u64) -> bool {
+        self.end == frame_i
+    }
+
+    pub fn finished(&self, frame_i: u64) -> bool {
+        frame_i > self.end
+    }
+
+    pub fn from(&self) -> &WorldPoint {
+        &self.from
+    }
+
+    pub fn to(&self) -> &WorldPoint {
+        &self.to
+    }
+
+    pub fn flight(&self) -> u64 {
+        self.flight
+    }
+
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    pub fn explosive(&self) -> &ExplosiveType {
+        &self.explosive
+    }
+
+    /// Weapon which fired, to play its sounds
+    pub fn weapon(&self) -> &WeaponType {
+        &self.weapon
+    }
+}
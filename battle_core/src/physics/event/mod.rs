@@ -1,3 +1,4 @@
 pub mod bullet;
 pub mod cannon_blast;
 pub mod explosion;
+pub mod indirect;
@@ -6,12 +6,22 @@ use strum_macros::EnumIter;
 pub enum PathMode {
     Walk,
     Drive(VehicleSize),
+    // Drive in reverse gear, vehicle keep its rear toward the path
+    Reverse(VehicleSize),
 }
 impl PathMode {
     pub fn include_vehicles(&self) -> bool {
         match self {
             PathMode::Walk => false,
-            PathMode::Drive(_) => true,
+            PathMode::Drive(_) | PathMode::Reverse(_) => true,
+        }
+    }
+
+    /// Same mode, in reverse gear for vehicles
+    pub fn reversed(self) -> Self {
+        match self {
+            PathMode::Walk => PathMode::Walk,
+            PathMode::Drive(size) | PathMode::Reverse(size) => PathMode::Reverse(size),
         }
     }
 }
@@ -21,6 +31,8 @@ pub const COST_DIAGONAL: i32 = 10;
 pub const COST_CORNER: i32 = 20;
 pub const COST_BACK_CORNER: i32 = 30;
 pub const COST_BACK: i32 = 50;
+// Added to each reverse gear move, reversing is slow so paths must stay short
+pub const COST_REVERSE: i32 = 10;
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, EnumIter)]
 pub enum Direction {
@@ -69,6 +81,25 @@ impl Direction {
         }
     }
 
+    pub fn opposite(&self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::NorthEst => Direction::SouthWest,
+            Direction::Est => Direction::West,
+            Direction::SouthEst => Direction::NorthWest,
+            Direction::South => Direction::North,
+            Direction::SouthWest => Direction::NorthEst,
+            Direction::West => Direction::Est,
+            Direction::NorthWest => Direction::SouthEst,
+        }
+    }
+
+    /// Cost of a reverse gear move to given direction for a vehicle oriented to self, the
+    /// vehicle turning to the opposite direction
+    pub fn reverse_angle_cost(&self, direction: &Direction) -> i32 {
+        self.angle_cost(&direction.opposite()) + COST_REVERSE
+    }
+
     pub fn angle_cost(&self, direction: &Direction) -> i32 {
         match self {
             Direction::North => match direction {
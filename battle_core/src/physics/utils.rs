@@ -9,3 +9,8 @@ pub fn distance_between_points(from: &WorldPoint, to: &WorldPoint) -> Distance {
         ((from.to_vec2().distance(to.to_vec2()) * DISTANCE_TO_METERS_COEFFICIENT) * 1000.) as i64,
     )
 }
+
+/// Length of given distance between two scene points
+pub fn distance_to_world(distance: &Distance) -> f32 {
+    (distance.millimeters() as f32 / DISTANCE_TO_METERS_COEFFICIENT) / 1000.
+}
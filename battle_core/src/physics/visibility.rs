@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bresenham::Bresenham;
 use serde::{Deserialize, Serialize};
@@ -14,7 +14,7 @@ use super::utils::distance_between_points;
 
 pub const VISIBLE_OPACITY_LIMIT: f32 = 0.5;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct Visibilities {
     visibilities: HashMap<(SoldierIndex, SoldierIndex), Visibility>,
 }
@@ -24,6 +24,17 @@ impl Visibilities {
         self.visibilities = value;
     }
 
+    pub fn update(
+        &mut self,
+        changes: HashMap<(SoldierIndex, SoldierIndex), Visibility>,
+        removed: &[(SoldierIndex, SoldierIndex)],
+    ) {
+        for soldiers in removed {
+            self.visibilities.remove(soldiers);
+        }
+        self.visibilities.extend(changes);
+    }
+
     pub fn get(&self, soldiers: &(SoldierIndex, SoldierIndex)) -> Option<&Visibility> {
         self.visibilities.get(soldiers)
     }
@@ -44,6 +55,18 @@ impl Visibilities {
             .collect()
     }
 
+    /// Visibilities computed from given soldiers only
+    pub fn from_soldiers(&self, soldiers: &HashSet<SoldierIndex>) -> Self {
+        Self {
+            visibilities: self
+                .visibilities
+                .iter()
+                .filter(|((from_soldier, _), _)| soldiers.contains(from_soldier))
+                .map(|(key, visibility)| (*key, visibility.clone()))
+                .collect(),
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.visibilities.len()
     }
@@ -53,7 +76,7 @@ impl Visibilities {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Visibility {
     pub from: WorldPoint,
     pub from_soldier: Option<SoldierIndex>,
@@ -0,0 +1,171 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, ErrorKind, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    config::ServerConfig, game::catalog::Catalog, message::InputMessage, sync::BattleStateCopy,
+};
+
+/// Version of written replays. Increment it when replay header or frames change.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Everything needed (with the map) to replay a battle : the initial state, the random generator
+/// seed, the server config and catalog the battle was played with and all inputs received by the
+/// server. A replay file contains the version, the header then frames.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReplayHeader {
+    map_name: String,
+    seed: u64,
+    config: ServerConfig,
+    catalog: Catalog,
+    initial: BattleStateCopy,
+}
+
+impl ReplayHeader {
+    pub fn new(
+        map_name: String,
+        seed: u64,
+        config: ServerConfig,
+        catalog: Catalog,
+        initial: BattleStateCopy,
+    ) -> Self {
+        Self {
+            map_name,
+            seed,
+            config,
+            catalog,
+            initial,
+        }
+    }
+
+    pub fn map_name(&self) -> &str {
+        &self.map_name
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    pub fn catalog(&self) -> &Catalog {
+        &self.catalog
+    }
+
+    pub fn initial(&self) -> &BattleStateCopy {
+        &self.initial
+    }
+}
+
+/// Inputs received by the server before computing given frame
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReplayFrame {
+    frame_i: u64,
+    inputs: Vec<InputMessage>,
+}
+
+impl ReplayFrame {
+    pub fn new(frame_i: u64, inputs: Vec<InputMessage>) -> Self {
+        Self { frame_i, inputs }
+    }
+
+    pub fn frame_i(&self) -> u64 {
+        self.frame_i
+    }
+
+    pub fn inputs(&self) -> &Vec<InputMessage> {
+        &self.inputs
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Replay {
+    header: ReplayHeader,
+    frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    pub fn header(&self) -> &ReplayHeader {
+        &self.header
+    }
+
+    pub fn frames(&self) -> &Vec<ReplayFrame> {
+        &self.frames
+    }
+
+    pub fn last_frame_i(&self) -> u64 {
+        self.frames
+            .last()
+            .map(|frame| frame.frame_i())
+            .unwrap_or(self.header.initial().frame_i())
+    }
+}
+
+/// Write frames as they come to keep replay usable if server stop unexpectedly
+pub struct ReplayWriter {
+    file: BufWriter<File>,
+}
+
+impl ReplayWriter {
+    pub fn new(destination: &Path, header: &ReplayHeader) -> Result<Self, ReplayError> {
+        let mut file = BufWriter::new(File::create(destination)?);
+        bincode::serialize_into(&mut file, &REPLAY_VERSION)?;
+        bincode::serialize_into(&mut file, header)?;
+        file.flush()?;
+        Ok(Self { file })
+    }
+
+    pub fn write(&mut self, frame: &ReplayFrame) -> Result<(), ReplayError> {
+        bincode::serialize_into(&mut self.file, frame)?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+pub struct ReplayReader;
+
+impl ReplayReader {
+    pub fn from_file(path: &Path) -> Result<Replay, ReplayError> {
+        let mut file = BufReader::new(File::open(path)?);
+        let version: u32 = bincode::deserialize_from(&mut file)?;
+        if version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+        let header: ReplayHeader = bincode::deserialize_from(&mut file)?;
+
+        let mut frames = vec![];
+        loop {
+            match bincode::deserialize_from::<_, ReplayFrame>(&mut file) {
+                Ok(frame) => frames.push(frame),
+                // End of file (or last frame partially written if server stopped unexpectedly)
+                Err(error) => match *error {
+                    bincode::ErrorKind::Io(ref error)
+                        if error.kind() == ErrorKind::UnexpectedEof =>
+                    {
+                        break
+                    }
+                    _ => return Err(error.into()),
+                },
+            }
+        }
+
+        Ok(Replay { header, frames })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    #[error("Disk error : {0}")]
+    Disk(#[from] io::Error),
+    #[error("Data format error : {0}")]
+    Format(#[from] bincode::Error),
+    #[error("Replay version {0} is not supported (current is {REPLAY_VERSION})")]
+    UnsupportedVersion(u32),
+}
@@ -0,0 +1,436 @@
+use std::{fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::sync::BattleStateCopy;
+
+/// First bytes of a battle save file. Saves written before versioning don't have it (version 0).
+pub const SAVE_MAGIC: [u8; 4] = *b"OCBS";
+/// Version of written saves. Increment it when `BattleStateCopy` (or what it contains) changes,
+/// then keep old payload format readable in `migrate`.
+pub const SAVE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SaveHeader {
+    magic: [u8; 4],
+    version: u32,
+    map_name: String,
+}
+
+impl SaveHeader {
+    pub fn new(map_name: String) -> Self {
+        Self {
+            magic: SAVE_MAGIC,
+            version: SAVE_VERSION,
+            map_name,
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn map_name(&self) -> &str {
+        &self.map_name
+    }
+}
+
+pub struct SaveWriter;
+
+impl SaveWriter {
+    pub fn to_file(
+        destination: &Path,
+        map_name: &str,
+        copy: &BattleStateCopy,
+    ) -> Result<(), SaveError> {
+        fs::write(destination, Self::to_bytes(map_name, copy)?)?;
+        Ok(())
+    }
+
+    pub fn to_bytes(map_name: &str, copy: &BattleStateCopy) -> Result<Vec<u8>, SaveError> {
+        let mut bytes = bincode::serialize(&SaveHeader::new(map_name.to_string()))?;
+        bytes.extend(bincode::serialize(copy)?);
+        Ok(bytes)
+    }
+}
+
+pub struct SaveReader;
+
+impl SaveReader {
+    /// Read the save and migrate it to current format if needed. Save must be a save of given map
+    /// (saves without header are considered as belonging to given map).
+    pub fn from_file(path: &Path, map_name: &str) -> Result<BattleStateCopy, SaveError> {
+        Self::from_bytes(&fs::read(path)?, map_name)
+    }
+
+    pub fn from_bytes(bytes: &[u8], map_name: &str) -> Result<BattleStateCopy, SaveError> {
+        if !bytes.starts_with(&SAVE_MAGIC) {
+            return migrate(0, bytes);
+        }
+
+        let header: SaveHeader = bincode::deserialize(bytes)?;
+        if header.map_name() != map_name {
+            return Err(SaveError::MapMismatch(
+                map_name.to_string(),
+                header.map_name().to_string(),
+            ));
+        }
+
+        let header_size = bincode::serialized_size(&header)? as usize;
+        migrate(header.version(), &bytes[header_size..])
+    }
+}
+
+/// Read payload written with given save version as current `BattleStateCopy`.
+/// When `SAVE_VERSION` is incremented, previous payload definition must be kept (as its own
+/// types, in a `vN` module) and converted here into the current one.
+fn migrate(version: u32, payload: &[u8]) -> Result<BattleStateCopy, SaveError> {
+    match version {
+        0 => Ok(bincode::deserialize::<v0::BattleStateCopy>(payload)?.into()),
+        SAVE_VERSION => Ok(bincode::deserialize(payload)?),
+        _ => Err(SaveError::UnsupportedVersion(version)),
+    }
+}
+
+/// Version 0 saves (written before versioning, without header) only contained soldiers,
+/// vehicles, boards, phase and flags. Weapons, magazines, vehicle types and passenger places were
+/// hardcoded enums.
+mod v0 {
+    use std::collections::HashMap;
+
+    use oc_core::morale::Morale;
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        behavior::{feeling::Feeling, gesture::Gesture, Behavior},
+        game::{
+            control::MapControl,
+            flag::FlagsOwnership,
+            weapon::{MagazineType, WeaponType},
+            Side,
+        },
+        order::Order,
+        state::battle::phase::Phase,
+        types::{Angle, SoldierIndex, SquadUuid, VehicleIndex, WorldPoint},
+    };
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub enum Magazine {
+        MosinNagant(usize),
+        Mauser(usize),
+    }
+
+    impl From<Magazine> for crate::game::weapon::Magazine {
+        fn from(magazine: Magazine) -> Self {
+            match magazine {
+                Magazine::MosinNagant(fill) => {
+                    Self::new(MagazineType("MosinNagant".to_string()), fill)
+                }
+                Magazine::Mauser(fill) => Self::new(MagazineType("Mauser".to_string()), fill),
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub enum Weapon {
+        MosinNagantM1924(bool, Option<Magazine>),
+        MauserG41(bool, Option<Magazine>),
+    }
+
+    impl From<Weapon> for crate::game::weapon::Weapon {
+        fn from(weapon: Weapon) -> Self {
+            let (type_, ready_bullet, magazine) = match weapon {
+                Weapon::MosinNagantM1924(ready_bullet, magazine) => {
+                    ("MosinNagantM1924", ready_bullet, magazine)
+                }
+                Weapon::MauserG41(ready_bullet, magazine) => ("MauserG41", ready_bullet, magazine),
+            };
+            Self::new(
+                WeaponType(type_.to_string()),
+                ready_bullet,
+                magazine.map(Into::into),
+            )
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct Soldier {
+        pub uuid: SoldierIndex,
+        pub side: Side,
+        pub world_point: WorldPoint,
+        pub squad_uuid: SquadUuid,
+        pub order: Order,
+        pub behavior: Behavior,
+        pub gesture: Gesture,
+        pub looking_direction: Angle,
+        pub alive: bool,
+        pub unconscious: bool,
+        pub under_fire: Feeling,
+        pub main_weapon: Option<Weapon>,
+        pub magazines: Vec<Magazine>,
+        pub last_shoot_frame_i: u64,
+        pub last_shot_frame_i: u64,
+    }
+
+    impl From<Soldier> for crate::entity::soldier::Soldier {
+        fn from(soldier: Soldier) -> Self {
+            let mut new = Self::new(
+                soldier.uuid,
+                soldier.world_point,
+                soldier.squad_uuid,
+                soldier.side,
+                soldier.main_weapon.map(Into::into),
+                soldier.magazines.into_iter().map(Into::into).collect(),
+            );
+            new.set_order(soldier.order);
+            new.set_behavior(soldier.behavior);
+            new.set_gesture(soldier.gesture);
+            new.set_looking_direction(soldier.looking_direction);
+            new.set_alive(soldier.alive);
+            new.set_unconscious(soldier.unconscious);
+            *new.under_fire_mut() = soldier.under_fire;
+            new.set_last_shoot_frame_i(soldier.last_shoot_frame_i);
+            new.set_last_shot_frame_i(soldier.last_shot_frame_i);
+            new
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub enum VehicleType {
+        T26,
+    }
+
+    impl From<VehicleType> for crate::entity::vehicle::VehicleType {
+        fn from(type_: VehicleType) -> Self {
+            match type_ {
+                VehicleType::T26 => Self("T26".to_string()),
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct Vehicle {
+        pub uuid: VehicleIndex,
+        pub type_: VehicleType,
+        pub world_point: WorldPoint,
+        pub chassis_orientation: Angle,
+        pub main_turret_relative_orientation: Angle,
+    }
+
+    impl From<Vehicle> for crate::entity::vehicle::Vehicle {
+        fn from(vehicle: Vehicle) -> Self {
+            let mut vehicle_ = Self::new(vehicle.uuid, vehicle.type_.into(), vehicle.world_point);
+            vehicle_.set_chassis_orientation(vehicle.chassis_orientation);
+            vehicle_.set_main_turret_relative_orientation(vehicle.main_turret_relative_orientation);
+            vehicle_
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub enum OnBoardPlace {
+        Driver,
+        MainTurretGunner,
+        MainCommandment,
+        Passenger1,
+    }
+
+    impl From<OnBoardPlace> for crate::entity::vehicle::OnBoardPlace {
+        fn from(place: OnBoardPlace) -> Self {
+            match place {
+                OnBoardPlace::Driver => Self::Driver,
+                OnBoardPlace::MainTurretGunner => Self::MainTurretGunner,
+                OnBoardPlace::MainCommandment => Self::MainCommandment,
+                OnBoardPlace::Passenger1 => Self::Passenger(0),
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct BattleStateCopy {
+        pub frame_i: u64,
+        pub soldiers: Vec<Soldier>,
+        pub vehicles: Vec<Vehicle>,
+        pub soldier_on_board: HashMap<SoldierIndex, (VehicleIndex, OnBoardPlace)>,
+        pub phase: Phase,
+        pub flags: FlagsOwnership,
+    }
+
+    impl From<BattleStateCopy> for super::BattleStateCopy {
+        fn from(copy: BattleStateCopy) -> Self {
+            // Squads are computed when state is resolved
+            Self::new(
+                copy.frame_i,
+                copy.soldiers.into_iter().map(Into::into).collect(),
+                copy.vehicles.into_iter().map(Into::into).collect(),
+                copy.soldier_on_board
+                    .into_iter()
+                    .map(|(soldier_index, (vehicle_index, place))| {
+                        (soldier_index, (vehicle_index, place.into()))
+                    })
+                    .collect(),
+                copy.phase,
+                copy.flags,
+                Default::default(),
+                Default::default(),
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                Default::default(),
+                false,
+                false,
+                false,
+                false,
+                Morale(1.0),
+                Morale(1.0),
+                MapControl::empty(),
+                MapControl::empty(),
+            )
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SaveError {
+    #[error("Disk error : {0}")]
+    Disk(#[from] io::Error),
+    #[error("Data format error : {0}")]
+    Format(#[from] bincode::Error),
+    #[error("Save version {0} is not supported (current is {SAVE_VERSION})")]
+    UnsupportedVersion(u32),
+    #[error("Save is for map {1}, not {0}")]
+    MapMismatch(String, String),
+}
+
+#[cfg(test)]
+pub mod test {
+    use oc_core::{morale::Morale, spawn::SpawnZoneName};
+    use rstest::*;
+
+    use crate::{
+        entity::vehicle::{OnBoardPlace, VehicleType},
+        game::{
+            control::MapControl,
+            flag::FlagsOwnership,
+            weapon::{MagazineType, WeaponType},
+        },
+        order::Order,
+        state::battle::phase::Phase,
+        types::{SoldierIndex, VehicleIndex, WorldPoint},
+    };
+
+    use super::*;
+
+    #[fixture]
+    fn copy() -> BattleStateCopy {
+        BattleStateCopy::new(
+            42,
+            vec![],
+            vec![],
+            Default::default(),
+            Phase::Placement,
+            FlagsOwnership::empty(),
+            Default::default(),
+            Default::default(),
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            Default::default(),
+            true,
+            true,
+            false,
+            false,
+            Morale(0.5),
+            Morale(1.0),
+            MapControl::new(vec![SpawnZoneName::North]),
+            MapControl::empty(),
+        )
+    }
+
+    fn with_header<T: Serialize>(version: u32, map_name: &str, payload: &T) -> Vec<u8> {
+        let header = SaveHeader {
+            magic: SAVE_MAGIC,
+            version,
+            map_name: map_name.to_string(),
+        };
+        let mut bytes = bincode::serialize(&header).unwrap();
+        bytes.extend(bincode::serialize(payload).unwrap());
+        bytes
+    }
+
+    #[rstest]
+    fn read_current_version(copy: BattleStateCopy) {
+        // Given
+        let bytes = SaveWriter::to_bytes("map1", &copy).unwrap();
+
+        // When
+        let read = SaveReader::from_bytes(&bytes, "map1").unwrap();
+
+        // Then
+        assert_eq!(read, copy);
+    }
+
+    #[rstest]
+    fn read_unversioned_save() {
+        // Given : map1 battle saved before versioning, after 120 frames of battle with soldier 0
+        // moving and soldiers 3 and 4 in a tank
+        let bytes = include_bytes!("../../assets/map1_unversioned.save");
+
+        // When
+        let read = SaveReader::from_bytes(bytes, "map1").unwrap();
+
+        // Then
+        assert_eq!(read.frame_i(), 121);
+        assert_eq!(read.phase(), &Phase::Battle);
+        assert_eq!(read.soldiers().len(), 10);
+        let soldier = &read.soldiers()[0];
+        assert_eq!(soldier.uuid(), SoldierIndex(0));
+        assert!(matches!(soldier.order(), Order::MoveTo(paths, None)
+            if paths.last_point() == Some(WorldPoint::new(100., 140.))));
+        assert_eq!(
+            soldier.main_weapon().as_ref().map(|weapon| weapon.type_()),
+            Some(&WeaponType("MosinNagantM1924".to_string()))
+        );
+        assert_eq!(
+            soldier
+                .magazines()
+                .iter()
+                .map(|magazine| (magazine.type_().clone(), magazine.fill()))
+                .collect::<Vec<_>>(),
+            vec![(MagazineType("MosinNagant".to_string()), 5); 2]
+        );
+        assert_eq!(
+            read.soldiers()[5]
+                .main_weapon()
+                .as_ref()
+                .map(|weapon| weapon.type_()),
+            Some(&WeaponType("MauserG41".to_string()))
+        );
+        assert_eq!(read.vehicles().len(), 1);
+        assert_eq!(read.vehicles()[0].type_(), &VehicleType("T26".to_string()));
+        assert_eq!(
+            read.soldier_on_board().get(&SoldierIndex(4)),
+            Some(&(VehicleIndex(0), OnBoardPlace::MainTurretGunner))
+        );
+        assert_eq!(read.a_morale(), &Morale(1.0));
+        assert!(read.bullet_fires().is_empty());
+        assert_eq!(read.a_control(), &MapControl::empty());
+    }
+
+    #[rstest]
+    fn refuse_unknown_version_or_other_map(copy: BattleStateCopy) {
+        // Given
+        let future = with_header(SAVE_VERSION + 1, "map1", &copy);
+        let other_map = with_header(SAVE_VERSION, "map2", &copy);
+
+        // When
+        let future = SaveReader::from_bytes(&future, "map1");
+        let other_map = SaveReader::from_bytes(&other_map, "map1");
+
+        // Then
+        assert!(matches!(future, Err(SaveError::UnsupportedVersion(_))));
+        assert!(matches!(other_map, Err(SaveError::MapMismatch(_, _))));
+    }
+}
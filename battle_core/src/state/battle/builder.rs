@@ -1,7 +1,10 @@
 use std::{collections::HashMap, fmt::Display, path::PathBuf};
 
 use crate::{
-    game::flag::FlagsOwnership,
+    game::{
+        catalog::{Catalog, CatalogError, CatalogReader},
+        flag::FlagsOwnership,
+    },
     map::reader::{MapReader, MapReaderError},
 };
 
@@ -10,11 +13,13 @@ use super::{phase::Phase, BattleState};
 pub struct BattleStateBuilder {
     map_name: String,
     resources: PathBuf,
+    catalog: Option<Catalog>,
 }
 
 #[derive(Debug)]
 pub enum BattleStateBuilderError {
     MapReaderError(MapReaderError),
+    Catalog(CatalogError),
 }
 
 impl From<MapReaderError> for BattleStateBuilderError {
@@ -23,12 +28,21 @@ impl From<MapReaderError> for BattleStateBuilderError {
     }
 }
 
+impl From<CatalogError> for BattleStateBuilderError {
+    fn from(error: CatalogError) -> Self {
+        Self::Catalog(error)
+    }
+}
+
 impl Display for BattleStateBuilderError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             BattleStateBuilderError::MapReaderError(error) => {
                 f.write_str(&format!("Map reader error : {}", error))
             }
+            BattleStateBuilderError::Catalog(error) => {
+                f.write_str(&format!("Catalog error : {}", error))
+            }
         }
     }
 }
@@ -38,14 +52,26 @@ impl BattleStateBuilder {
         Self {
             map_name: map_name.to_string(),
             resources,
+            catalog: None,
         }
     }
 
+    /// Use this catalog instead of the resources one (ex. the one a replay was recorded with)
+    pub fn catalog(mut self, catalog: Catalog) -> Self {
+        self.catalog = Some(catalog);
+        self
+    }
+
     pub fn build(&self) -> Result<BattleState, BattleStateBuilderError> {
         let map = MapReader::new(&self.map_name, &self.resources)?.build()?;
+        let catalog = match &self.catalog {
+            Some(catalog) => catalog.clone(),
+            None => CatalogReader::from_resources(&self.resources)?,
+        };
         let mut state = BattleState::new(
             0,
             map,
+            catalog,
             vec![],
             vec![],
             HashMap::new(),
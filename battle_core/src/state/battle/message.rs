@@ -5,14 +5,17 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     behavior::{gesture::Gesture, Behavior},
-    entity::soldier::WeaponClass,
-    game::flag::FlagsOwnership,
+    entity::{soldier::WeaponClass, vehicle::VehicleDamage},
+    game::{flag::FlagsOwnership, weapon::Magazine},
     order::Order,
     physics::{
-        event::{bullet::BulletFire, cannon_blast::CannonBlast, explosion::Explosion},
+        event::{
+            bullet::BulletFire, cannon_blast::CannonBlast, explosion::Explosion,
+            indirect::IndirectFire,
+        },
         visibility::Visibility,
     },
-    types::{Angle, SoldierIndex, SquadUuid, VehicleIndex, WorldPoint},
+    types::{Angle, PlayerName, SoldierBoard, SoldierIndex, SquadUuid, VehicleIndex, WorldPoint},
 };
 
 use super::phase::Phase;
@@ -24,8 +27,14 @@ pub enum BattleStateMessage {
     Vehicle(VehicleIndex, VehicleMessage),
     PushBulletFire(BulletFire),
     PushExplosion(Explosion),
+    PushIndirectFire(IndirectFire),
     PushCannonBlast(CannonBlast),
     SetVisibilities(HashMap<(SoldierIndex, SoldierIndex), Visibility>),
+    // Changed visibilities and removed ones since last visibilities replication
+    UpdateVisibilities(
+        HashMap<(SoldierIndex, SoldierIndex), Visibility>,
+        Vec<(SoldierIndex, SoldierIndex)>,
+    ),
     SetPhase(Phase),
     SetAConnected(bool),
     SetBConnected(bool),
@@ -35,6 +44,10 @@ pub enum BattleStateMessage {
     SetBMorale(Morale),
     SetFlagsOwnership(FlagsOwnership),
     SetSquadLeader(SquadUuid, SoldierIndex),
+    // Soldier get on (or off with None) a vehicle
+    SetSoldierBoard(SoldierIndex, Option<SoldierBoard>),
+    // Squad is owned by a player (or by nobody with None)
+    SetSquadOwner(SquadUuid, Option<PlayerName>),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -52,13 +65,18 @@ pub enum SoldierMessage {
     ReloadWeapon(WeaponClass),
     WeaponShot(WeaponClass),
     SetLastShootFrameI(u64),
+    SetWeaponSetup(Option<Angle>),
+    LoadWeapon(WeaponClass, Magazine),
+    RemoveMagazine(Magazine),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub enum VehicleMessage {
     SetWorldPosition(WorldPoint),
     SetChassisOrientation(Angle),
-    // SetMainTurretOrientation(Angle),
+    // Relative to chassis orientation
+    SetMainTurretOrientation(Angle),
+    AddDamage(VehicleDamage),
 }
 
 // TODO : Side effects should not exists : All side effects
@@ -1,23 +1,25 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use oc_core::morale::Morale;
 
 use crate::{
     deployment::Deployment,
     entity::{soldier::Soldier, vehicle::Vehicle},
-    game::{control::MapControl, flag::FlagsOwnership, Side},
-    graphics::vehicle::VehicleGraphicInfos,
+    game::{catalog::Catalog, control::MapControl, flag::FlagsOwnership, Side},
     map::Map,
     order::Order,
     physics::{
-        event::{bullet::BulletFire, cannon_blast::CannonBlast, explosion::Explosion},
+        event::{
+            bullet::BulletFire, cannon_blast::CannonBlast, explosion::Explosion,
+            indirect::IndirectFire,
+        },
         path::{Direction, PathMode},
         visibility::Visibilities,
     },
     sync::BattleStateCopy,
     types::{
-        SoldierBoard, SoldierIndex, SoldiersOnBoard, SquadComposition, SquadUuid, VehicleBoard,
-        VehicleIndex,
+        SoldierBoard, SoldierIndex, SoldiersOnBoard, SquadComposition, SquadUuid, SquadsOwners,
+        VehicleBoard, VehicleIndex, WorldPoint,
     },
     utils::{vehicle_board_from_soldiers_on_board, WorldShape},
 };
@@ -39,14 +41,17 @@ pub mod visibility;
 pub struct BattleState {
     frame_i: u64,
     map: Map,
+    catalog: Catalog,
     phase: Phase,
     soldiers: Vec<Soldier>,
     vehicles: Vec<Vehicle>,
     soldier_on_board: SoldiersOnBoard,
     vehicle_board: VehicleBoard,
     squads: HashMap<SquadUuid, SquadComposition>,
+    squads_owners: SquadsOwners,
     bullet_fires: Vec<BulletFire>,
     explosions: Vec<Explosion>,
+    indirect_fires: Vec<IndirectFire>,
     cannon_blasts: Vec<CannonBlast>,
     visibilities: Visibilities,
     a_connected: bool,
@@ -56,12 +61,17 @@ pub struct BattleState {
     a_morale: Morale,
     b_morale: Morale,
     flags: FlagsOwnership,
+    // Spawn zones where each side can place its soldiers
+    a_control: MapControl,
+    b_control: MapControl,
 }
 
 impl BattleState {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         frame_i: u64,
         map: Map,
+        catalog: Catalog,
         soldiers: Vec<Soldier>,
         vehicles: Vec<Vehicle>,
         soldier_on_board: SoldiersOnBoard,
@@ -72,14 +82,17 @@ impl BattleState {
         Self {
             frame_i,
             map,
+            catalog,
             phase,
             soldiers,
             vehicles,
             soldier_on_board,
             vehicle_board,
             squads: HashMap::new(),
+            squads_owners: HashMap::new(),
             bullet_fires: vec![],
             explosions: vec![],
+            indirect_fires: vec![],
             cannon_blasts: vec![],
             visibilities: Visibilities::default(),
             a_connected: false,
@@ -89,21 +102,26 @@ impl BattleState {
             a_morale: Morale(1.0), // FIXME BS NOW : from context ?
             b_morale: Morale(1.0), // FIXME BS NOW : from context ?
             flags,
+            a_control: MapControl::empty(),
+            b_control: MapControl::empty(),
         }
     }
 
-    pub fn empty(map: &Map) -> Self {
+    pub fn empty(map: &Map, catalog: &Catalog) -> Self {
         Self {
             frame_i: 0,
             map: map.clone(),
+            catalog: catalog.clone(),
             phase: Phase::Placement,
             soldiers: vec![],
             vehicles: vec![],
             soldier_on_board: HashMap::new(),
             vehicle_board: HashMap::new(),
             squads: HashMap::new(),
+            squads_owners: HashMap::new(),
             bullet_fires: vec![],
             explosions: vec![],
+            indirect_fires: vec![],
             cannon_blasts: vec![],
             visibilities: Visibilities::default(),
             a_connected: false, // TODO : should be in (server) Runner ?
@@ -113,19 +131,38 @@ impl BattleState {
             a_morale: Morale(1.0),
             b_morale: Morale(1.0),
             flags: FlagsOwnership::empty(),
+            a_control: MapControl::empty(),
+            b_control: MapControl::empty(),
         }
     }
 
-    pub fn from_copy(copy: &BattleStateCopy, map: &Map) -> Self {
-        Self::new(
+    pub fn from_copy(copy: &BattleStateCopy, map: &Map, catalog: &Catalog) -> Self {
+        let mut battle_state = Self::new(
             copy.frame_i(),
             map.clone(),
+            catalog.clone(),
             copy.soldiers().clone(),
             copy.vehicles().clone(),
             copy.soldier_on_board().clone(),
             copy.phase().clone(),
             copy.flags().clone(),
-        )
+        );
+        battle_state.squads = copy.squads().clone();
+        battle_state.squads_owners = copy.squads_owners().clone();
+        battle_state.bullet_fires = copy.bullet_fires().clone();
+        battle_state.explosions = copy.explosions().clone();
+        battle_state.indirect_fires = copy.indirect_fires().clone();
+        battle_state.cannon_blasts = copy.cannon_blasts().clone();
+        battle_state.visibilities = copy.visibilities().clone();
+        battle_state.a_connected = copy.a_connected();
+        battle_state.b_connected = copy.b_connected();
+        battle_state.a_ready = copy.a_ready();
+        battle_state.b_ready = copy.b_ready();
+        battle_state.a_morale = copy.a_morale().clone();
+        battle_state.b_morale = copy.b_morale().clone();
+        battle_state.a_control = copy.a_control().clone();
+        battle_state.b_control = copy.b_control().clone();
+        battle_state
     }
 
     pub fn resolve(&mut self) {
@@ -140,6 +177,7 @@ impl BattleState {
         let frame_i = replaced_frame_i.unwrap_or(self.frame_i);
         self.bullet_fires.retain(|b| !b.finished(frame_i));
         self.explosions.retain(|e| !e.finished(frame_i));
+        self.indirect_fires.retain(|i| !i.finished(frame_i));
         self.cannon_blasts.retain(|b| !b.finished(frame_i));
     }
 
@@ -151,6 +189,10 @@ impl BattleState {
         &self.map
     }
 
+    pub fn catalog(&self) -> &Catalog {
+        &self.catalog
+    }
+
     pub fn visibilities(&self) -> &Visibilities {
         &self.visibilities
     }
@@ -187,6 +229,10 @@ impl BattleState {
         self.squads = squads;
     }
 
+    pub fn squads_owners(&self) -> &SquadsOwners {
+        &self.squads_owners
+    }
+
     pub fn all_orders(&self, side: &Side) -> Vec<(SquadUuid, &Order)> {
         let mut orders: Vec<(SquadUuid, &Order)> = vec![];
 
@@ -222,6 +268,10 @@ impl BattleState {
         self.explosions.as_ref()
     }
 
+    pub fn indirect_fires(&self) -> &Vec<IndirectFire> {
+        self.indirect_fires.as_ref()
+    }
+
     pub fn cannon_blasts(&self) -> &Vec<CannonBlast> {
         self.cannon_blasts.as_ref()
     }
@@ -250,7 +300,7 @@ impl BattleState {
         if let Some(vehicle_index) = self.soldier_vehicle(squad_leader_index) {
             let vehicle = self.vehicle(vehicle_index);
             (
-                PathMode::Drive(*VehicleGraphicInfos::from_type(vehicle.type_()).size()),
+                PathMode::Drive(self.vehicle_definition(vehicle).size()),
                 Some(Direction::from_angle(vehicle.chassis_orientation())),
             )
         } else {
@@ -281,6 +331,11 @@ impl BattleState {
                 explosion.init(frame_i + 1);
                 self.explosions.push(explosion)
             }
+            BattleStateMessage::PushIndirectFire(indirect_fire) => {
+                let mut indirect_fire = indirect_fire.clone();
+                indirect_fire.init(frame_i + 1);
+                self.indirect_fires.push(indirect_fire)
+            }
             BattleStateMessage::PushCannonBlast(cannon_blast) => {
                 let mut cannon_blast = cannon_blast.clone();
                 cannon_blast.init(frame_i + 1);
@@ -289,6 +344,9 @@ impl BattleState {
             BattleStateMessage::SetVisibilities(visibilities) => {
                 self.visibilities.set(visibilities.clone())
             }
+            BattleStateMessage::UpdateVisibilities(changes, removed) => {
+                self.visibilities.update(changes.clone(), removed)
+            }
             BattleStateMessage::SetPhase(phase) => self.phase = phase.clone(),
             BattleStateMessage::SetAConnected(value) => self.a_connected = *value,
             BattleStateMessage::SetBConnected(value) => self.b_connected = *value,
@@ -304,22 +362,44 @@ impl BattleState {
                     .expect("Squad indexes must be consistent")
                     .leader_mut() = *soldier_index
             }
+            BattleStateMessage::SetSoldierBoard(soldier_index, soldier_board) => {
+                self.set_soldier_board(*soldier_index, soldier_board.clone())
+            }
+            BattleStateMessage::SetSquadOwner(squad_uuid, owner) => match owner {
+                Some(owner) => {
+                    self.squads_owners.insert(*squad_uuid, owner.clone());
+                }
+                None => {
+                    self.squads_owners.remove(squad_uuid);
+                }
+            },
         };
 
         vec![]
     }
 
+    /// Add deployment soldiers and vehicles after already deployed ones (deployment indexes
+    /// are shifted accordingly), so each side can be deployed by its own player
     pub fn inject(&mut self, deployment: &Deployment) {
+        let deployment = deployment.shifted(self.soldiers.len(), self.vehicles.len());
         for soldier_deployment in deployment.soldiers() {
             self.soldiers.push(Soldier::from(soldier_deployment))
         }
         for vehicle_deployment in deployment.vehicles() {
             self.vehicles.push(Vehicle::from(vehicle_deployment))
         }
-        self.soldier_on_board = deployment.boards().clone();
+        self.soldier_on_board.extend(deployment.boards().clone());
+        self.vehicle_board = vehicle_board_from_soldiers_on_board(&self.soldier_on_board);
+        self.arm_main_turret_gunners();
+        self.squads_owners
+            .extend(deployment.squads_owners().clone());
         self.resolve();
     }
 
+    pub fn side_is_deployed(&self, side: &Side) -> bool {
+        self.soldiers.iter().any(|soldier| soldier.side() == side)
+    }
+
     pub fn debug_lines(&self) -> Vec<(String, String)> {
         vec![
             (
@@ -342,6 +422,105 @@ impl BattleState {
             self.soldier_on_board.clone(),
             self.phase.clone(),
             self.flags.clone(),
+            self.squads.clone(),
+            self.squads_owners.clone(),
+            self.bullet_fires.clone(),
+            self.explosions.clone(),
+            self.indirect_fires.clone(),
+            self.cannon_blasts.clone(),
+            self.visibilities.clone(),
+            self.a_connected,
+            self.b_connected,
+            self.a_ready,
+            self.b_ready,
+            self.a_morale.clone(),
+            self.b_morale.clone(),
+            self.a_control.clone(),
+            self.b_control.clone(),
+        )
+    }
+
+    /// Copy of what given side is allowed to know. Opponent soldiers and vehicles it can't see
+    /// are replaced by idle ones at map origin, visible opponents don't reveal their orders,
+    /// and only visibilities and shots from known positions are kept. Observers know all.
+    pub fn side_copy(&self, side: &Side) -> BattleStateCopy {
+        if side == &Side::Observer || side == &Side::All {
+            return self.copy();
+        }
+
+        let known = self.soldiers_known_by_side(side);
+        let own: HashSet<SoldierIndex> = self
+            .soldiers
+            .iter()
+            .filter(|soldier| soldier.side() == side)
+            .map(|soldier| soldier.uuid())
+            .collect();
+        let hidden_point = WorldPoint::new(0., 0.);
+
+        let soldiers = self
+            .soldiers
+            .iter()
+            .map(|soldier| {
+                if own.contains(&soldier.uuid()) {
+                    soldier.clone()
+                } else if known.contains(&soldier.uuid()) {
+                    let mut soldier = soldier.clone();
+                    soldier.set_order(Order::Idle);
+                    soldier.set_behavior(soldier.behavior().without_route());
+                    soldier
+                } else {
+                    let mut soldier = Soldier::from_soldier(soldier);
+                    soldier.set_world_point(hidden_point);
+                    soldier
+                }
+            })
+            .collect();
+        let vehicles = self
+            .vehicles
+            .iter()
+            .map(|vehicle| {
+                if self.vehicle_is_known(vehicle.uuid(), &known) {
+                    vehicle.clone()
+                } else {
+                    Vehicle::new(*vehicle.uuid(), vehicle.type_().clone(), hidden_point)
+                }
+            })
+            .collect();
+
+        BattleStateCopy::new(
+            self.frame_i,
+            soldiers,
+            vehicles,
+            self.soldier_on_board.clone(),
+            self.phase.clone(),
+            self.flags.clone(),
+            self.squads.clone(),
+            self.squads_owners.clone(),
+            self.bullet_fires
+                .iter()
+                .filter(|fire| self.shot_origin_is_known(fire.from(), &known))
+                .cloned()
+                .collect(),
+            self.explosions.clone(),
+            self.indirect_fires
+                .iter()
+                .filter(|fire| self.shot_origin_is_known(fire.from(), &known))
+                .cloned()
+                .collect(),
+            self.cannon_blasts
+                .iter()
+                .filter(|blast| self.shot_origin_is_known(blast.point(), &known))
+                .cloned()
+                .collect(),
+            self.visibilities.from_soldiers(&own),
+            self.a_connected,
+            self.b_connected,
+            self.a_ready,
+            self.b_ready,
+            self.a_morale.clone(),
+            self.b_morale.clone(),
+            self.a_control.clone(),
+            self.b_control.clone(),
         )
     }
 
@@ -378,11 +557,38 @@ impl BattleState {
             Side::A => self.a_ready,
             Side::B => self.b_ready,
             Side::All => panic!("Never call ready for Side::All"),
+            Side::Observer => false,
+        }
+    }
+
+    /// Set sides controls, except for sides given with an empty control or which already have
+    /// one (each player only gives its own side control)
+    pub fn load_controls(&mut self, a_control: MapControl, b_control: MapControl) {
+        if self.a_control.spawn_zone_names().is_empty() {
+            self.a_control = a_control;
+        }
+        if self.b_control.spawn_zone_names().is_empty() {
+            self.b_control = b_control;
         }
+        self.flags = FlagsOwnership::from_control(&self.map, &self.a_control, &self.b_control);
     }
 
-    pub fn update_flags_from_control(&mut self, a_control: MapControl, b_control: MapControl) {
-        self.flags = FlagsOwnership::from_control(&self.map, &a_control, &b_control);
+    /// Side can place its soldiers in its spawn zones, except in ones also controlled by opponent
+    pub fn allowed_placement_point(&self, side: &Side, point: &WorldPoint) -> bool {
+        let (allowed_control, opponent_control) = self.zone_controls(side);
+        self.map.point_in_spawn_zones(point, allowed_control, true)
+            && !self
+                .map
+                .point_in_spawn_zones(point, opponent_control, false)
+    }
+
+    /// Given side spawn zones and its opponent ones
+    pub fn zone_controls(&self, side: &Side) -> (&MapControl, &MapControl) {
+        if side == &Side::A {
+            (&self.a_control, &self.b_control)
+        } else {
+            (&self.b_control, &self.a_control)
+        }
     }
 
     pub fn flags(&self) -> &FlagsOwnership {
@@ -21,7 +21,8 @@ impl BattleState {
             match &order {
                 Order::MoveTo(world_paths, _)
                 | Order::MoveFastTo(world_paths, _)
-                | Order::SneakTo(world_paths, _) => {
+                | Order::SneakTo(world_paths, _)
+                | Order::ReverseTo(world_paths, _) => {
                     // Return one couple by move path (because can have multiple move paths))
                     marker_data.extend::<Vec<(
                         Order,
@@ -56,7 +57,7 @@ impl BattleState {
                         OrderMarkerIndex(0),
                     ));
                 }
-                Order::Idle => {}
+                Order::Idle | Order::Board(_) | Order::Dismount => {}
                 Order::EngageSquad(squad_index) => {
                     let squad = self.squad(*squad_index);
                     let leader = self.soldier(squad.leader());
@@ -68,7 +69,7 @@ impl BattleState {
                         OrderMarkerIndex(0),
                     ));
                 }
-                Order::SuppressFire(point) => {
+                Order::SuppressFire(point) | Order::IndirectFire(point) => {
                     marker_data.push((
                         order.clone(),
                         marker.clone().unwrap(), // FIXME unwrap to remove
@@ -46,7 +46,7 @@ pub struct Victorious(pub Side);
 impl Display for Victorious {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.0 {
-            Side::All => f.write_str("Undetermined"),
+            Side::All | Side::Observer => f.write_str("Undetermined"),
             Side::A | Side::B => f.write_str(&self.0.to_string()),
         }
     }
@@ -12,7 +12,7 @@ impl BattleState {
         soldier_message: &SoldierMessage,
     ) -> Vec<SideEffect> {
         let frame_i = self.frame_i;
-        let soldier = &mut self.soldier_mut(*soldier_index);
+        let soldier = &mut self.soldiers[soldier_index.0];
         match soldier_message {
             SoldierMessage::SetWorldPosition(new_world_point) => {
                 soldier.set_world_point(*new_world_point)
@@ -41,9 +41,14 @@ impl BattleState {
             }
             SoldierMessage::DecreaseUnderFire => soldier.decrease_under_fire(),
             SoldierMessage::SetOrder(order) => soldier.set_order(order.clone()),
-            SoldierMessage::ReloadWeapon(class) => soldier.reload_weapon(class),
-            SoldierMessage::WeaponShot(class) => soldier.weapon_shot(class),
+            SoldierMessage::ReloadWeapon(class) => soldier.reload_weapon(class, &self.catalog),
+            SoldierMessage::WeaponShot(class) => soldier.weapon_shot(class, &self.catalog, frame_i),
             SoldierMessage::SetLastShootFrameI(frame_i) => soldier.set_last_shoot_frame_i(*frame_i),
+            SoldierMessage::SetWeaponSetup(setup) => soldier.set_weapon_setup(*setup),
+            SoldierMessage::LoadWeapon(class, magazine) => {
+                soldier.load_weapon(class, magazine.clone())
+            }
+            SoldierMessage::RemoveMagazine(magazine) => soldier.remove_magazine(magazine),
         }
 
         vec![]
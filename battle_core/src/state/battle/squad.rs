@@ -2,12 +2,24 @@ use std::collections::{HashMap, HashSet};
 
 use crate::{
     behavior::BehaviorMode,
+    entity::soldier::Soldier,
+    game::player::Player,
     types::{SoldierIndex, SquadComposition, SquadUuid},
 };
 
 use super::BattleState;
 
 impl BattleState {
+    /// Player can control soldiers of its side, except ones of squads owned by another player
+    pub fn player_controls(&self, player: &Player, soldier: &Soldier) -> bool {
+        soldier.side() == player.side()
+            && self
+                .squads_owners()
+                .get(&soldier.squad_uuid())
+                .map(|owner| owner == player.name())
+                .unwrap_or(true)
+    }
+
     pub fn update_squads(&mut self) {
         let mut new_squads = HashMap::new();
 
@@ -39,6 +51,13 @@ impl BattleState {
             return None;
         }
 
+        // Keep current leader (ex. when state is rebuilt from a copy)
+        if let Some(squad) = self.squads().get(&squad_uuid) {
+            if squad_entities.contains(&squad.leader()) {
+                return Some(squad.leader());
+            }
+        }
+
         // For now, election is done by get the first
         Some(
             *squad_entities
@@ -1,8 +1,12 @@
 use crate::{
-    entity::vehicle::OnBoardPlace,
+    entity::{
+        soldier::WeaponClass,
+        vehicle::{OnBoardPlace, Vehicle, VehicleDefinition},
+    },
+    game::weapon::Weapon,
     graphics::vehicle::VehicleGraphicInfos,
-    types::{SoldierIndex, VehicleIndex, WorldPoint},
-    utils::apply_angle_on_point,
+    types::{SoldierBoard, SoldierIndex, VehicleIndex, WorldPoint},
+    utils::{apply_angle_on_point, vehicle_board_from_soldiers_on_board},
 };
 
 use super::{
@@ -11,11 +15,18 @@ use super::{
 };
 
 impl BattleState {
+    /// Definition of the vehicle type (deployments with unknown vehicle types are refused)
+    pub fn vehicle_definition(&self, vehicle: &Vehicle) -> &VehicleDefinition {
+        self.catalog()
+            .vehicle(vehicle.type_())
+            .expect("Vehicle types must be checked at deployment")
+    }
+
     pub fn check_board_integrity(&self) -> Result<(), BattleStateError> {
         // Check if on boards are coherent with vehicle types
         for (vehicle_index, board) in self.vehicle_board() {
             let vehicle = self.vehicle(*vehicle_index);
-            let board_composition = vehicle.type_().board_composition();
+            let board_composition = self.vehicle_definition(vehicle).board_composition();
 
             for (place, _) in board {
                 if !board_composition.contains(place) {
@@ -45,16 +56,22 @@ impl BattleState {
                 vehicle.set_chassis_orientation(*angle);
                 self.propagate_vehicle_position(*vehicle_index);
             }
+            VehicleMessage::SetMainTurretOrientation(angle) => {
+                vehicle.set_main_turret_relative_orientation(*angle);
+            }
+            VehicleMessage::AddDamage(damage) => {
+                vehicle.add_damage(*damage);
+            }
         }
 
         vec![]
     }
 
     pub fn propagate_vehicle_position(&mut self, vehicle_index: VehicleIndex) {
-        let vehicle = &mut self.vehicle_mut(vehicle_index);
+        let vehicle = self.vehicle(vehicle_index);
         let vehicle_point = vehicle.world_point();
         let vehicle_orientation = *vehicle.chassis_orientation();
-        let sprite_infos = VehicleGraphicInfos::from_type(vehicle.type_());
+        let sprite_infos = VehicleGraphicInfos::from_definition(self.vehicle_definition(vehicle));
         let places = sprite_infos.places();
         let mut new_positions: Vec<(SoldierIndex, WorldPoint)> = vec![];
         for (place, soldier_index) in self.vehicle_board().get(&vehicle_index).unwrap_or(&vec![]) {
@@ -76,12 +93,77 @@ impl BattleState {
         }
     }
 
+    /// Give vehicles main gun to their main turret gunner deployed without weapon (ammunition
+    /// stay the gunner magazines)
+    pub fn arm_main_turret_gunners(&mut self) {
+        let mut weapons = vec![];
+        for (soldier_index, (vehicle_index, place)) in &self.soldier_on_board {
+            if place != &OnBoardPlace::MainTurretGunner
+                || self.soldier(*soldier_index).main_weapon().is_some()
+            {
+                continue;
+            }
+
+            if let Some(main_gun) = self
+                .vehicle_definition(self.vehicle(*vehicle_index))
+                .main_gun()
+            {
+                weapons.push((*soldier_index, Weapon::new(main_gun.clone(), false, None)));
+            }
+        }
+
+        for (soldier_index, weapon) in weapons {
+            *self
+                .soldier_mut(soldier_index)
+                .weapon_mut(&WeaponClass::Main) = Some(weapon);
+        }
+    }
+
     pub fn initialize_vehicle_positions(&mut self) {
         for i in 0..self.vehicles().len() {
             self.propagate_vehicle_position(VehicleIndex(i))
         }
     }
 
+    /// Place soldier on given vehicle place (if still free) or take it off its vehicle
+    pub fn set_soldier_board(
+        &mut self,
+        soldier_index: SoldierIndex,
+        soldier_board: Option<SoldierBoard>,
+    ) {
+        match soldier_board {
+            Some((vehicle_index, place)) => {
+                if self.vehicle_place_is_free(vehicle_index, &place) {
+                    self.soldier_on_board
+                        .insert(soldier_index, (vehicle_index, place));
+                    self.vehicle_board =
+                        vehicle_board_from_soldiers_on_board(&self.soldier_on_board);
+                    self.propagate_vehicle_position(vehicle_index);
+                }
+            }
+            None => {
+                self.soldier_on_board.remove(&soldier_index);
+                self.vehicle_board = vehicle_board_from_soldiers_on_board(&self.soldier_on_board);
+            }
+        }
+    }
+
+    pub fn vehicle_place_is_free(&self, vehicle_index: VehicleIndex, place: &OnBoardPlace) -> bool {
+        !self
+            .vehicle_board()
+            .get(&vehicle_index)
+            .map(|board| board.iter().any(|(place_, _)| place_ == place))
+            .unwrap_or(false)
+    }
+
+    /// First place of vehicle composition which is not taken by a soldier
+    pub fn vehicle_free_place(&self, vehicle_index: VehicleIndex) -> Option<OnBoardPlace> {
+        self.vehicle_definition(self.vehicle(vehicle_index))
+            .board_composition()
+            .into_iter()
+            .find(|place| self.vehicle_place_is_free(vehicle_index, place))
+    }
+
     pub fn soldier_vehicle_place(&self, soldier_index: SoldierIndex) -> Option<&OnBoardPlace> {
         if let Some((_, place)) = self.soldier_board(soldier_index) {
             return Some(place);
@@ -1,15 +1,21 @@
+use std::collections::HashSet;
+
 use crate::{
-    config::ServerConfig,
+    config::{ServerConfig, KNOWN_SHOT_ORIGIN_DISTANCE},
     entity::soldier::Soldier,
     game::Side,
     physics::visibility::Visibility,
-    types::{SquadUuid, WorldPoint},
+    types::{SoldierIndex, SquadUuid, VehicleIndex, WorldPoint},
 };
 
 use super::BattleState;
 
 impl BattleState {
     pub fn soldier_is_visible_by_side(&self, soldier: &Soldier, side: &Side) -> bool {
+        if side == &Side::Observer {
+            return true;
+        }
+
         for visibility in self.visibilities().visibles_soldiers().iter().filter(|v| {
             self.soldier(
                 v.from_soldier
@@ -26,7 +32,70 @@ impl BattleState {
         false
     }
 
+    /// Soldiers of which given side know the state : its own ones and the opponent ones it
+    /// currently see (observers know all soldiers)
+    pub fn soldiers_known_by_side(&self, side: &Side) -> HashSet<SoldierIndex> {
+        if side == &Side::Observer || side == &Side::All {
+            return self.soldiers().iter().map(|s| s.uuid()).collect();
+        }
+
+        let mut known: HashSet<SoldierIndex> = self
+            .soldiers()
+            .iter()
+            .filter(|s| s.side() == side)
+            .map(|s| s.uuid())
+            .collect();
+        known.extend(
+            self.visibilities()
+                .visibles_soldiers()
+                .iter()
+                .filter(|v| {
+                    known.contains(
+                        &v.from_soldier
+                            .expect("visibles_soldiers implies from_soldier"),
+                    )
+                })
+                .map(|v| v.to_soldier.expect("visibles_soldiers implies to_soldier"))
+                .collect::<Vec<SoldierIndex>>(),
+        );
+
+        known
+    }
+
+    /// Vehicle is known when one of its crew members is (crewless vehicles are known by all)
+    pub fn vehicle_is_known(
+        &self,
+        vehicle_index: &VehicleIndex,
+        known_soldiers: &HashSet<SoldierIndex>,
+    ) -> bool {
+        match self.vehicle_board().get(vehicle_index) {
+            Some(board) if !board.is_empty() => board
+                .iter()
+                .any(|(_, soldier_index)| known_soldiers.contains(soldier_index)),
+            _ => true,
+        }
+    }
+
+    /// Shot origin (bullet, cannon blast, etc) is known when a known soldier stand there
+    pub fn shot_origin_is_known(
+        &self,
+        origin: &WorldPoint,
+        known_soldiers: &HashSet<SoldierIndex>,
+    ) -> bool {
+        known_soldiers.iter().any(|soldier_index| {
+            self.soldier(*soldier_index)
+                .world_point()
+                .to_vec2()
+                .distance(origin.to_vec2())
+                <= KNOWN_SHOT_ORIGIN_DISTANCE
+        })
+    }
+
     pub fn soldier_squad_is_visible_by_side(&self, soldier: &Soldier, side: &Side) -> bool {
+        if side == &Side::Observer {
+            return true;
+        }
+
         for soldier_uuid in self.squad(soldier.squad_uuid()).members() {
             let squad_solider = self.soldier(*soldier_uuid);
             for visibility in self.visibilities().visibles_soldiers().iter().filter(|v| {
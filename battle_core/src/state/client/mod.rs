@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{audio::Sound, utils::NewDebugPoint};
+use crate::{audio::Sound, game::weapon::WeaponSound, utils::NewDebugPoint};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ClientStateMessage {
@@ -8,5 +8,8 @@ pub enum ClientStateMessage {
     PlayInterfaceSound(Sound),
     // TODO : ajouter le WorldPoint
     PlayBattleSound(Sound),
+    PlayWeaponSound(WeaponSound),
     BattleStarted,
+    // Server reset for a new match, clients must join again to play it
+    MatchClosed,
 }
@@ -1,12 +1,23 @@
+use std::collections::HashMap;
+
+use oc_core::morale::Morale;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     entity::{soldier::Soldier, vehicle::Vehicle},
-    game::flag::FlagsOwnership,
+    game::{control::MapControl, flag::FlagsOwnership},
+    physics::{
+        event::{
+            bullet::BulletFire, cannon_blast::CannonBlast, explosion::Explosion,
+            indirect::IndirectFire,
+        },
+        visibility::Visibilities,
+    },
     state::battle::phase::Phase,
-    types::SoldiersOnBoard,
+    types::{SoldiersOnBoard, SquadComposition, SquadUuid, SquadsOwners},
 };
 
+/// Everything needed to rebuild a `BattleState` exactly as it was (with the map)
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct BattleStateCopy {
     frame_i: u64,
@@ -15,9 +26,25 @@ pub struct BattleStateCopy {
     soldier_on_board: SoldiersOnBoard,
     phase: Phase,
     flags: FlagsOwnership,
+    squads: HashMap<SquadUuid, SquadComposition>,
+    squads_owners: SquadsOwners,
+    bullet_fires: Vec<BulletFire>,
+    explosions: Vec<Explosion>,
+    indirect_fires: Vec<IndirectFire>,
+    cannon_blasts: Vec<CannonBlast>,
+    visibilities: Visibilities,
+    a_connected: bool,
+    b_connected: bool,
+    a_ready: bool,
+    b_ready: bool,
+    a_morale: Morale,
+    b_morale: Morale,
+    a_control: MapControl,
+    b_control: MapControl,
 }
 
 impl BattleStateCopy {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         frame_i: u64,
         soldiers: Vec<Soldier>,
@@ -25,6 +52,21 @@ impl BattleStateCopy {
         soldier_on_board: SoldiersOnBoard,
         phase: Phase,
         flags: FlagsOwnership,
+        squads: HashMap<SquadUuid, SquadComposition>,
+        squads_owners: SquadsOwners,
+        bullet_fires: Vec<BulletFire>,
+        explosions: Vec<Explosion>,
+        indirect_fires: Vec<IndirectFire>,
+        cannon_blasts: Vec<CannonBlast>,
+        visibilities: Visibilities,
+        a_connected: bool,
+        b_connected: bool,
+        a_ready: bool,
+        b_ready: bool,
+        a_morale: Morale,
+        b_morale: Morale,
+        a_control: MapControl,
+        b_control: MapControl,
     ) -> BattleStateCopy {
         Self {
             frame_i,
@@ -33,6 +75,21 @@ impl BattleStateCopy {
             soldier_on_board,
             phase,
             flags,
+            squads,
+            squads_owners,
+            bullet_fires,
+            explosions,
+            indirect_fires,
+            cannon_blasts,
+            visibilities,
+            a_connected,
+            b_connected,
+            a_ready,
+            b_ready,
+            a_morale,
+            b_morale,
+            a_control,
+            b_control,
         }
     }
 
@@ -59,4 +116,64 @@ impl BattleStateCopy {
     pub fn flags(&self) -> &FlagsOwnership {
         &self.flags
     }
+
+    pub fn squads(&self) -> &HashMap<SquadUuid, SquadComposition> {
+        &self.squads
+    }
+
+    pub fn squads_owners(&self) -> &SquadsOwners {
+        &self.squads_owners
+    }
+
+    pub fn bullet_fires(&self) -> &Vec<BulletFire> {
+        &self.bullet_fires
+    }
+
+    pub fn explosions(&self) -> &Vec<Explosion> {
+        &self.explosions
+    }
+
+    pub fn indirect_fires(&self) -> &Vec<IndirectFire> {
+        &self.indirect_fires
+    }
+
+    pub fn cannon_blasts(&self) -> &Vec<CannonBlast> {
+        &self.cannon_blasts
+    }
+
+    pub fn visibilities(&self) -> &Visibilities {
+        &self.visibilities
+    }
+
+    pub fn a_connected(&self) -> bool {
+        self.a_connected
+    }
+
+    pub fn b_connected(&self) -> bool {
+        self.b_connected
+    }
+
+    pub fn a_ready(&self) -> bool {
+        self.a_ready
+    }
+
+    pub fn b_ready(&self) -> bool {
+        self.b_ready
+    }
+
+    pub fn a_morale(&self) -> &Morale {
+        &self.a_morale
+    }
+
+    pub fn b_morale(&self) -> &Morale {
+        &self.b_morale
+    }
+
+    pub fn a_control(&self) -> &MapControl {
+        &self.a_control
+    }
+
+    pub fn b_control(&self) -> &MapControl {
+        &self.b_control
+    }
 }
@@ -334,6 +334,10 @@ impl WorldPaths {
         }
     }
 
+    pub fn last_point(&self) -> Option<WorldPoint> {
+        self.paths.last().and_then(|path| path.last_point())
+    }
+
     pub fn remove_next_point(&mut self) -> Option<WorldPoint> {
         if let Some(path) = self.paths.first_mut() {
             let point = path
@@ -348,6 +352,15 @@ impl WorldPaths {
         None
     }
 
+    /// Paths reduced to their next point only
+    pub fn next_step(&self) -> Self {
+        Self::new(
+            self.next_point()
+                .map(|point| vec![WorldPath::new(vec![point])])
+                .unwrap_or_default(),
+        )
+    }
+
     pub fn is_last_point(&self) -> Option<bool> {
         if self.paths.is_empty() {
             None
@@ -392,6 +405,29 @@ pub struct ExplosionIndex(pub usize);
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct SquadUuid(pub usize);
 
+impl Display for SquadUuid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}", self.0))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct PlayerName(pub String);
+
+impl Display for PlayerName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Secret given by the server to an accepted client, which must join it to each request
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct PlayerToken(pub u128);
+
+/// Squads which can only be controlled by given player (other squads can be controlled by any
+/// player of their side)
+pub type SquadsOwners = HashMap<SquadUuid, PlayerName>;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SquadComposition(SoldierIndex, Vec<SoldierIndex>);
 
@@ -556,7 +592,7 @@ impl Distance {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Precision(u8);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,4 +1,7 @@
-use battle_core::audio::Sound;
+use battle_core::{
+    audio::Sound,
+    game::{catalog::Catalog, weapon::WeaponSound},
+};
 use ggez::{
     audio::{SoundSource, Source},
     Context, GameResult,
@@ -8,17 +11,26 @@ use strum::IntoEnumIterator;
 
 pub struct Player {
     sounds: HashMap<Sound, Source>,
+    weapon_sounds: HashMap<WeaponSound, Source>,
 }
 
 impl Player {
-    pub fn new(ctx: &mut Context) -> GameResult<Self> {
+    pub fn new(ctx: &mut Context, catalog: &Catalog) -> GameResult<Self> {
         let mut sounds = HashMap::new();
+        let mut weapon_sounds = HashMap::new();
 
         for sound in Sound::iter() {
             sounds.insert(sound, Source::new(ctx, sound.file_path())?);
         }
 
-        Ok(Self { sounds })
+        for (sound, file) in catalog.weapon_sounds() {
+            weapon_sounds.insert(sound.clone(), Source::new(ctx, file)?);
+        }
+
+        Ok(Self {
+            sounds,
+            weapon_sounds,
+        })
     }
 
     pub fn play(&mut self, sound: &Sound, ctx: &mut Context) -> GameResult {
@@ -35,4 +47,19 @@ impl Player {
 
         Ok(())
     }
+
+    pub fn play_weapon(&mut self, sound: &WeaponSound, ctx: &mut Context) -> GameResult {
+        puffin::profile_scope!("play_weapon_sound", sound.to_string());
+
+        match self.weapon_sounds.get_mut(sound) {
+            Some(source) => {
+                source.play_detached(ctx)?;
+            }
+            None => {
+                println!("ERROR :: Unknown weapon sound {}", sound)
+            }
+        };
+
+        Ok(())
+    }
 }
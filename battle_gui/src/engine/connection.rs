@@ -0,0 +1,33 @@
+use ggegui::egui::{Align2, Vec2, Window};
+use ggez::Context;
+
+use super::{gui::EGUI_SCALE, Engine};
+
+impl Engine {
+    /// Inform player when remote server don't respond anymore (network client is reconnecting)
+    pub fn update_connection_gui(&mut self, ctx: &mut Context) {
+        let connected = match &self.network_stats {
+            Some(network_stats) => network_stats.connected(),
+            None => return,
+        };
+        if connected {
+            return;
+        }
+
+        let drawable_size = ctx.gfx.drawable_size();
+        self.egui_backend
+            .input
+            .set_scale_factor(EGUI_SCALE, drawable_size);
+        let egui_ctx = self.egui_backend.ctx();
+
+        Window::new("Connection lost")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, Vec2::new(0., 0.))
+            .show(&egui_ctx, |ui| {
+                ui.label("Connection with server lost, trying to reconnect ...");
+            });
+
+        self.egui_backend.update(ctx);
+    }
+}
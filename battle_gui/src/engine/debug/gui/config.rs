@@ -2,6 +2,7 @@ use ggegui::egui::{Context as EguiContext, Grid, Slider, Ui};
 use ggez::Context;
 
 use battle_core::config::{
+    file::{ServerConfigReader, ServerConfigWriter},
     ChangeConfigMessage, FEELING_DECREASING_FREQ, INTERIORS_UPDATE_FREQ, SOLDIER_ANIMATE_FREQ,
     SOLDIER_UPDATE_FREQ, TARGET_FPS, TILE_TYPE_OPACITY_BRICK_WALL, TILE_TYPE_OPACITY_CONCRETE,
     TILE_TYPE_OPACITY_DEEP_WATER, TILE_TYPE_OPACITY_DIRT, TILE_TYPE_OPACITY_HEDGE,
@@ -32,6 +33,31 @@ impl Engine {
     ) -> Vec<EngineMessage> {
         let mut messages = vec![];
 
+        ui.horizontal(|ui| {
+            ui.label(self.server_config_file.display().to_string());
+            if ui.button("Dump").clicked() {
+                match ServerConfigWriter::to_file(&self.server_config_file, &self.server_config) {
+                    Ok(_) => println!("Config written in {}", self.server_config_file.display()),
+                    Err(error) => eprintln!("Error during config dump : {}", error),
+                }
+            }
+            if ui.button("Reload").clicked() {
+                match ServerConfigReader::from_file(&self.server_config_file) {
+                    Ok(config) => {
+                        self.server_config = config.clone();
+                        messages.push(EngineMessage::Graphics(
+                            GraphicsMessage::RecomputeDebugTerrainOpacity,
+                        ));
+                        messages.push(EngineMessage::ChangeServerConfig(
+                            ChangeConfigMessage::Replace(Box::new(config)),
+                        ));
+                    }
+                    Err(error) => eprintln!("Error during config reload : {}", error),
+                }
+            }
+        });
+        ui.separator();
+
         Grid::new("meta")
             .num_columns(3)
             .spacing([40.0, 4.0])
@@ -41,6 +41,13 @@ impl Engine {
                     ui.label(&value);
                     ui.end_row();
                 }
+                if let Some(network_stats) = &self.network_stats {
+                    for (name, value) in network_stats.debug_lines() {
+                        ui.label(&name);
+                        ui.label(&value);
+                        ui.end_row();
+                    }
+                }
             });
 
         vec![]
@@ -24,12 +24,7 @@ impl Engine {
             .striped(true)
             .show(ui, |ui| {
                 if ui.button("Make a save").clicked() {
-                    match self.save_battle_state() {
-                        Ok(save) => self.gui_state.saves_mut().push(save),
-                        Err(error) => {
-                            eprintln!("Error happen during save : {}", error)
-                        }
-                    }
+                    messages.push(EngineMessage::MakeASave)
                 }
                 if ui.button("Make a deployment file").clicked() {
                     if let Err(error) = self.save_deployment() {
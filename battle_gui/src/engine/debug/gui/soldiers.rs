@@ -50,6 +50,28 @@ impl Engine {
         ui: &mut Ui,
         soldier_index: &SoldierIndex,
     ) -> Vec<EngineMessage> {
+        let soldier = self.battle_state.soldier(*soldier_index);
+        let catalog = self.battle_state.catalog();
+        let weapon_text = if let Some(weapon) = soldier.weapon(&WeaponClass::Main) {
+            catalog
+                .weapon(weapon.type_())
+                .map(|definition| definition.name().to_string())
+                .unwrap_or_else(|| weapon.type_().to_string())
+        } else {
+            "".to_string()
+        };
+        let magazines_text = soldier
+            .magazines()
+            .iter()
+            .map(|magazine| {
+                catalog
+                    .magazine(magazine.type_())
+                    .map(|definition| definition.name().to_string())
+                    .unwrap_or_else(|| magazine.type_().to_string())
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+
         let soldier = &mut self.battle_state.soldier_mut(*soldier_index);
         let mut messages = vec![];
 
@@ -116,21 +138,10 @@ impl Engine {
                 ui.end_row();
 
                 ui.label("MainWeapon");
-                let weapon_text = if let Some(weapon) = soldier.weapon(&WeaponClass::Main) {
-                    weapon.name().to_string()
-                } else {
-                    "".to_string()
-                };
                 ui.label(weapon_text);
                 ui.end_row();
 
                 ui.label("Magazines");
-                let magazines_text = soldier
-                    .magazines()
-                    .iter()
-                    .map(|magazine| magazine.name())
-                    .collect::<Vec<&str>>()
-                    .join(", ");
                 ui.label(magazines_text);
                 ui.end_row();
             });
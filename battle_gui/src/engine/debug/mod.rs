@@ -8,7 +8,7 @@ use battle_core::{
     game::{
         explosive::ExplosiveType,
         squad::{squad_positions, Formation},
-        weapon::Weapon,
+        weapon::{Weapon, WeaponType},
         Side,
     },
     physics::event::{bullet::BulletFire, explosion::Explosion},
@@ -153,9 +153,9 @@ impl Engine {
 
         // Draw vehicle physics areas
         for vehicle in self.battle_state.vehicles() {
-            let shape = self
-                .gui_state
-                .window_shape_from_world_shape(&vehicle.chassis_shape());
+            let shape = self.gui_state.window_shape_from_world_shape(
+                &vehicle.chassis_shape(self.battle_state.vehicle_definition(vehicle)),
+            );
 
             mesh_builder.line(&shape.draw_points(), 1.0, MAGENTA)?;
         }
@@ -270,19 +270,21 @@ impl Engine {
         match self.gui_state.debug_physics() {
             DebugPhysics::None => {}
             DebugPhysics::MosinNagantM1924GunFire => {
-                let weapon = Weapon::MosinNagantM1924(true, None);
-                messages.extend(
-                    [vec![EngineMessage::BattleState(
-                        BattleStateMessage::PushBulletFire(BulletFire::new(
-                            from,
-                            to,
-                            None,
-                            weapon.ammunition(),
-                            weapon.gun_fire_sound_type(),
-                        )),
-                    )]]
-                    .concat(),
-                );
+                let weapon = Weapon::new(WeaponType("MosinNagantM1924".to_string()), true, None);
+                if let Some(ammunition) = self.battle_state.catalog().weapon_ammunition(&weapon) {
+                    messages.extend(
+                        [vec![EngineMessage::BattleState(
+                            BattleStateMessage::PushBulletFire(BulletFire::new(
+                                from,
+                                to,
+                                None,
+                                ammunition,
+                                weapon.type_().clone(),
+                            )),
+                        )]]
+                        .concat(),
+                    );
+                }
             }
             DebugPhysics::BrandtMle2731Shelling => {
                 messages.push(EngineMessage::BattleState(
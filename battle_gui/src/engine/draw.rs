@@ -35,10 +35,14 @@ impl Engine {
             self.graphics
                 .soldiers_mut()
                 .extend(&self.gui_state.zoom, soldier_sprites.clone());
-            if let Some(weapon) = soldier.weapon(&WeaponClass::Main) {
+            if let Some(definition) = soldier
+                .weapon(&WeaponClass::Main)
+                .as_ref()
+                .and_then(|weapon| self.battle_state.catalog().weapon(weapon.type_()))
+            {
                 self.graphics.weapons_mut().extend(
                     &self.gui_state.zoom,
-                    weapon.sprite_type(),
+                    definition.sprite(),
                     weapon_sprites,
                 )
             }
@@ -100,10 +104,21 @@ impl Engine {
     }
 
     pub fn generate_vehicles_sprites(&mut self) -> GameResult {
+        // Don't draw opposite side vehicles of which no crew member is visible
+        let known = self
+            .battle_state
+            .soldiers_known_by_side(self.gui_state.side());
         for (i, vehicle) in self.battle_state.vehicles().iter().enumerate() {
-            let sprites =
-                self.graphics
-                    .vehicle_sprites(VehicleIndex(i), vehicle, &self.gui_state.zoom);
+            if !self.battle_state.vehicle_is_known(&VehicleIndex(i), &known) {
+                continue;
+            }
+
+            let sprites = self.graphics.vehicle_sprites(
+                VehicleIndex(i),
+                vehicle,
+                self.battle_state.vehicle_definition(vehicle),
+                &self.gui_state.zoom,
+            );
             self.graphics
                 .vehicles_mut()
                 .extend(&self.gui_state.zoom, sprites);
@@ -364,7 +379,8 @@ impl Engine {
         match pending_order {
             PendingOrder::MoveTo(_, _, cached_points)
             | PendingOrder::MoveFastTo(_, _, cached_points)
-            | PendingOrder::SneakTo(_, _, cached_points) => {
+            | PendingOrder::SneakTo(_, _, cached_points)
+            | PendingOrder::ReverseTo(_, _, cached_points) => {
                 let pending_order_marker = self.pending_order_marker(pending_order);
                 for cached_point in cached_points {
                     let point = self.gui_state.window_point_from_world_point(*cached_point);
@@ -456,6 +472,35 @@ impl Engine {
                     Angle(0.),
                 ))
             }
+            PendingOrder::Board(_) => {
+                let pending_order_marker = self.pending_order_marker(pending_order);
+                let from_point = self
+                    .gui_state
+                    .window_point_from_world_point(squad_leader.world_point());
+                let to_point = self.gui_state.current_cursor_window_point();
+                // Line is highlighted when a vehicle is under cursor
+                let color = if self.create_board_order().is_some() {
+                    Color::WHITE
+                } else {
+                    Color::BLACK
+                };
+                mesh_builder.line(&[from_point.to_vec2(), to_point.to_vec2()], 2., color)?;
+
+                draw_params.push(
+                    self.graphics
+                        .order_marker_draw_params(&pending_order_marker, *to_point, Angle(0.))
+                        .scale(self.gui_state.zoom.to_vec2()),
+                );
+            }
+            PendingOrder::Dismount(_) => {
+                let pending_order_marker = self.pending_order_marker(pending_order);
+                let cursor_point = self.gui_state.current_cursor_window_point();
+                draw_params.push(
+                    self.graphics
+                        .order_marker_draw_params(&pending_order_marker, *cursor_point, Angle(0.))
+                        .scale(self.gui_state.zoom.to_vec2()),
+                );
+            }
         }
 
         Ok(draw_params)
@@ -466,8 +511,10 @@ impl Engine {
             PendingOrder::MoveTo(_, _, _) => OrderMarker::MoveTo,
             PendingOrder::MoveFastTo(_, _, _) => OrderMarker::MoveFastTo,
             PendingOrder::SneakTo(_, _, _) => OrderMarker::SneakTo,
+            PendingOrder::ReverseTo(_, _, _) => OrderMarker::ReverseTo,
             PendingOrder::Defend(_) => OrderMarker::Defend,
             PendingOrder::Hide(_) => OrderMarker::Hide,
+            PendingOrder::Board(_) | PendingOrder::Dismount(_) => OrderMarker::MoveTo,
             PendingOrder::EngageOrFire(_) => {
                 let cursor_point = self.gui_state.current_cursor_world_point();
                 if self
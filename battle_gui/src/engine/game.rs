@@ -7,18 +7,8 @@ use super::Engine;
 
 impl Engine {
     pub fn allowed_drop_point(&self, point: &WorldPoint) -> bool {
-        let (allowed_zone_names, opponent_zone_names) = self.zone_controls();
-
-        let in_allowed_zone =
-            self.battle_state
-                .map()
-                .point_in_spawn_zones(point, allowed_zone_names, true);
-        let in_opponent_zone =
-            self.battle_state
-                .map()
-                .point_in_spawn_zones(point, opponent_zone_names, false);
-
-        in_allowed_zone && !in_opponent_zone
+        self.battle_state
+            .allowed_placement_point(self.gui_state.side(), point)
     }
 
     pub fn zone_controls(&self) -> (&MapControl, &MapControl) {
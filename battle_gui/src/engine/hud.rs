@@ -40,6 +40,23 @@ impl Engine {
         ))]
     }
 
+    /// Claim selected squads, or release them when already owned by the player
+    pub fn switch_selected_squads_owner(&self) -> Vec<EngineMessage> {
+        let player = self.gui_state.player();
+        self.gui_state
+            .selected_squads()
+            .1
+            .iter()
+            .map(|squad_uuid| {
+                let owner = match self.battle_state.squads_owners().get(squad_uuid) {
+                    Some(owner) if owner == player.name() => None,
+                    _ => Some(player.name().clone()),
+                };
+                EngineMessage::BattleState(BattleStateMessage::SetSquadOwner(*squad_uuid, owner))
+            })
+            .collect()
+    }
+
     pub fn select_squad(&self, squad_id: &SquadUuid) -> Vec<EngineMessage> {
         vec![EngineMessage::GuiState(GuiStateMessage::SetSelectedSquads(
             Some(self.battle_state.squad(*squad_id).leader()),
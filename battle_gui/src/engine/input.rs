@@ -152,6 +152,9 @@ impl Engine {
             Some(VirtualKeyCode::F5) => {
                 messages.push(EngineMessage::TryLoadLastSave);
             }
+            Some(VirtualKeyCode::O) if self.battle_state.phase().is_placement() => {
+                messages.extend(self.switch_selected_squads_owner());
+            }
             Some(VirtualKeyCode::F12) => {
                 messages.push(EngineMessage::GuiState(
                     GuiStateMessage::SetDisplayDebugGui(!self.gui_state.display_debug_gui()),
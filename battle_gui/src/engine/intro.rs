@@ -1,4 +1,5 @@
 use battle_core::audio::Sound;
+use battle_core::game::Side;
 use ggegui::egui::{Align, Align2, Layout, Vec2, Window};
 use ggez::{Context, GameResult};
 
@@ -15,7 +16,9 @@ impl Engine {
                 .battle_state
                 .soldiers()
                 .iter()
-                .find(|s| s.side() == self.gui_state.side())
+                .find(|s| {
+                    s.side() == self.gui_state.side() || self.gui_state.side() == &Side::Observer
+                })
                 .expect("Must have at least one soldier to start battle")
                 .world_point();
             return vec![
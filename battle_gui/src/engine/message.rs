@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use battle_core::{
     audio::Sound,
     config::ChangeConfigMessage,
+    game::weapon::WeaponSound,
     order::PendingOrder,
     state::battle::message::BattleStateMessage,
     types::{Offset, SoldierIndex, SquadUuid, WindowPoint, WorldPaths, WorldPoint},
@@ -14,6 +15,7 @@ use battle_core::{
 use crate::{
     debug::{DebugPhysics, DebugTerrain},
     graphics::{message::GraphicsMessage, qualified::Zoom},
+    replay::ReplayControl,
 };
 
 use super::{event::UIEvent, input::Control};
@@ -24,12 +26,14 @@ pub enum EngineMessage {
     GuiState(GuiStateMessage),
     Graphics(GraphicsMessage),
     PlaySound(Sound),
+    PlayWeaponSound(WeaponSound),
     ChangeServerConfig(ChangeConfigMessage),
     LoadFromSave(PathBuf),
     TryLoadLastSave,
     MakeASave,
     UpdateInteriors,
     SwitchDecorDisplay,
+    Replay(ReplayControl),
     Exit,
 }
 
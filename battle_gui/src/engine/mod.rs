@@ -1,10 +1,12 @@
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use battle_core::config::{GuiConfig, ServerConfig};
+use battle_core::config::{GuiConfig, ServerConfig, DEFAULT_SERVER_CONFIG_FILE};
 use battle_core::game::control::MapControl;
-use battle_core::game::Side;
+use battle_core::game::player::Player as BattlePlayer;
 use battle_core::message::{InputMessage, OutputMessage};
+use battle_core::network::client::{Client, ClientStats};
 use battle_core::state::battle::BattleState;
 use battle_core::types::WindowPoint;
 use crossbeam_channel::{Receiver, Sender};
@@ -19,13 +21,16 @@ use ggez::{Context, GameResult};
 use crate::audio::player::Player;
 use crate::graphics::Graphics;
 use crate::saves::reader::BattleSavesListBuilder;
+use crate::server::EmbeddedServer;
 use crate::ui::hud::builder::HudBuilder;
 use crate::ui::hud::painter::HudPainter;
 use crate::ui::hud::{Hud, HUD_HEIGHT};
 
 use self::debug::gui::state::DebugGuiState;
+use self::replay::ReplayState;
 use self::state::GuiState;
 
+pub mod connection;
 pub mod debug;
 pub mod draw;
 pub mod end;
@@ -41,6 +46,7 @@ pub mod network;
 pub mod order;
 pub mod physics;
 pub mod react;
+pub mod replay;
 pub mod save;
 pub mod state;
 pub mod tick;
@@ -68,13 +74,24 @@ pub struct Engine {
     hud: Hud,
     a_control: MapControl,
     b_control: MapControl,
+    // Replay controls (and replay thread), when gui plays a replay instead of a battle
+    replay: Option<ReplayState>,
+    // Network client (and its counters), when connected to a remote server
+    client: Option<Client>,
+    // Messages sent to join a match, sent again to join the next one
+    join_messages: Vec<InputMessage>,
+    network_stats: Option<Arc<ClientStats>>,
+    // Server, when embedded by this gui
+    embedded_server: Option<EmbeddedServer>,
+    // Where debug window dumps and reloads the server config
+    server_config_file: PathBuf,
 }
 
 impl Engine {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         ctx: &mut Context,
-        side: &Side,
+        player: &BattlePlayer,
         config: GuiConfig,
         server_config: ServerConfig,
         input_sender: Sender<Vec<InputMessage>>,
@@ -86,7 +103,8 @@ impl Engine {
         a_control: MapControl,
         b_control: MapControl,
     ) -> GameResult<Engine> {
-        let mut gui_state = GuiState::new(*side, battle_state.map());
+        let mut gui_state =
+            GuiState::new(*player.side(), player.name().clone(), battle_state.map());
         gui_state.set_saves(
             BattleSavesListBuilder::new(battle_state.map().name())
                 .build()
@@ -100,7 +118,7 @@ impl Engine {
             graphics,
             input: output_receiver, // Gui input is server output
             output: input_sender,   // Gui output is server input
-            player: Player::new(ctx)?,
+            player: Player::new(ctx, battle_state.catalog())?,
             battle_state,
             gui_state,
             sync_required,
@@ -110,9 +128,43 @@ impl Engine {
             hud,
             a_control,
             b_control,
+            replay: None,
+            client: None,
+            join_messages: vec![],
+            network_stats: None,
+            embedded_server: None,
+            server_config_file: PathBuf::from(DEFAULT_SERVER_CONFIG_FILE),
         };
         Ok(engine)
     }
+
+    pub fn set_client(&mut self, client: Client, join_messages: Vec<InputMessage>) {
+        self.network_stats = Some(client.stats());
+        self.client = Some(client);
+        self.join_messages = join_messages;
+    }
+
+    /// Stop threads owned by gui (network client, embedded server or replay) and wait for them
+    pub fn shutdown(&mut self) {
+        self.stop_required.store(true, Ordering::Relaxed);
+        if let Some(client) = self.client.as_mut() {
+            client.shutdown();
+        }
+        if let Some(embedded_server) = self.embedded_server.as_mut() {
+            embedded_server.shutdown();
+        }
+        if let Some(replay) = self.replay.as_mut() {
+            replay.join();
+        }
+    }
+
+    pub fn set_embedded_server(&mut self, embedded_server: EmbeddedServer) {
+        self.embedded_server = Some(embedded_server);
+    }
+
+    pub fn set_server_config_file(&mut self, server_config_file: &Path) {
+        self.server_config_file = server_config_file.to_path_buf();
+    }
 }
 
 impl EventHandler<ggez::GameError> for Engine {
@@ -132,6 +184,8 @@ impl EventHandler<ggez::GameError> for Engine {
         self.update_debug_gui(ctx)?;
         self.update_intro_gui(ctx)?;
         self.update_end_gui(ctx)?;
+        self.update_replay_gui(ctx)?;
+        self.update_connection_gui(ctx);
         self.graphics.tick(ctx);
 
         Ok(())
@@ -260,7 +314,7 @@ impl EventHandler<ggez::GameError> for Engine {
     }
 
     fn quit_event(&mut self, _ctx: &mut Context) -> Result<bool, ggez::GameError> {
-        self.stop_required.store(true, Ordering::Relaxed);
+        self.shutdown();
         Ok(false)
     }
 }
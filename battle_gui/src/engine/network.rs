@@ -1,7 +1,7 @@
 use std::sync::atomic::Ordering;
 
 use battle_core::{
-    message::OutputMessage,
+    message::{InputMessage, OutputMessage},
     state::{
         battle::{message::SideEffect, BattleState},
         client::ClientStateMessage,
@@ -10,7 +10,7 @@ use battle_core::{
 };
 use ggez::{Context, GameResult};
 
-use super::Engine;
+use super::{message::GuiStateMessage, Engine};
 
 impl Engine {
     pub fn sync(&mut self, ctx: &mut Context) -> GameResult {
@@ -22,8 +22,11 @@ impl Engine {
             for message in &messages {
                 match message {
                     OutputMessage::LoadFromCopy(copy) => {
-                        let mut battle_state =
-                            BattleState::from_copy(copy, self.battle_state.map());
+                        let mut battle_state = BattleState::from_copy(
+                            copy,
+                            self.battle_state.map(),
+                            self.battle_state.catalog(),
+                        );
                         battle_state.resolve();
 
                         side_effects.extend(
@@ -57,14 +60,41 @@ impl Engine {
                             ClientStateMessage::PlayBattleSound(sound) => {
                                 self.player.play(sound, ctx)?
                             }
+                            ClientStateMessage::PlayWeaponSound(sound) => {
+                                self.player.play_weapon(sound, ctx)?
+                            }
                             ClientStateMessage::BattleStarted => {
                                 self.graphics.battle_started(ctx, self.battle_state.map())?;
                             }
+                            ClientStateMessage::MatchClosed => {
+                                println!("Match closed by server, join the next one");
+                                self.join_next_match(ctx)?;
+                            }
                         }
                     }
                     OutputMessage::ChangeConfig(change_config) => {
                         self.server_config.react(change_config);
                     }
+                    // TODO : manage failures in user display
+                    OutputMessage::SaveData(save) => match self.save_battle_state(save) {
+                        Ok(save) => self.gui_state.saves_mut().push(save),
+                        Err(error) => {
+                            eprintln!("Error happen during save : {}", error)
+                        }
+                    },
+                    OutputMessage::InputRejected(player, rejection) => {
+                        if player == &self.gui_state.player() {
+                            // Local state already changed with rejected message, so resync it
+                            eprintln!("Server rejected a message : {}", rejection);
+                            self.sync_required.swap(true, Ordering::Relaxed);
+                            if let Err(error) = self
+                                .output
+                                .send(vec![InputMessage::RequireCompleteSync(*player.side())])
+                            {
+                                eprintln!("Error while requiring sync : {}", error)
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -73,4 +103,24 @@ impl Engine {
 
         Ok(())
     }
+
+    /// Forget the closed match and send join messages again. Server don't know this client
+    /// anymore, so network client will handshake again before sending them.
+    fn join_next_match(&mut self, ctx: &mut Context) -> GameResult {
+        self.battle_state =
+            BattleState::empty(self.battle_state.map(), self.battle_state.catalog());
+        self.gui_state
+            .react(&GuiStateMessage::SetSelectedSquads(None, vec![]), ctx);
+        self.gui_state
+            .react(&GuiStateMessage::SetPendingOrders(vec![]), ctx);
+        self.graphics
+            .placement_started(ctx, self.battle_state.map(), &self.a_control)?;
+        self.sync_required.swap(true, Ordering::Relaxed);
+
+        if let Err(error) = self.output.send(self.join_messages.clone()) {
+            eprintln!("Error while joining next match : {}", error)
+        }
+
+        Ok(())
+    }
 }
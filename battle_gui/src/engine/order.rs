@@ -59,6 +59,21 @@ impl Engine {
         None
     }
 
+    pub fn create_reverse_to_order(
+        &self,
+        squad_id: &SquadUuid,
+        order_marker_index: &Option<OrderMarkerIndex>,
+        cached_points: &Vec<WorldPoint>,
+    ) -> Option<Order> {
+        if let Some(world_paths) =
+            self.create_world_paths_from_context(squad_id, order_marker_index, cached_points)
+        {
+            return Some(Order::ReverseTo(world_paths, None));
+        }
+
+        None
+    }
+
     pub fn create_defend_order(&self, squad_id: SquadUuid) -> Option<Order> {
         let angle = self.angle_from_cursor_and_squad(squad_id);
         Some(Order::Defend(angle))
@@ -71,6 +86,11 @@ impl Engine {
 
     pub fn create_engage_order(&self, squad_id: &SquadUuid) -> Option<Order> {
         let world_point = self.gui_state.current_cursor_world_point();
+        // Mortar squads fire on the point, without needing to see it
+        if self.squad_has_indirect_weapon(squad_id) {
+            return Some(Order::IndirectFire(world_point));
+        }
+
         if let Some(soldier) = self
             .get_opponent_soldiers_at_point(world_point)
             .iter()
@@ -96,6 +116,34 @@ impl Engine {
         None
     }
 
+    pub fn create_board_order(&self) -> Option<Order> {
+        let world_point = self.gui_state.current_cursor_world_point();
+        self.battle_state
+            .vehicles()
+            .iter()
+            .find(|vehicle| {
+                vehicle
+                    .chassis_shape(self.battle_state.vehicle_definition(vehicle))
+                    .contains(&world_point)
+            })
+            .map(|vehicle| Order::Board(*vehicle.uuid()))
+    }
+
+    pub fn create_dismount_order(&self) -> Option<Order> {
+        Some(Order::Dismount)
+    }
+
+    fn squad_has_indirect_weapon(&self, squad_id: &SquadUuid) -> bool {
+        self.battle_state
+            .squad(*squad_id)
+            .members()
+            .iter()
+            .map(|soldier_index| self.battle_state.soldier(*soldier_index))
+            .filter_map(|soldier| soldier.main_weapon().as_ref())
+            .filter_map(|weapon| self.battle_state.catalog().weapon(weapon.type_()))
+            .any(|definition| definition.indirect())
+    }
+
     pub fn create_pending_order_from_order_marker(
         &self,
         order_marker: &OrderMarker,
@@ -113,6 +161,9 @@ impl Engine {
             OrderMarker::SneakTo => {
                 PendingOrder::SneakTo(*squad_index, *order_marker_index, cached_points.into())
             }
+            OrderMarker::ReverseTo => {
+                PendingOrder::ReverseTo(*squad_index, *order_marker_index, cached_points.into())
+            }
             OrderMarker::Defend => PendingOrder::Defend(*squad_index),
             OrderMarker::Hide => PendingOrder::Hide(*squad_index),
             OrderMarker::EngageSquad => PendingOrder::EngageOrFire(*squad_index),
@@ -160,10 +211,14 @@ impl Engine {
             }
             Order::EngageSquad(_)
             | Order::SuppressFire(_)
+            | Order::IndirectFire(_)
             | Order::Idle
             | Order::MoveTo(_, _)
             | Order::MoveFastTo(_, _)
-            | Order::SneakTo(_, _) => {
+            | Order::SneakTo(_, _)
+            | Order::ReverseTo(_, _)
+            | Order::Board(_)
+            | Order::Dismount => {
                 // No direct solving in placement for these orders
                 vec![]
             }
@@ -260,10 +315,14 @@ impl Engine {
             }
             Order::EngageSquad(_)
             | Order::SuppressFire(_)
+            | Order::IndirectFire(_)
             | Order::Idle
             | Order::MoveTo(_, _)
             | Order::MoveFastTo(_, _)
-            | Order::SneakTo(_, _) => {
+            | Order::SneakTo(_, _)
+            | Order::ReverseTo(_, _)
+            | Order::Board(_)
+            | Order::Dismount => {
                 // No direct solving in placement for these orders
                 vec![]
             }
@@ -22,8 +22,10 @@ impl Engine {
         let mut messages = vec![];
 
         if bullet_fire.start() == self.gui_state.frame_i() {
-            for sound in bullet_fire.gun_fire_sound_type().fire_sounds() {
-                messages.push(EngineMessage::PlaySound(sound));
+            if let Some(definition) = self.battle_state.catalog().weapon(bullet_fire.weapon()) {
+                for sound in definition.fire_sounds(&mut rand::thread_rng()) {
+                    messages.push(EngineMessage::PlayWeaponSound(sound));
+                }
             }
         }
 
@@ -32,6 +34,11 @@ impl Engine {
 
     pub fn draw_bullet_fires(&self, mesh_builder: &mut MeshBuilder) -> GameResult {
         for bullet_fire in self.battle_state.bullet_fires() {
+            // Next rounds of a burst
+            if !bullet_fire.started(self.gui_state.frame_i()) {
+                continue;
+            }
+
             let from = self
                 .gui_state
                 .window_point_from_world_point(*bullet_fire.from());
@@ -20,8 +20,7 @@ impl Engine {
                     GraphicsMessage::PushCanonBlastAnimation(
                         *canon_blast.point(),
                         *canon_blast.angle(),
-                        canon_blast.weapon_sprite_type().clone(),
-                        canon_blast.soldier_animation_type().clone(),
+                        canon_blast.animation_type().clone(),
                     ),
                 ))
             }
@@ -47,8 +46,7 @@ impl Engine {
                 GraphicsMessage::PushCanonBlastAnimation(
                     *canon_blast.point(),
                     *canon_blast.angle(),
-                    canon_blast.weapon_sprite_type().clone(),
-                    canon_blast.soldier_animation_type().clone(),
+                    canon_blast.animation_type().clone(),
                 ),
             ));
         }
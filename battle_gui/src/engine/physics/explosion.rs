@@ -39,7 +39,7 @@ impl Engine {
         let mut messages = vec![];
 
         if explosion.start() == self.gui_state.frame_i() {
-            for sound in explosion.explosive_type().sounds() {
+            for sound in explosion.explosive_type().sounds(&mut rand::thread_rng()) {
                 messages.push(EngineMessage::PlaySound(sound));
             }
 
@@ -0,0 +1,30 @@
+use battle_core::physics::event::indirect::IndirectFire;
+
+use crate::engine::{message::EngineMessage, Engine};
+
+impl Engine {
+    pub fn tick_indirect_fires(&self) -> Vec<EngineMessage> {
+        puffin::profile_scope!("tick_indirect_fires");
+        let mut messages = vec![];
+
+        for indirect_fire in self.battle_state.indirect_fires() {
+            messages.extend(self.indirect_fire_fx(indirect_fire));
+        }
+
+        messages
+    }
+
+    pub fn indirect_fire_fx(&self, indirect_fire: &IndirectFire) -> Vec<EngineMessage> {
+        let mut messages = vec![];
+
+        if indirect_fire.start() == self.gui_state.frame_i() {
+            if let Some(definition) = self.battle_state.catalog().weapon(indirect_fire.weapon()) {
+                for sound in definition.fire_sounds(&mut rand::thread_rng()) {
+                    messages.push(EngineMessage::PlayWeaponSound(sound));
+                }
+            }
+        }
+
+        messages
+    }
+}
@@ -7,6 +7,7 @@ use super::{input::Control, Engine};
 mod bullet;
 mod canon_blast;
 mod explosion;
+mod indirect;
 
 impl Engine {
     pub fn tick_physics(&mut self) -> Vec<EngineMessage> {
@@ -16,6 +17,7 @@ impl Engine {
         messages.extend(self.tick_bullet_fires());
         messages.extend(self.tick_cannon_blasts());
         messages.extend(self.tick_explosions());
+        messages.extend(self.tick_indirect_fires());
 
         messages
     }
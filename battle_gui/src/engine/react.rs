@@ -10,6 +10,8 @@ impl Engine {
 
         for message in messages {
             match message {
+                // Replay can't be changed by gui
+                EngineMessage::BattleState(_) if self.replay.is_some() => {}
                 EngineMessage::BattleState(battle_state_message) => {
                     // Update gui battle state and modify server battle state to
                     side_effects.extend(
@@ -35,6 +37,7 @@ impl Engine {
                     self.gui_state.react(&gui_state_message, ctx)
                 }
                 EngineMessage::PlaySound(sound) => self.player.play(&sound, ctx)?,
+                EngineMessage::PlayWeaponSound(sound) => self.player.play_weapon(&sound, ctx)?,
                 EngineMessage::Graphics(graphics_message) => self.graphics.react(
                     graphics_message,
                     self.battle_state.map(),
@@ -51,19 +54,17 @@ impl Engine {
                 }
                 // TODO : manage failures in user display
                 EngineMessage::MakeASave => {
-                    //
-                    match self.save_battle_state() {
-                        Ok(save) => self.gui_state.saves_mut().push(save),
-                        Err(error) => {
-                            eprintln!("Error happen during save : {}", error)
-                        }
+                    // Save will be written when received from server
+                    if let Err(error) = self.request_save() {
+                        eprintln!("Error happen during save : {}", error)
                     }
                 }
                 // TODO : manage failures in user display
-                EngineMessage::LoadFromSave(save_path) => {
-                    if let Some(copy) = self.load_from_save(&save_path) {
-                        if let Err(error) =
-                            self.output.send(vec![InputMessage::SetBattleState(copy)])
+                EngineMessage::LoadFromSave(save_path) => match self.load_from_save(&save_path) {
+                    Ok(copy) => {
+                        if let Err(error) = self
+                            .output
+                            .send(vec![InputMessage::SetBattleState(Box::new(copy))])
                         {
                             eprintln!(
                                 "Error when try to send battle state copy to server : {}",
@@ -71,21 +72,32 @@ impl Engine {
                             )
                         }
                     }
-                }
+                    Err(error) => {
+                        eprintln!("Error when load save {} : {}", save_path.display(), error)
+                    }
+                },
                 // TODO : manage failures in user display
                 EngineMessage::TryLoadLastSave => {
                     let mut saves = self.gui_state.saves().clone();
                     saves.sort();
                     if let Some(save_path) = saves.first() {
-                        if let Some(copy) = self.load_from_save(save_path) {
-                            if let Err(error) =
-                                self.output.send(vec![InputMessage::SetBattleState(copy)])
-                            {
-                                eprintln!(
-                                    "Error when try to send battle state copy to server : {}",
-                                    error
-                                )
+                        match self.load_from_save(save_path) {
+                            Ok(copy) => {
+                                if let Err(error) = self
+                                    .output
+                                    .send(vec![InputMessage::SetBattleState(Box::new(copy))])
+                                {
+                                    eprintln!(
+                                        "Error when try to send battle state copy to server : {}",
+                                        error
+                                    )
+                                }
                             }
+                            Err(error) => eprintln!(
+                                "Error when load save {} : {}",
+                                save_path.display(),
+                                error
+                            ),
                         }
                     }
                 }
@@ -95,6 +107,11 @@ impl Engine {
                 EngineMessage::SwitchDecorDisplay => {
                     self.gui_state.draw_decor = !self.gui_state.draw_decor
                 }
+                EngineMessage::Replay(control) => {
+                    if let Some(replay) = self.replay.as_mut() {
+                        replay.react(control)
+                    }
+                }
                 EngineMessage::Exit => ctx.request_quit(),
             }
         }
@@ -0,0 +1,115 @@
+use std::thread::JoinHandle;
+
+use crossbeam_channel::Sender;
+use ggegui::egui::{Align2, Slider, Vec2, Window};
+use ggez::{Context, GameResult};
+
+use crate::replay::ReplayControl;
+
+use super::{gui::EGUI_SCALE, message::EngineMessage, Engine};
+
+pub const REPLAY_SPEEDS: [u64; 4] = [1, 2, 4, 8];
+
+/// Local state of replay controls (replay itself is played by replay thread)
+pub struct ReplayState {
+    control: Sender<ReplayControl>,
+    handle: Option<JoinHandle<()>>,
+    last_frame_i: u64,
+    paused: bool,
+    speed: u64,
+    seek_to: u64,
+}
+
+impl ReplayState {
+    pub fn new(control: Sender<ReplayControl>, handle: JoinHandle<()>, last_frame_i: u64) -> Self {
+        Self {
+            control,
+            handle: Some(handle),
+            last_frame_i,
+            paused: false,
+            speed: 1,
+            seek_to: 0,
+        }
+    }
+
+    /// Wait for replay thread end (stop must be required before)
+    pub fn join(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            if handle.join().is_err() {
+                println!("Error while waiting replay end");
+            }
+        }
+    }
+
+    pub fn react(&mut self, control: ReplayControl) {
+        match &control {
+            ReplayControl::SetPaused(paused) => self.paused = *paused,
+            ReplayControl::SetSpeed(speed) => self.speed = *speed,
+            ReplayControl::Seek(_) => {}
+        }
+
+        if let Err(error) = self.control.send(control) {
+            eprintln!("Error when transmit replay control : {}", error)
+        }
+    }
+}
+
+impl Engine {
+    pub fn set_replay(&mut self, replay: ReplayState) {
+        self.replay = Some(replay);
+    }
+
+    pub fn update_replay_gui(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let messages = self.replay_gui(ctx);
+        self.react(messages, ctx)?;
+        Ok(())
+    }
+
+    pub fn replay_gui(&mut self, ctx: &mut Context) -> Vec<EngineMessage> {
+        let frame_i = *self.battle_state.frame_i();
+        let replay = match self.replay.as_mut() {
+            Some(replay) => replay,
+            None => return vec![],
+        };
+
+        let drawable_size = ctx.gfx.drawable_size();
+        self.egui_backend
+            .input
+            .set_scale_factor(EGUI_SCALE, drawable_size);
+        let egui_ctx = self.egui_backend.ctx();
+        let mut messages = vec![];
+
+        Window::new("Replay")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_BOTTOM, Vec2::new(0., -50.))
+            .show(&egui_ctx, |ui| {
+                ui.label(format!("Frame {} / {}", frame_i, replay.last_frame_i));
+                ui.horizontal(|ui| {
+                    let label = if replay.paused { "Play" } else { "Pause" };
+                    if ui.button(label).clicked() {
+                        messages.push(EngineMessage::Replay(ReplayControl::SetPaused(
+                            !replay.paused,
+                        )));
+                    }
+
+                    let mut speed = replay.speed;
+                    for speed_ in REPLAY_SPEEDS {
+                        ui.selectable_value(&mut speed, speed_, format!("x{}", speed_));
+                    }
+                    if speed != replay.speed {
+                        messages.push(EngineMessage::Replay(ReplayControl::SetSpeed(speed)));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.add(Slider::new(&mut replay.seek_to, 0..=replay.last_frame_i));
+                    if ui.button("Go").clicked() {
+                        messages.push(EngineMessage::Replay(ReplayControl::Seek(replay.seek_to)));
+                    }
+                });
+            });
+
+        self.egui_backend.update(ctx);
+        messages
+    }
+}
@@ -1,7 +1,16 @@
-use std::{fs, path::PathBuf, time::SystemTime};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
 
 use anyhow::{Context, Result};
-use battle_core::{deployment::Deployment, sync::BattleStateCopy};
+use battle_core::{
+    deployment::Deployment,
+    message::InputMessage,
+    save::{SaveError, SaveReader},
+    sync::BattleStateCopy,
+};
 use oc_core::resources::{EnsureDir, Resources};
 
 use crate::saves::writer::BattleStateWriter;
@@ -9,9 +18,16 @@ use crate::saves::writer::BattleStateWriter;
 use super::Engine;
 
 impl Engine {
-    // TODO: Maybe we should ask server to send us the save
-    // to avoid error due to incomplete gui battle state
-    pub fn save_battle_state(&self) -> Result<PathBuf> {
+    /// Ask server for a save : gui battle state can be incomplete (ex. not visible opponents)
+    pub fn request_save(&self) -> Result<()> {
+        self.output
+            .send(vec![InputMessage::RequestSave(*self.gui_state.side())])
+            .context("Send save request")?;
+        Ok(())
+    }
+
+    /// Write save received from server
+    pub fn save_battle_state(&self, save: &[u8]) -> Result<PathBuf> {
         let now_ns = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)?
             .as_secs();
@@ -24,18 +40,12 @@ impl Engine {
             .to_path_buf()
             .ensure()?;
 
-        BattleStateWriter::new(save_to.clone()).write(&self.battle_state)?;
+        BattleStateWriter::new(save_to.clone()).write(save)?;
         Ok(save_to)
     }
 
-    pub fn load_from_save(&self, save: &PathBuf) -> Option<BattleStateCopy> {
-        if let Ok(bytes) = fs::read(save) {
-            if let Ok(copy) = bincode::deserialize::<BattleStateCopy>(&bytes) {
-                return Some(copy);
-            }
-        }
-
-        None
+    pub fn load_from_save(&self, save: &Path) -> Result<BattleStateCopy, SaveError> {
+        SaveReader::from_file(save, self.battle_state.map().name())
     }
 
     pub fn save_deployment(&self) -> Result<PathBuf> {
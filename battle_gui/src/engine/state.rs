@@ -1,11 +1,12 @@
 use std::path::PathBuf;
 
+use battle_core::game::player::Player;
 use battle_core::game::Side;
 use battle_core::map::Map;
 use battle_core::order::PendingOrder;
 use battle_core::physics::utils::DISTANCE_TO_METERS_COEFFICIENT;
 use battle_core::types::{
-    Distance, Offset, SoldierIndex, SquadUuid, WindowPoint, WorldPaths, WorldPoint,
+    Distance, Offset, PlayerName, SoldierIndex, SquadUuid, WindowPoint, WorldPaths, WorldPoint,
 };
 use battle_core::utils::{DebugPoint, WindowShape, WorldShape};
 use ggez::graphics::Rect;
@@ -24,6 +25,8 @@ pub struct GuiState {
     frame_i: u64,
     /// Side of game instance
     side: Side,
+    /// Name of game instance player (squads can be owned by a player)
+    player_name: PlayerName,
     /// Offset to apply to battle scene by window relative
     pub display_scene_offset: Offset,
     /// Scale to apply to battle scene by window relative
@@ -85,10 +88,11 @@ pub struct GuiState {
 }
 
 impl GuiState {
-    pub fn new(side: Side, map: &Map) -> Self {
+    pub fn new(side: Side, player_name: PlayerName, map: &Map) -> Self {
         Self {
             frame_i: 0,
             side,
+            player_name,
             display_scene_offset: Offset::new(0., 0.),
             zoom: Zoom::default(),
             draw_decor: true,
@@ -138,6 +142,10 @@ impl GuiState {
         &self.side
     }
 
+    pub fn player(&self) -> Player {
+        Player::new(self.side, self.player_name.clone())
+    }
+
     pub fn opponent_side(&self) -> &Side {
         match self.side {
             Side::A => &Side::B,
@@ -360,6 +368,8 @@ impl GuiState {
                 Side::A => self.side = Side::B,
                 Side::B => self.side = Side::A,
                 Side::All => unreachable!("Side All is excluded from ChangeSide"),
+                // Observer can't play any side
+                Side::Observer => {}
             },
             GuiStateMessage::SetZoom(scale, point) => {
                 //
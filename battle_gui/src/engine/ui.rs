@@ -13,7 +13,6 @@ use battle_core::{
     },
     entity::soldier::Soldier,
     game::{cover::CoverFinder, health::SoldierHealthBuilder},
-    graphics::vehicle::VehicleGraphicInfos,
     order::{Order, PendingOrder},
     physics::path::Direction,
     state::battle::message::{BattleStateMessage, SoldierMessage, VehicleMessage},
@@ -246,6 +245,10 @@ impl Engine {
                 //
                 self.create_sneak_to_order(squad_index, order_marker_index, cached_points)
             }
+            PendingOrder::ReverseTo(squad_index, order_marker_index, cached_points) => {
+                //
+                self.create_reverse_to_order(squad_index, order_marker_index, cached_points)
+            }
             PendingOrder::Defend(squad_index) => {
                 //
                 self.create_defend_order(*squad_index)
@@ -258,6 +261,14 @@ impl Engine {
                 //
                 self.create_engage_order(squad_index)
             }
+            PendingOrder::Board(_) => {
+                //
+                self.create_board_order()
+            }
+            PendingOrder::Dismount(_) => {
+                //
+                self.create_dismount_order()
+            }
         }
     }
 
@@ -352,7 +363,7 @@ impl Engine {
 
                     for (squad_id, order_marker_index, cached_points) in draw_path_findings {
                         let (path_mode, start_direction) =
-                            self.battle_state.squad_path_mode_and_direction(squad_id);
+                            self.squad_pending_path_mode_and_direction(squad_id);
 
                         if let Some(world_paths) = self.create_path_finding(
                             squad_id,
@@ -544,12 +555,11 @@ impl Engine {
     ) -> Vec<EngineMessage> {
         let vehicle = self.battle_state.vehicle(*vehicle_index);
         let grid_point = self.battle_state.map().grid_point_from_world_point(point);
-        let vehicle_graphics = VehicleGraphicInfos::from_type(vehicle.type_());
-        let chassis_size = vehicle_graphics.size();
+        let chassis_size = self.battle_state.vehicle_definition(vehicle).size();
         if !self
             .battle_state
             .map()
-            .point_allow_vehicle(&grid_point, chassis_size)
+            .point_allow_vehicle(&grid_point, &chassis_size)
         {
             vec![EngineMessage::PlaySound(Sound::Bip1)]
         } else {
@@ -5,7 +5,7 @@ use ggez::graphics::Rect;
 use battle_core::{
     behavior::BehaviorMode,
     entity::{soldier::Soldier, vehicle::OnBoardPlace},
-    game::Side,
+    game::{player::Player, Side},
     order::{marker::OrderMarker, Order},
     physics::path::{find_path, Direction, PathMode},
     types::*,
@@ -38,7 +38,13 @@ impl Engine {
         soldier_indexes
     }
 
+    /// Soldiers of player side must be controlled by player
+    fn controlled_if_player_side(&self, soldier: &Soldier, player: &Player) -> bool {
+        soldier.side() != player.side() || self.battle_state.player_controls(player, soldier)
+    }
+
     pub fn soldiers_at_point(&self, point: WorldPoint, side: Option<&Side>) -> Vec<&Soldier> {
+        let player = self.gui_state.player();
         self.battle_state
             .soldiers()
             .iter()
@@ -49,6 +55,7 @@ impl Engine {
                     true
                 }
             })
+            .filter(|soldier| self.controlled_if_player_side(soldier, &player))
             .filter(|soldier| {
                 self.graphics
                     .soldier_selection_rect(soldier)
@@ -63,10 +70,11 @@ impl Engine {
         side: &Side,
     ) -> Vec<SoldierIndex> {
         let mut filtered_soldier_indexes = vec![];
+        let player = self.gui_state.player();
 
         for soldier_index in soldier_indexes {
             let soldier = self.battle_state.soldier(soldier_index);
-            if soldier.side() == side {
+            if soldier.side() == side && self.controlled_if_player_side(soldier, &player) {
                 filtered_soldier_indexes.push(soldier_index);
             }
         }
@@ -251,8 +259,7 @@ impl Engine {
         }
 
         // Else, create a path
-        let (path_mode, start_direction) =
-            self.battle_state.squad_path_mode_and_direction(*squad_id);
+        let (path_mode, start_direction) = self.squad_pending_path_mode_and_direction(*squad_id);
         self.create_path_finding(
             *squad_id,
             order_marker_index,
@@ -262,6 +269,24 @@ impl Engine {
         )
     }
 
+    /// Squad path mode, in reverse gear if squad pending order is a reverse one
+    pub fn squad_pending_path_mode_and_direction(
+        &self,
+        squad_id: SquadUuid,
+    ) -> (PathMode, Option<Direction>) {
+        let (path_mode, start_direction) =
+            self.battle_state.squad_path_mode_and_direction(squad_id);
+        let reverse = self.gui_state.pending_order().iter().any(|pending_order| {
+            pending_order.is_reverse() && pending_order.squad_index() == &squad_id
+        });
+
+        if reverse {
+            (path_mode.reversed(), start_direction)
+        } else {
+            (path_mode, start_direction)
+        }
+    }
+
     pub fn angle_from_cursor_and_squad(&self, squad_id: SquadUuid) -> Angle {
         let squad = self.battle_state.squad(squad_id);
         let squad_leader = self.battle_state.soldier(squad.leader());
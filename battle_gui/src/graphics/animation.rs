@@ -1,7 +1,7 @@
 use battle_core::{
     entity::soldier::{Soldier, WeaponClass},
-    game::{explosive::ExplosiveType, weapon::WeaponSprite, Side},
-    graphics::{cannon_blast::CannonBlastAnimationType, soldier::SoldierAnimationType, Sprite},
+    game::{explosive::ExplosiveType, Side},
+    graphics::{cannon_blast::CannonBlastAnimationType, Sprite},
     types::{Angle, WorldPoint},
 };
 use ggez::Context;
@@ -28,10 +28,9 @@ impl Graphics {
         &mut self,
         point: WorldPoint,
         angle: Angle,
-        type_: WeaponSprite,
-        soldier_animation_type: SoldierAnimationType,
+        type_: CannonBlastAnimationType,
     ) {
-        let animation = self.canon_blast_animation(type_, soldier_animation_type);
+        let animation = self.canon_blast_animation(type_);
         self.canon_blast_sequences.push((point, angle, animation));
     }
 
@@ -142,11 +141,8 @@ impl Graphics {
 
     pub fn canon_blast_animation(
         &self,
-        type_: WeaponSprite,
-        soldier_animation_type: SoldierAnimationType,
+        animation_type: CannonBlastAnimationType,
     ) -> AnimationSequence<TweenableRect> {
-        let animation_type = CannonBlastAnimationType::from((type_, soldier_animation_type));
-
         let src_rect_start = TweenableRect::new(
             animation_type.src_x_start(),
             animation_type.src_y(&Side::A), // TODO: Give side here is not correct
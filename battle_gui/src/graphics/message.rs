@@ -1,6 +1,6 @@
 use battle_core::{
-    game::{explosive::ExplosiveType, weapon::WeaponSprite},
-    graphics::soldier::SoldierAnimationType,
+    game::explosive::ExplosiveType,
+    graphics::cannon_blast::CannonBlastAnimationType,
     types::{Angle, WorldPoint},
 };
 use serde::{Deserialize, Serialize};
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum GraphicsMessage {
     PushExplosionAnimation(WorldPoint, ExplosiveType),
-    PushCanonBlastAnimation(WorldPoint, Angle, WeaponSprite, SoldierAnimationType),
+    PushCanonBlastAnimation(WorldPoint, Angle, CannonBlastAnimationType),
     RemoveExplosionAnimation(WorldPoint),
     RemoveCanonBlastAnimation(WorldPoint),
     RecomputeDebugTerrainOpacity,
@@ -2,8 +2,11 @@ use std::{collections::HashMap, fs, path::PathBuf};
 
 use battle_core::{
     config::ServerConfig,
-    entity::{soldier::Soldier, vehicle::Vehicle},
-    game::{control::MapControl, weapon::WeaponSprite},
+    entity::{
+        soldier::Soldier,
+        vehicle::{Vehicle, VehicleDefinition},
+    },
+    game::{catalog::Catalog, control::MapControl},
     graphics::{
         cannon_blast::{
             TILE_HEIGHT as CANNON_BLAST_TILE_HEIGHT, TILE_WIDTH as CANNON_BLAST_TILE_WIDTH,
@@ -26,8 +29,6 @@ use battle_core::{
     graphics::explosion::TILE_WIDTH as EXPLOSION_TILE_HEIGHT,
     graphics::soldier::TILE_HEIGHT as SOLDIER_TILE_HEIGHT,
     graphics::soldier::TILE_WIDTH as SOLDIER_TILE_WIDTH,
-    graphics::vehicle::TILE_HEIGHT as VEHICLE_TILE_HEIGHT,
-    graphics::vehicle::TILE_WIDTH as VEHICLE_TILE_WIDTH,
 };
 use oc_core::resources::RESOURCE_PATH;
 
@@ -70,7 +71,6 @@ pub mod weapons;
 
 pub enum AssetsType {
     Soldiers,
-    Weapon(WeaponSprite),
     CannonBlasts,
     Vehicles,
     Explosions,
@@ -82,7 +82,6 @@ impl AssetsType {
     pub fn prefix(&self) -> &str {
         match self {
             AssetsType::Soldiers => "/soldiers",
-            AssetsType::Weapon(type_) => type_.prefix(),
             AssetsType::CannonBlasts => "/cannon_blasts",
             AssetsType::Vehicles => "/vehicles",
             AssetsType::Explosions => "/explosions",
@@ -141,6 +140,7 @@ impl Graphics {
         ctx: &mut Context,
         map: &Map,
         config: &ServerConfig,
+        catalog: &Catalog,
         a_control: &MapControl,
         b_control: &MapControl,
     ) -> GameResult<Graphics> {
@@ -148,7 +148,7 @@ impl Graphics {
         let soldiers_files = collect_resources_by_prefix(AssetsType::Soldiers.prefix())?;
         let soldiers = SoldiersBuilder::new(ctx).build()?;
 
-        let weapons = Weapons::new(ctx)?;
+        let weapons = Weapons::new(ctx, catalog)?;
 
         let vehicles_file = AssetsType::Vehicles.prefix().to_string() + ".png";
         let vehicles_files = collect_resources_by_prefix(AssetsType::Vehicles.prefix())?;
@@ -309,18 +309,20 @@ impl Graphics {
         &self,
         _vehicle_index: VehicleIndex,
         vehicle: &Vehicle,
+        definition: &VehicleDefinition,
         zoom: &Zoom,
     ) -> Vec<graphics::DrawParam> {
-        let vehicle_sprite_infos = VehicleGraphicInfos::from_type(vehicle.type_());
+        let vehicle_sprite_infos = VehicleGraphicInfos::from_definition(definition);
         let mut sprites = vec![];
 
+        let chassis_sprite = vehicle_sprite_infos.chassis();
         let vehicle_sprite_offset: (f32, f32) = (
-            VEHICLE_TILE_WIDTH * zoom.factor() * 0.5,
-            VEHICLE_TILE_HEIGHT * zoom.factor() * 0.5,
+            chassis_sprite.tile_width * zoom.factor() * 0.5,
+            chassis_sprite.tile_height * zoom.factor() * 0.5,
         );
         let vehicle_sprite_shadow_offset: (f32, f32) = (
-            VEHICLE_TILE_WIDTH * zoom.factor() * 0.05,
-            VEHICLE_TILE_HEIGHT * zoom.factor() * 0.05,
+            chassis_sprite.tile_width * zoom.factor() * 0.05,
+            chassis_sprite.tile_height * zoom.factor() * 0.05,
         );
 
         // Vehicle body shadow
@@ -344,29 +346,27 @@ impl Graphics {
             .dest(vehicle.world_point().to_vec2() * zoom.factor());
         sprites.push(body_draw);
 
-        // Main turret
+        // Main turret, placed on the chassis and traversing independently
         if let Some((turret_offset, turret_sprite_info)) = vehicle_sprite_infos.main_turret() {
+            let turret_dest = vehicle.world_point().to_vec2() * zoom.factor()
+                - Vec2::from_angle(vehicle.chassis_orientation().0)
+                    .rotate(turret_sprite_info.abs_offset(turret_offset).to_vec2());
             let turret_shadow_sprite = turret_sprite_info.shadow_version();
             let turret_shadow_draw = DrawParam::new()
                 .offset(
-                    Vec2::from(vehicle_sprite_offset)
-                        + turret_sprite_info.abs_offset(turret_offset).to_vec2()
-                        + Vec2::from(vehicle_sprite_shadow_offset),
+                    Vec2::from(vehicle_sprite_offset) + Vec2::from(vehicle_sprite_shadow_offset),
                 )
                 .src(Rect::from(turret_shadow_sprite.relative_rect().to_array()))
-                .dest(vehicle.world_point().to_vec2() * zoom.factor())
-                .rotation(vehicle.chassis_orientation().0);
+                .dest(turret_dest)
+                .rotation(vehicle.main_turret_orientation().0);
             sprites.push(turret_shadow_draw);
 
             let turret_sprite = turret_sprite_info;
             let turret_draw = DrawParam::new()
-                .offset(
-                    Vec2::from(vehicle_sprite_offset)
-                        + turret_sprite_info.abs_offset(turret_offset).to_vec2(),
-                )
+                .offset(Vec2::from(vehicle_sprite_offset))
                 .src(Rect::from(turret_sprite.relative_rect().to_array()))
-                .dest(vehicle.world_point().to_vec2() * zoom.factor())
-                .rotation(vehicle.chassis_orientation().0);
+                .dest(turret_dest)
+                .rotation(vehicle.main_turret_orientation().0);
             sprites.push(turret_draw);
         }
 
@@ -571,12 +571,9 @@ impl Graphics {
             GraphicsMessage::PushExplosionAnimation(point, type_) => {
                 self.push_explosion_animation(point, type_)
             }
-            GraphicsMessage::PushCanonBlastAnimation(
-                point,
-                angle,
-                type_,
-                soldier_animation_type,
-            ) => self.push_canon_blast_animation(point, angle, type_, soldier_animation_type),
+            GraphicsMessage::PushCanonBlastAnimation(point, angle, type_) => {
+                self.push_canon_blast_animation(point, angle, type_)
+            }
             GraphicsMessage::RemoveExplosionAnimation(point) => {
                 self.remove_explosion_animation(point)
             }
@@ -693,6 +690,19 @@ impl Graphics {
         Ok(())
     }
 
+    pub fn placement_started(
+        &mut self,
+        ctx: &mut Context,
+        map: &Map,
+        a_control: &MapControl,
+    ) -> GameResult<()> {
+        // While placement, decors are only displayed in spawn zones
+        self.decor = DecorsBuilder::new(ctx, map)
+            .rule(DrawOnly(a_control.clone()))
+            .build()?;
+        Ok(())
+    }
+
     pub fn draw_minimap(&self, ctx: &mut Context, canvas: &mut Canvas, hud: &Hud) -> GameResult {
         let dest = WindowPoint::new(hud.minimap().point(ctx).x, hud.minimap().point(ctx).y);
         canvas.draw(&self.minimap, DrawParam::new().dest(dest.to_vec2()));
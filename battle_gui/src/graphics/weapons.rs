@@ -1,6 +1,6 @@
-use super::AssetsType;
+use std::{collections::HashMap, path::PathBuf};
 
-use battle_core::game::weapon::WeaponSprite;
+use battle_core::game::{catalog::Catalog, weapon::WeaponSprite};
 use ggez::{
     graphics::{Canvas, DrawParam, Image, InstanceArray},
     Context, GameError, GameResult,
@@ -11,7 +11,7 @@ use crate::utils::qualified::ToQualified;
 use super::{batch::QualifiedBatch, qualified::Zoom};
 
 pub struct Weapons {
-    riffle: Weapon,
+    weapons: HashMap<WeaponSprite, Weapon>,
 }
 
 pub struct Weapon {
@@ -20,24 +20,33 @@ pub struct Weapon {
 }
 
 impl Weapons {
-    pub fn new(ctx: &mut Context) -> GameResult<Self> {
-        Ok(Self {
-            riffle: WeaponBuilder::new(ctx, WeaponSprite::Riffle).build()?,
-        })
+    /// Build a sprite batch for each catalog weapon sprite
+    pub fn new(ctx: &mut Context, catalog: &Catalog) -> GameResult<Self> {
+        let mut weapons = HashMap::new();
+        for (sprite, file) in catalog.weapon_sprites() {
+            weapons.insert(sprite.clone(), WeaponBuilder::new(ctx, file).build()?);
+        }
+
+        Ok(Self { weapons })
     }
 
-    pub fn extend(&mut self, zoom: &Zoom, type_: WeaponSprite, weapon_sprites: Vec<DrawParam>) {
-        match type_ {
-            WeaponSprite::Riffle => self.riffle.extend(zoom, weapon_sprites),
+    pub fn extend(&mut self, zoom: &Zoom, type_: &WeaponSprite, weapon_sprites: Vec<DrawParam>) {
+        match self.weapons.get_mut(type_) {
+            Some(weapon) => weapon.extend(zoom, weapon_sprites),
+            None => println!("ERROR :: Unknown weapon sprite {}", type_),
         };
     }
 
     pub fn clear(&mut self, zoom: &Zoom) {
-        self.riffle.clear(zoom);
+        for weapon in self.weapons.values_mut() {
+            weapon.clear(zoom);
+        }
     }
 
     pub fn draw(&self, canvas: &mut Canvas, zoom: &Zoom, draw_param: DrawParam) {
-        canvas.draw(self.riffle.drawable(zoom), draw_param);
+        for weapon in self.weapons.values() {
+            canvas.draw(weapon.drawable(zoom), draw_param);
+        }
     }
 }
 
@@ -75,12 +84,12 @@ impl QualifiedBatch<InstanceArray> for Weapon {
 
 pub struct WeaponBuilder<'a> {
     ctx: &'a mut Context,
-    type_: WeaponSprite,
+    file: &'a str,
 }
 
 impl<'a> WeaponBuilder<'a> {
-    pub fn new(ctx: &'a mut Context, type_: WeaponSprite) -> Self {
-        Self { ctx, type_ }
+    pub fn new(ctx: &'a mut Context, file: &'a str) -> Self {
+        Self { ctx, file }
     }
 
     pub fn build(&self) -> GameResult<Weapon> {
@@ -91,8 +100,7 @@ impl<'a> WeaponBuilder<'a> {
     }
 
     fn build_for(&self, zoom: &Zoom) -> GameResult<InstanceArray> {
-        let image_path = AssetsType::Weapon(self.type_.clone())
-            .path()
+        let image_path = PathBuf::from(self.file)
             .to_qualified(zoom)
             .map_err(|error| {
                 GameError::ResourceLoadError(format!(
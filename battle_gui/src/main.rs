@@ -2,22 +2,30 @@ use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
+use battle_core::config::file::ConfigError;
+use battle_core::config::file::ServerConfigReader;
 use battle_core::config::GuiConfig;
 use battle_core::config::ServerConfig;
 use battle_core::config::DEFAULT_SERVER_PUB_ADDRESS;
 use battle_core::config::DEFAULT_SERVER_REP_ADDRESS;
 use battle_core::deployment::DeploymentReader;
 use battle_core::deployment::DeploymentReaderError;
+use battle_core::game::catalog::CatalogError;
+use battle_core::game::catalog::CatalogReader;
 use battle_core::game::control::MapControl;
+use battle_core::game::player::Player;
 use battle_core::game::Side;
 use battle_core::map::reader::MapReader;
 use battle_core::map::reader::MapReaderError;
 use battle_core::message::InputMessage;
 use battle_core::network::client::Client;
 use battle_core::network::error::NetworkError;
+use battle_core::replay::ReplayError;
+use battle_core::replay::ReplayReader;
 use battle_core::state::battle::builder::BattleStateBuilder;
 use battle_core::state::battle::builder::BattleStateBuilderError;
-use battle_core::state::battle::message::BattleStateMessage;
+use battle_core::types::PlayerName;
+use battle_server::replay::ReplayPlayer;
 use crossbeam_channel::unbounded;
 use crossbeam_channel::SendError;
 use ggez::conf::WindowMode;
@@ -26,6 +34,7 @@ use ggez::GameError;
 use oc_core::resources::Resources;
 use oc_core::resources::ResourcesError;
 use oc_core::spawn::SpawnZoneName;
+use replay::EmbeddedReplay;
 use server::EmbeddedServer;
 use thiserror::Error;
 
@@ -34,6 +43,7 @@ mod debug;
 mod engine;
 mod graphics;
 mod physics;
+mod replay;
 mod saves;
 mod server;
 mod ui;
@@ -63,6 +73,10 @@ pub struct Opt {
     #[structopt(long = "side")]
     side: Side,
 
+    /// Player name, used when squads are owned by players
+    #[structopt(long = "player", default_value = "Player")]
+    player: String,
+
     #[structopt(long = "profile")]
     profile: bool,
 
@@ -74,14 +88,37 @@ pub struct Opt {
 
     #[structopt(long = "side-b-control")]
     b_control: Vec<SpawnZoneName>,
+
+    /// Server config file (.toml or .json) used by embedded server and debug window
+    #[structopt(long = "server-config", parse(from_os_str))]
+    server_config: Option<PathBuf>,
+
+    /// Play this replay file instead of joining a battle (map is the replay one)
+    #[structopt(long = "replay", parse(from_os_str))]
+    replay: Option<PathBuf>,
 }
 
 fn main() -> Result<(), GuiError> {
     let opt = Opt::from_args();
-    let map_name: &String = &opt.map_name;
+    let replay = opt
+        .replay
+        .as_ref()
+        .map(|path| ReplayReader::from_file(path))
+        .transpose()?;
+    let map_name: &String = &replay
+        .as_ref()
+        .map(|replay| replay.header().map_name().to_string())
+        .unwrap_or(opt.map_name.clone());
+    let player = Player::new(opt.side, PlayerName(opt.player.clone()));
     let sync_required = Arc::new(AtomicBool::new(true));
     let stop_required = Arc::new(AtomicBool::new(false));
     let resources = Resources::new()?.ensure()?;
+    // Replay is played with the server config and catalog it was recorded with
+    let server_config = match (&replay, &opt.server_config) {
+        (Some(replay), _) => replay.header().config().clone(),
+        (None, Some(path)) => ServerConfigReader::from_file(path)?,
+        (None, None) => ServerConfig::default(),
+    };
 
     // Profiling server
     // NOTE : We must keep server object to avoid its destruction
@@ -93,36 +130,71 @@ fn main() -> Result<(), GuiError> {
         None
     };
 
-    let (input_sender, output_receiver) = if opt.embedded_server {
+    // TODO : If remote server, download map before read it
+    let map = MapReader::new(map_name, &resources.lib())?.build()?;
+    let catalog = match &replay {
+        Some(replay) => replay.header().catalog().clone(),
+        None => CatalogReader::from_resources(&resources.lib())?,
+    };
+
+    let (replay_control_sender, replay_control_receiver) = unbounded();
+    let replay_last_frame_i = replay.as_ref().map(|replay| replay.last_frame_i());
+    // Kept by the engine to shut them down when gui quit
+    let mut client = None;
+    let mut embedded_server = None;
+    let mut replay_handle = None;
+    let (input_sender, output_receiver) = if let Some(replay) = replay {
+        let (input_sender, input_receiver) = unbounded();
+        let (output_sender, output_receiver) = unbounded();
+
+        let handle = EmbeddedReplay::new(
+            ReplayPlayer::new(replay, &map),
+            opt.side,
+            input_receiver,
+            output_sender,
+            replay_control_receiver,
+            stop_required.clone(),
+        )
+        .start();
+        replay_handle = Some(handle);
+
+        (input_sender, output_receiver)
+    } else if opt.embedded_server {
         let (input_sender, input_receiver) = unbounded();
         let (output_sender, output_receiver) = unbounded();
 
-        EmbeddedServer::new(
+        let mut server = EmbeddedServer::new(
             &resources.lib(),
             input_receiver,
             output_sender,
             stop_required.clone(),
         )
         .map_name(map_name)
+        .side(&opt.side)
+        .config(&server_config)
         .server_rep_address(&opt.server_rep_address)
-        .server_pub_address(&opt.server_pub_address)
-        .start()?;
+        .server_pub_address(&opt.server_pub_address);
+        server.start()?;
+        embedded_server = Some(server);
 
         (input_sender, output_receiver)
     } else {
         let (input_sender, input_receiver) = unbounded();
         let (output_sender, output_receiver) = unbounded();
 
-        Client::new(
+        let mut client_ = Client::new(
             opt.server_rep_address.clone(),
             opt.server_pub_address.clone(),
+            player.clone(),
             input_sender.clone(),
             input_receiver,
             output_sender,
             output_receiver.clone(),
             sync_required.clone(),
-        )
-        .connect()?;
+            stop_required.clone(),
+        );
+        client_.connect(&map)?;
+        client = Some(client_);
 
         (input_sender, output_receiver)
     };
@@ -131,20 +203,34 @@ fn main() -> Result<(), GuiError> {
     let a_control = MapControl::new(opt.a_control.clone());
     let b_control = MapControl::new(opt.b_control.clone());
 
-    let ready_message = if opt.side == Side::A {
-        InputMessage::BattleState(BattleStateMessage::SetAConnected(true))
-    } else {
-        InputMessage::BattleState(BattleStateMessage::SetBConnected(true))
-    };
-
     // These messages will initialize the battle state
     // Then, the RequireCompleteSync permit client to be same state than server
-    input_sender.send(vec![
-        InputMessage::LoadDeployment(deployment),
-        InputMessage::LoadControl((a_control.clone(), b_control.clone())),
-        InputMessage::RequireCompleteSync,
-        ready_message,
-    ])?;
+    // (replay state is initialized by replay itself, observer only watch server state)
+    let join_messages = if replay_last_frame_i.is_some() {
+        vec![]
+    } else if opt.side == Side::Observer {
+        vec![InputMessage::RequireCompleteSync(opt.side)]
+    } else {
+        // Remote server only accept player side deployment and control
+        let (deployment, controls) = if client.is_some() {
+            let controls = match opt.side {
+                Side::A => (a_control.clone(), MapControl::empty()),
+                Side::B => (MapControl::empty(), b_control.clone()),
+                _ => (MapControl::empty(), MapControl::empty()),
+            };
+            (deployment.side(&opt.side), controls)
+        } else {
+            (deployment, (a_control.clone(), b_control.clone()))
+        };
+        vec![
+            InputMessage::LoadDeployment(deployment),
+            InputMessage::LoadControl(controls),
+            InputMessage::RequireCompleteSync(opt.side),
+        ]
+    };
+    if !join_messages.is_empty() {
+        input_sender.send(join_messages.clone())?;
+    }
 
     let mut context_builder = ggez::ContextBuilder::new("Open Combat", "Bastien Sevajol")
         .window_mode(
@@ -157,16 +243,21 @@ fn main() -> Result<(), GuiError> {
     }
     let (mut context, event_loop) = context_builder.build()?;
 
-    // TODO : If remote server, download map before read it
-    let map = MapReader::new(map_name, &resources.lib())?.build()?;
     let config = GuiConfig::default();
-    let server_config = ServerConfig::default();
-    let graphics =
-        graphics::Graphics::new(&mut context, &map, &server_config, &a_control, &b_control)?;
-    let battle_state = BattleStateBuilder::new(map_name, resources.lib()).build()?;
-    let engine = engine::Engine::new(
+    let graphics = graphics::Graphics::new(
         &mut context,
-        &opt.side,
+        &map,
+        &server_config,
+        &catalog,
+        &a_control,
+        &b_control,
+    )?;
+    let battle_state = BattleStateBuilder::new(map_name, resources.lib())
+        .catalog(catalog)
+        .build()?;
+    let mut engine = engine::Engine::new(
+        &mut context,
+        &player,
         config,
         server_config,
         input_sender,
@@ -178,6 +269,22 @@ fn main() -> Result<(), GuiError> {
         a_control,
         b_control,
     )?;
+    if let Some(client) = client {
+        engine.set_client(client, join_messages);
+    }
+    if let Some(embedded_server) = embedded_server {
+        engine.set_embedded_server(embedded_server);
+    }
+    if let Some(server_config_file) = &opt.server_config {
+        engine.set_server_config_file(server_config_file);
+    }
+    if let (Some(handle), Some(last_frame_i)) = (replay_handle, replay_last_frame_i) {
+        engine.set_replay(engine::replay::ReplayState::new(
+            replay_control_sender,
+            handle,
+            last_frame_i,
+        ));
+    }
 
     // FIXME BS NOW : Closing GUI don't close thread correctly and keep process running
     println!("Start Gui");
@@ -202,6 +309,24 @@ enum GuiError {
     EmbeddedServer(EmbeddedServerError),
     #[error("Battle state builder error : {0}")]
     BattleStateBuilderError(BattleStateBuilderError),
+    #[error("Replay error : {0}")]
+    Replay(ReplayError),
+    #[error("Server config error : {0}")]
+    ServerConfig(ConfigError),
+    #[error("Catalog error : {0}")]
+    Catalog(CatalogError),
+}
+
+impl From<CatalogError> for GuiError {
+    fn from(error: CatalogError) -> Self {
+        Self::Catalog(error)
+    }
+}
+
+impl From<ConfigError> for GuiError {
+    fn from(error: ConfigError) -> Self {
+        Self::ServerConfig(error)
+    }
 }
 
 impl From<MapReaderError> for GuiError {
@@ -251,3 +376,9 @@ impl From<BattleStateBuilderError> for GuiError {
         Self::BattleStateBuilderError(error)
     }
 }
+
+impl From<ReplayError> for GuiError {
+    fn from(error: ReplayError) -> Self {
+        Self::Replay(error)
+    }
+}
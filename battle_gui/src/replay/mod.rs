@@ -0,0 +1,124 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use battle_core::config::TARGET_FPS;
+use battle_core::game::Side;
+use battle_core::message::{side_outputs, InputMessage, OutputMessage};
+use battle_server::replay::ReplayPlayer;
+use crossbeam_channel::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum ReplayControl {
+    SetPaused(bool),
+    // Frames computed by gui frame
+    SetSpeed(u64),
+    Seek(u64),
+}
+
+/// Play a replay in place of server : recorded inputs are given to a local runner
+/// and its outputs are sent to the gui
+pub struct EmbeddedReplay {
+    player: ReplayPlayer,
+    side: Side,
+    gui_input_receiver: Receiver<Vec<InputMessage>>,
+    gui_output_sender: Sender<Vec<OutputMessage>>,
+    control_receiver: Receiver<ReplayControl>,
+    stop_required: Arc<AtomicBool>,
+}
+
+impl EmbeddedReplay {
+    pub fn new(
+        player: ReplayPlayer,
+        side: Side,
+        gui_input_receiver: Receiver<Vec<InputMessage>>,
+        gui_output_sender: Sender<Vec<OutputMessage>>,
+        control_receiver: Receiver<ReplayControl>,
+        stop_required: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            player,
+            side,
+            gui_input_receiver,
+            gui_output_sender,
+            control_receiver,
+            stop_required,
+        }
+    }
+
+    /// Start replay thread. Returned handle permit to wait for it when gui quit.
+    pub fn start(mut self) -> JoinHandle<()> {
+        // Gui expect a deployed battle to start
+        while self.player.battle_state().soldiers().is_empty()
+            && self.player.frame_i() <= self.player.last_frame_i()
+        {
+            self.player.step();
+        }
+        self.load_current_state();
+
+        thread::Builder::new()
+            .name("replay".to_string())
+            .spawn(move || {
+                let mut paused = false;
+                let mut speed = 1;
+
+                while !self.stop_required.load(Ordering::Relaxed) {
+                    while let Ok(control) = self.control_receiver.try_recv() {
+                        match control {
+                            ReplayControl::SetPaused(value) => paused = value,
+                            ReplayControl::SetSpeed(value) => speed = value,
+                            ReplayControl::Seek(frame_i) => {
+                                let copy = self.player.seek(frame_i);
+                                self.send(vec![OutputMessage::LoadFromCopy(Box::new(copy))]);
+                            }
+                        }
+                    }
+
+                    // Gui inputs (orders, etc) can't change a replay, only sync requests are honored
+                    while let Ok(inputs) = self.gui_input_receiver.try_recv() {
+                        if inputs
+                            .iter()
+                            .any(|input| matches!(input, InputMessage::RequireCompleteSync(_)))
+                        {
+                            self.load_current_state();
+                        }
+                    }
+
+                    // Once last frame is computed, replay wait for a seek or the quit
+                    if !paused {
+                        let mut outputs: Vec<(Side, OutputMessage)> = vec![];
+                        for _ in 0..speed {
+                            if self.player.frame_i() > self.player.last_frame_i() {
+                                break;
+                            }
+                            outputs.extend(self.player.step());
+                        }
+                        if !outputs.is_empty() {
+                            self.send(side_outputs(&outputs, &self.side));
+                        }
+                    }
+
+                    thread::sleep(Duration::from_micros(1_000_000 / TARGET_FPS));
+                }
+
+                println!("Replay finished");
+            })
+            .expect("Thread must be builded correctly")
+    }
+
+    fn load_current_state(&mut self) {
+        let copy = self.player.full_sync();
+        self.send(vec![OutputMessage::LoadFromCopy(Box::new(copy))]);
+    }
+
+    fn send(&self, outputs: Vec<OutputMessage>) {
+        if let Err(error) = self.gui_output_sender.send(outputs) {
+            println!(
+                "Error during transmit replay output messages to gui : {}",
+                error
+            )
+        }
+    }
+}
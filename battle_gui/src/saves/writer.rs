@@ -1,9 +1,8 @@
-use battle_core::state::battle::BattleState;
-use std::fs::File;
-use std::io::prelude::*;
+use std::fs;
 use std::{io, path::PathBuf};
 use thiserror::Error;
 
+/// Write battle save as given by server (see `battle_core::save`)
 pub struct BattleStateWriter {
     destination: PathBuf,
 }
@@ -13,10 +12,8 @@ impl BattleStateWriter {
         Self { destination }
     }
 
-    pub fn write(&self, battle_state: &BattleState) -> Result<(), BattleStateWriterError> {
-        let mut file = File::create(&self.destination)?;
-        // TODO : use firsts bytes to write save game version and manage possible upgrades
-        file.write_all(&bincode::serialize(&battle_state.copy())?)?;
+    pub fn write(&self, save: &[u8]) -> Result<(), BattleStateWriterError> {
+        fs::write(&self.destination, save)?;
 
         Ok(())
     }
@@ -26,8 +23,6 @@ impl BattleStateWriter {
 pub enum BattleStateWriterError {
     #[error("Disk error : {0}")]
     Disk(io::Error),
-    #[error("Serialization error : {0}")]
-    Serialization(bincode::ErrorKind),
 }
 
 impl From<io::Error> for BattleStateWriterError {
@@ -35,9 +30,3 @@ impl From<io::Error> for BattleStateWriterError {
         Self::Disk(value)
     }
 }
-
-impl From<Box<bincode::ErrorKind>> for BattleStateWriterError {
-    fn from(value: Box<bincode::ErrorKind>) -> Self {
-        Self::Serialization(*value)
-    }
-}
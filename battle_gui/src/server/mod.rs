@@ -1,19 +1,29 @@
 use std::fmt::Display;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
-use std::thread;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
 
 use battle_core::config::{ServerConfig, DEFAULT_SERVER_PUB_ADDRESS, DEFAULT_SERVER_REP_ADDRESS};
-use battle_core::message::{InputMessage, OutputMessage};
+use battle_core::game::Side;
+use battle_core::map::Map;
+use battle_core::message::{side_outputs, InputMessage, OutputMessage};
 use battle_core::network::error::NetworkError;
-use battle_core::network::server::Server;
+use battle_core::network::server::{connected_message, Server};
+use battle_core::network::session::Session;
 use battle_core::state::battle::builder::{BattleStateBuilder, BattleStateBuilderError};
+use battle_core::state::battle::BattleState;
 use battle_server::runner::Runner;
 use crossbeam_channel::{unbounded, Receiver, Sender};
 
-type ServerChannel = (Sender<Vec<OutputMessage>>, Receiver<Vec<InputMessage>>);
-type RunnerChannel = (Sender<Vec<InputMessage>>, Receiver<Vec<OutputMessage>>);
+type ServerChannel = (
+    Sender<Vec<(Side, OutputMessage)>>,
+    Receiver<Vec<InputMessage>>,
+);
+type RunnerChannel = (
+    Sender<Vec<InputMessage>>,
+    Receiver<Vec<(Side, OutputMessage)>>,
+);
 
 #[derive(Debug)]
 pub enum EmbeddedServerError {
@@ -45,11 +55,15 @@ impl Display for EmbeddedServerError {
 pub struct EmbeddedServer {
     resources: PathBuf,
     map_name: Option<String>,
+    side: Side,
     server_rep_address: String,
     server_pub_address: String,
     gui_input_receiver: Receiver<Vec<InputMessage>>,
     gui_output_sender: Sender<Vec<OutputMessage>>,
     stop_required: Arc<AtomicBool>,
+    config: ServerConfig,
+    server: Option<Server>,
+    runner_handle: Option<JoinHandle<()>>,
 }
 
 impl EmbeddedServer {
@@ -62,11 +76,15 @@ impl EmbeddedServer {
         Self {
             resources: resources.to_path_buf(),
             map_name: None,
+            side: Side::A,
             server_rep_address: DEFAULT_SERVER_REP_ADDRESS.to_string(),
             server_pub_address: DEFAULT_SERVER_PUB_ADDRESS.to_string(),
             gui_input_receiver,
             gui_output_sender,
             stop_required,
+            config: ServerConfig::default(),
+            server: None,
+            runner_handle: None,
         }
     }
 
@@ -75,6 +93,17 @@ impl EmbeddedServer {
         self
     }
 
+    /// Side played by the gui embedding this server
+    pub fn side(mut self, side: &Side) -> Self {
+        self.side = *side;
+        self
+    }
+
+    pub fn config(mut self, config: &ServerConfig) -> Self {
+        self.config = config.clone();
+        self
+    }
+
     pub fn server_rep_address(mut self, address: &str) -> Self {
         self.server_rep_address = address.to_string();
         self
@@ -85,19 +114,21 @@ impl EmbeddedServer {
         self
     }
 
-    fn start_runner(&self) -> Result<RunnerChannel, EmbeddedServerError> {
-        let (runner_input_sender, runner_input_receiver) = unbounded();
-        let (runner_output_sender, runner_output_receiver) = unbounded();
-
+    fn battle_state(&self) -> Result<BattleState, EmbeddedServerError> {
         let map_name = self
             .map_name
             .as_ref()
             .ok_or(EmbeddedServerError::MissingMapName)?;
-        let config = ServerConfig::default();
-        let state = BattleStateBuilder::new(map_name, self.resources.clone()).build()?;
+        Ok(BattleStateBuilder::new(map_name, self.resources.clone()).build()?)
+    }
+
+    fn start_runner(&mut self, state: BattleState) -> Result<RunnerChannel, EmbeddedServerError> {
+        let (runner_input_sender, runner_input_receiver) = unbounded();
+        let (runner_output_sender, runner_output_receiver) = unbounded();
+        let config = self.config.clone();
 
         let stop_required_ = self.stop_required.clone();
-        thread::Builder::new()
+        let handle = thread::Builder::new()
             .name("runner".to_string())
             .spawn(|| {
                 println!("Start runner");
@@ -119,35 +150,58 @@ impl EmbeddedServer {
                 };
             })
             .unwrap();
+        self.runner_handle = Some(handle);
 
         Ok((runner_input_sender, runner_output_receiver))
     }
 
-    fn start_server(&self) -> Result<ServerChannel, EmbeddedServerError> {
+    fn start_server(&mut self, map: &Map) -> Result<ServerChannel, EmbeddedServerError> {
         let server_rep_address = self.server_rep_address.clone();
         let server_pub_address = self.server_pub_address.clone();
         let (server_input_sender, server_input_receiver) = unbounded();
         let (server_output_sender, server_output_receiver) = unbounded();
 
         println!("Start server");
-        if let Err(error) = Server::new(
+        let mut server = Server::new(
             server_rep_address,
             server_pub_address,
+            Arc::new(Mutex::new(Session::new(map.name(), map.hash()))),
             server_output_receiver,
             server_input_sender,
             self.stop_required.clone(),
-        )
-        .serve()
-        {
+        );
+        if let Err(error) = server.serve() {
             return Err(EmbeddedServerError::Network(error));
         };
+        self.server = Some(server);
 
         Ok((server_output_sender, server_input_receiver))
     }
 
-    pub fn start(&self) -> Result<(), EmbeddedServerError> {
-        let (runner_input_sender, runner_output_receiver) = self.start_runner()?;
-        let (server_output_sender, server_input_receiver) = self.start_server()?;
+    /// Stop network server and runner, and wait for them
+    pub fn shutdown(&mut self) {
+        self.stop_required.store(true, Ordering::Relaxed);
+        if let Some(server) = self.server.as_mut() {
+            server.shutdown();
+        }
+        if let Some(handle) = self.runner_handle.take() {
+            if handle.join().is_err() {
+                println!("Error while waiting runner end");
+            }
+        }
+    }
+
+    pub fn start(&mut self) -> Result<(), EmbeddedServerError> {
+        let state = self.battle_state()?;
+        let (server_output_sender, server_input_receiver) = self.start_server(state.map())?;
+        let (runner_input_sender, runner_output_receiver) = self.start_runner(state)?;
+
+        // Gui embedding the server is connected from the start
+        if let Some(connected) = connected_message(&self.side) {
+            if let Err(error) = runner_input_sender.send(vec![connected]) {
+                println!("Error during transmit connected side to runner : {}", error)
+            }
+        }
 
         let gui_input_receiver_ = self.gui_input_receiver.clone();
         let runner_input_sender_ = runner_input_sender.clone();
@@ -168,11 +222,12 @@ impl EmbeddedServer {
             .expect("Thread must be builded correctly");
 
         let gui_output_sender_ = self.gui_output_sender.clone();
+        let side = self.side;
         thread::Builder::new()
             .name("emb_runner_outputs_bridge".to_string())
             .spawn(move || {
                 while let Ok(messages) = runner_output_receiver.recv() {
-                    if let Err(error) = gui_output_sender_.send(messages.clone()) {
+                    if let Err(error) = gui_output_sender_.send(side_outputs(&messages, &side)) {
                         println!(
                             "Error during transmit runner output messages to gui : {}",
                             error
@@ -104,7 +104,7 @@ impl IntoSprite for CurrentAction {
                 ACTION_REL_WIDTH,
                 ACTION_REL_HEIGHT,
             ],
-            CurrentAction::Defending => [
+            CurrentAction::Defending | CurrentAction::Covering => [
                 ACTION_DEFENDING_START_REL_X,
                 ACTION_DEFENDING_START_REL_Y,
                 ACTION_REL_WIDTH,
@@ -122,7 +122,8 @@ impl IntoSprite for CurrentAction {
                 ACTION_REL_WIDTH,
                 ACTION_REL_HEIGHT,
             ],
-            CurrentAction::Rotating => [
+            // TODO : crew served weapons actions sprites
+            CurrentAction::Rotating | CurrentAction::SettingUp | CurrentAction::TearingDown => [
                 ACTION_EMPTY_START_REL_X,
                 ACTION_EMPTY_START_REL_Y,
                 ACTION_REL_WIDTH,
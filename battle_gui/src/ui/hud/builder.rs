@@ -1,5 +1,8 @@
 use battle_core::{
-    game::squad::{SquadStatusResume, SquadStatusesResume},
+    game::{
+        squad::{SquadStatusResume, SquadStatusesResume},
+        Side,
+    },
     state::battle::{phase::Phase, BattleState},
     types::WindowPoint,
 };
@@ -86,11 +89,12 @@ impl<'a> HudBuilder<'a> {
     fn battle_button(&self, point: &WindowPoint) -> BattleButton {
         match self.battle_state.phase() {
             Phase::Placement => {
-                let enabled = !self.battle_state.ready(self.gui_state.side());
+                let enabled = self.gui_state.side() != &Side::Observer
+                    && !self.battle_state.ready(self.gui_state.side());
                 BattleButton::begin(*point, enabled)
             }
             // FIXME BS NOW : enabled computing
-            Phase::Battle => BattleButton::end(*point, true),
+            Phase::Battle => BattleButton::end(*point, self.gui_state.side() != &Side::Observer),
             Phase::End(_, _) => BattleButton::end(*point, false),
         }
     }
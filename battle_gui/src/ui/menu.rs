@@ -4,7 +4,9 @@ use battle_core::{order::PendingOrder, types::*};
 use oc_core::graphics::{UI_SPRITE_SHEET_HEIGHT, UI_SPRITE_SHEET_WIDTH};
 
 const SCENE_ITEM_MENU_WIDTH: f32 = 71.0;
-const SCENE_ITEM_MENU_HEIGHT: f32 = 90.0;
+const SCENE_ITEM_MENU_HEIGHT: f32 = 135.0;
+const SCENE_ITEM_MENU_START_X: f32 = 300.0;
+const SCENE_ITEM_MENU_START_Y: f32 = 100.0;
 const SCENE_ITEM_MENU_ITEM_HEIGHT: f32 = 15.0;
 
 #[derive(Clone, Debug)]
@@ -15,6 +17,9 @@ pub enum MenuItem {
     Fire,
     Defend,
     Hide,
+    Board,
+    Dismount,
+    Reverse,
 }
 
 impl MenuItem {
@@ -26,14 +31,17 @@ impl MenuItem {
             MenuItem::Defend => PendingOrder::Defend(*squad_index),
             MenuItem::Hide => PendingOrder::Hide(*squad_index),
             MenuItem::Fire => PendingOrder::EngageOrFire(*squad_index),
+            MenuItem::Board => PendingOrder::Board(*squad_index),
+            MenuItem::Dismount => PendingOrder::Dismount(*squad_index),
+            MenuItem::Reverse => PendingOrder::ReverseTo(*squad_index, None, vec![]),
         }
     }
 }
 
 pub fn squad_menu_sprite_info() -> VerticalMenuSpriteInfo {
     VerticalMenuSpriteInfo {
-        relative_start_x: 0.0,
-        relative_start_y: 0.0,
+        relative_start_x: SCENE_ITEM_MENU_START_X / UI_SPRITE_SHEET_WIDTH,
+        relative_start_y: SCENE_ITEM_MENU_START_Y / UI_SPRITE_SHEET_HEIGHT,
         relative_width: SCENE_ITEM_MENU_WIDTH / UI_SPRITE_SHEET_WIDTH,
         relative_height: SCENE_ITEM_MENU_HEIGHT / UI_SPRITE_SHEET_HEIGHT,
         width: SCENE_ITEM_MENU_WIDTH,
@@ -47,6 +55,9 @@ pub fn squad_menu_sprite_info() -> VerticalMenuSpriteInfo {
             MenuItem::Fire,
             MenuItem::Defend,
             MenuItem::Hide,
+            MenuItem::Board,
+            MenuItem::Dismount,
+            MenuItem::Reverse,
         ],
     }
 }
@@ -76,11 +87,11 @@ impl VerticalMenuSpriteInfo {
 
         // Cursor inside menu
         if relative_cursor_position.x >= 0.0
-            && relative_cursor_position.x <= SCENE_ITEM_MENU_WIDTH
+            && relative_cursor_position.x <= self.width
             && relative_cursor_position.y >= 0.0
-            && relative_cursor_position.y <= SCENE_ITEM_MENU_HEIGHT
+            && relative_cursor_position.y <= self.height
         {
-            return Some((relative_cursor_position.y / SCENE_ITEM_MENU_ITEM_HEIGHT) as usize);
+            return Some((relative_cursor_position.y / self.item_height) as usize);
         }
 
         None
@@ -104,8 +115,8 @@ impl VerticalMenuSpriteInfo {
             self.item_position(menu_scene_point, scene_current_cursor_point)
         {
             let source = graphics::Rect::new(
-                self.relative_width,
-                self.relative_item_height * item_position as f32,
+                self.relative_start_x + self.relative_width,
+                self.relative_start_y + self.relative_item_height * item_position as f32,
                 self.relative_width,
                 self.relative_item_height,
             );
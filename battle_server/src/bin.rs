@@ -1,22 +1,41 @@
 use crossbeam_channel::unbounded;
 use env_logger::Env;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use battle_core::config::file::{ConfigError, ServerConfigReader, ServerConfigWriter};
 use battle_core::config::ServerConfig;
+use battle_core::map::reader::{MapReader, MapReaderError};
 use battle_core::network::error::NetworkError;
 use battle_core::network::server::Server;
+use battle_core::network::session::Session;
+use battle_core::save::{SaveError, SaveReader};
 use battle_core::state::battle::builder::{BattleStateBuilder, BattleStateBuilderError};
+use battle_core::state::battle::BattleState;
 use structopt::StructOpt;
 
+use battle_server::lobby::{Lobby, LobbyError};
 use battle_server::runner::{Runner, RunnerError};
 
 #[derive(StructOpt, Debug)]
 #[structopt(name = "basic")]
 pub struct Opt {
+    /// Battle map (not required in lobby mode)
     #[structopt()]
-    map_name: String,
+    map_name: Option<String>,
+
+    /// Run as lobby : players choose the battle map among these ones and a new match
+    /// begins after each battle end
+    #[structopt(long = "lobby-map")]
+    lobby_maps: Vec<String>,
+
+    /// Seconds let to players to watch battle end before the next match (lobby mode)
+    #[structopt(long = "match-end-delay", default_value = "30")]
+    match_end_delay: u64,
 
     #[structopt(long = "rep-address")]
     rep_address: String,
@@ -29,6 +48,30 @@ pub struct Opt {
 
     #[structopt(long = "--profile-address", default_value = "0.0.0.0:8585")]
     profile_address: String,
+
+    /// Random generator seed (randomly chosen if not given)
+    #[structopt(long = "seed")]
+    seed: Option<u64>,
+
+    /// Record received inputs into this replay file (suffixed by match number in lobby mode)
+    #[structopt(long = "record", parse(from_os_str))]
+    record: Option<PathBuf>,
+
+    /// Resume the battle from this save file
+    #[structopt(long = "load", parse(from_os_str))]
+    load: Option<PathBuf>,
+
+    /// Read server config from this file (.toml or .json, missing values are defaults)
+    #[structopt(long = "config", parse(from_os_str))]
+    config: Option<PathBuf>,
+
+    /// Override a config value (ex. --config-set visibility_firsts=4)
+    #[structopt(long = "config-set")]
+    config_overrides: Vec<String>,
+
+    /// Write the resulting config into this file (.toml or .json) then exit
+    #[structopt(long = "dump-config", parse(from_os_str))]
+    dump_config: Option<PathBuf>,
 }
 
 fn main() -> Result<(), Error> {
@@ -37,7 +80,6 @@ fn main() -> Result<(), Error> {
     let stop_required = Arc::new(AtomicBool::new(false));
     let opt = Opt::from_args();
     let resources = PathBuf::from("./resources");
-    let map_name = &opt.map_name;
 
     let _puffin_server = if opt.profile {
         let puffin_server = puffin_http::Server::new(&opt.profile_address).unwrap();
@@ -47,12 +89,79 @@ fn main() -> Result<(), Error> {
         None
     };
 
+    let mut config = match &opt.config {
+        Some(path) => ServerConfigReader::from_file(path)?,
+        None => ServerConfig::default(),
+    };
+    for expression in &opt.config_overrides {
+        config.override_(expression)?;
+    }
+    if let Some(path) = &opt.dump_config {
+        ServerConfigWriter::to_file(path, &config)?;
+        log::info!("Config written into {}", path.display());
+        return Ok(());
+    }
+
     let (server_input_sender, server_input_receiver) = unbounded();
     let (server_output_sender, server_output_receiver) = unbounded();
+
+    if !opt.lobby_maps.is_empty() {
+        if opt.load.is_some() {
+            return Err(Error::LobbyLoad);
+        }
+
+        let mut maps = HashMap::new();
+        for map_name in &opt.lobby_maps {
+            let map = MapReader::new(map_name, &resources)?.build()?;
+            maps.insert(map_name.clone(), map.hash());
+        }
+        let session = Arc::new(Mutex::new(Session::lobby(maps)));
+
+        let mut server = Server::new(
+            opt.rep_address.clone(),
+            opt.pub_address.clone(),
+            session.clone(),
+            server_output_receiver,
+            server_input_sender,
+            stop_required.clone(),
+        );
+        server.serve()?;
+        log::info!("Lobby open with maps {}", opt.lobby_maps.join(", "));
+
+        let result = Lobby::new(
+            config,
+            &resources,
+            session,
+            server_input_receiver,
+            server_output_sender,
+            stop_required,
+        )
+        .end_delay(Duration::from_secs(opt.match_end_delay))
+        .seed(opt.seed)
+        .record(opt.record.as_deref())
+        .run();
+        server.shutdown();
+        result?;
+        return Ok(());
+    }
+
+    let map_name = opt.map_name.as_ref().ok_or(Error::NoMap)?;
+    let mut battle_state = BattleStateBuilder::new(map_name, resources.clone()).build()?;
+    if let Some(load) = &opt.load {
+        let copy = SaveReader::from_file(load, map_name)?;
+        battle_state = BattleState::from_copy(&copy, battle_state.map(), battle_state.catalog());
+        battle_state.resolve();
+        log::info!("Battle loaded from {}", load.display());
+    }
+
     let stop_required_ = stop_required.clone();
-    let server = Server::new(
+    let mut server = Server::new(
         opt.rep_address.clone(),
         opt.pub_address.clone(),
+        Arc::new(Mutex::new(Session::new(
+            map_name,
+            battle_state.map().hash(),
+        ))),
         server_output_receiver,
         server_input_sender,
         stop_required_,
@@ -60,8 +169,6 @@ fn main() -> Result<(), Error> {
     server.serve()?;
 
     let stop_required_ = stop_required.clone();
-    let config = ServerConfig::default();
-    let battle_state = BattleStateBuilder::new(map_name, resources.clone()).build()?;
     let mut runner = Runner::new(
         config,
         server_input_receiver,
@@ -69,16 +176,74 @@ fn main() -> Result<(), Error> {
         stop_required_,
         battle_state,
     );
+    if let Some(seed) = opt.seed {
+        runner.set_seed(seed);
+    }
+    log::info!("Random generator seed : {}", runner.seed());
+    if let Some(record) = &opt.record {
+        runner.record(record)?;
+        log::info!("Record replay into {}", record.display());
+    }
 
-    runner.run()?;
+    let result = runner.run();
+    server.shutdown();
+    result?;
     Ok(())
 }
 
-#[derive(Debug)]
 enum Error {
+    NoMap,
+    LobbyLoad,
+    Config(ConfigError),
+    LoadMap(MapReaderError),
     LoadBattle(BattleStateBuilderError),
+    LoadSave(SaveError),
     Network(NetworkError),
     Run(RunnerError),
+    Lobby(LobbyError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NoMap => f.write_str("A map name is required (except in lobby mode)"),
+            Error::LobbyLoad => f.write_str("A save can't be loaded in lobby mode"),
+            Error::Config(error) => f.write_str(&format!("Config error : {}", error)),
+            Error::LoadMap(error) => f.write_str(&format!("Error while loading map : {}", error)),
+            Error::LoadBattle(error) => {
+                f.write_str(&format!("Error while loading battle : {}", error))
+            }
+            Error::LoadSave(error) => f.write_str(&format!("Error while loading save : {}", error)),
+            Error::Network(error) => f.write_str(&format!("Network error : {}", error)),
+            Error::Run(error) => f.write_str(&format!("Error while running : {}", error)),
+            Error::Lobby(error) => f.write_str(&format!("Lobby error : {}", error)),
+        }
+    }
+}
+
+// Returned by main, so print the readable error
+impl Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl From<ConfigError> for Error {
+    fn from(error: ConfigError) -> Self {
+        Self::Config(error)
+    }
+}
+
+impl From<LobbyError> for Error {
+    fn from(error: LobbyError) -> Self {
+        Self::Lobby(error)
+    }
+}
+
+impl From<MapReaderError> for Error {
+    fn from(error: MapReaderError) -> Self {
+        Self::LoadMap(error)
+    }
 }
 
 impl From<RunnerError> for Error {
@@ -93,6 +258,12 @@ impl From<NetworkError> for Error {
     }
 }
 
+impl From<SaveError> for Error {
+    fn from(error: SaveError) -> Self {
+        Self::LoadSave(error)
+    }
+}
+
 impl From<BattleStateBuilderError> for Error {
     fn from(error: BattleStateBuilderError) -> Self {
         Self::LoadBattle(error)
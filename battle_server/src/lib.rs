@@ -1 +1,3 @@
+pub mod lobby;
+pub mod replay;
 pub mod runner;
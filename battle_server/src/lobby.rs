@@ -0,0 +1,207 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use battle_core::{
+    config::ServerConfig,
+    game::Side,
+    message::{InputMessage, OutputMessage},
+    network::{session::Session, RECEIVE_TIMEOUT},
+    state::{
+        battle::builder::{BattleStateBuilder, BattleStateBuilderError},
+        client::ClientStateMessage,
+    },
+};
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::runner::{Runner, RunnerError};
+
+/// Chain matches without restarting the server : wait a client chooses the map (see
+/// `Session`), run the battle until its end, then reset the session for a new match.
+/// Sides are picked by clients handshakes, deployment by each side client
+/// `InputMessage::LoadDeployment` and battle begins when both sides are ready. Clients of a
+/// closed match must handshake again to join the next one.
+pub struct Lobby {
+    config: ServerConfig,
+    resources: PathBuf,
+    session: Arc<Mutex<Session>>,
+    input: Receiver<Vec<InputMessage>>,
+    output: Sender<Vec<(Side, OutputMessage)>>,
+    stop_required: Arc<AtomicBool>,
+    end_delay: Duration,
+    seed: Option<u64>,
+    record: Option<PathBuf>,
+}
+
+impl Lobby {
+    pub fn new(
+        config: ServerConfig,
+        resources: &Path,
+        session: Arc<Mutex<Session>>,
+        input: Receiver<Vec<InputMessage>>,
+        output: Sender<Vec<(Side, OutputMessage)>>,
+        stop_required: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            config,
+            resources: resources.to_path_buf(),
+            session,
+            input,
+            output,
+            stop_required,
+            end_delay: Duration::from_secs(30),
+            seed: None,
+            record: None,
+        }
+    }
+
+    /// Time let to players to watch battle end before the next match
+    pub fn end_delay(mut self, end_delay: Duration) -> Self {
+        self.end_delay = end_delay;
+        self
+    }
+
+    /// Random generator seed of each match (randomly chosen if not given)
+    pub fn seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Record each match into a replay file suffixed by match number
+    pub fn record(mut self, record: Option<&Path>) -> Self {
+        self.record = record.map(|record| record.to_path_buf());
+        self
+    }
+
+    pub fn run(&self) -> Result<(), LobbyError> {
+        let mut match_i: u32 = 0;
+
+        while let Some(map_name) = self.wait_map() {
+            match_i += 1;
+            log::info!("Match {} begins on map {}", match_i, map_name);
+
+            let battle_state =
+                BattleStateBuilder::new(&map_name, self.resources.clone()).build()?;
+            let mut runner = Runner::new(
+                self.config.clone(),
+                self.input.clone(),
+                self.output.clone(),
+                self.stop_required.clone(),
+                battle_state,
+            );
+            if let Some(seed) = self.seed {
+                runner.set_seed(seed);
+            }
+            log::info!("Random generator seed : {}", runner.seed());
+            if let Some(record) = &self.record {
+                let record = match_record_path(record, match_i);
+                runner.record(&record)?;
+                log::info!("Record replay into {}", record.display());
+            }
+
+            runner.run_match(self.end_delay)?;
+            self.close_match()?;
+            log::info!("Match {} finished", match_i);
+        }
+
+        Ok(())
+    }
+
+    /// Return chosen map name, or None if stop is required before
+    fn wait_map(&self) -> Option<String> {
+        while !self.stop_required.load(Ordering::Relaxed) {
+            if let Some(map_name) = self.session.lock().ok()?.map() {
+                return Some(map_name.to_string());
+            }
+
+            thread::sleep(Duration::from_millis(RECEIVE_TIMEOUT as u64));
+        }
+
+        None
+    }
+
+    /// Forget match players and their pending messages, then inform them
+    fn close_match(&self) -> Result<(), LobbyError> {
+        if let Ok(mut session) = self.session.lock() {
+            session.reset();
+        }
+        while self.input.try_recv().is_ok() {}
+
+        self.output.send(vec![(
+            Side::All,
+            OutputMessage::ClientState(ClientStateMessage::MatchClosed),
+        )])?;
+        Ok(())
+    }
+}
+
+fn match_record_path(record: &Path, match_i: u32) -> PathBuf {
+    let stem = record
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file_name = match record.extension() {
+        Some(extension) => format!("{}_{}.{}", stem, match_i, extension.to_string_lossy()),
+        None => format!("{}_{}", stem, match_i),
+    };
+    record.with_file_name(file_name)
+}
+
+#[derive(Debug)]
+pub enum LobbyError {
+    LoadBattle(BattleStateBuilderError),
+    Run(RunnerError),
+}
+
+impl From<BattleStateBuilderError> for LobbyError {
+    fn from(error: BattleStateBuilderError) -> Self {
+        Self::LoadBattle(error)
+    }
+}
+
+impl From<RunnerError> for LobbyError {
+    fn from(error: RunnerError) -> Self {
+        Self::Run(error)
+    }
+}
+
+impl From<crossbeam_channel::SendError<Vec<(Side, OutputMessage)>>> for LobbyError {
+    fn from(error: crossbeam_channel::SendError<Vec<(Side, OutputMessage)>>) -> Self {
+        Self::Run(RunnerError::Output(error))
+    }
+}
+
+impl Display for LobbyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LobbyError::LoadBattle(error) => {
+                f.write_str(&format!("Error while loading battle : {}", error))
+            }
+            LobbyError::Run(error) => f.write_str(&format!("Error while running : {}", error)),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("replays/battle.replay", "replays/battle_2.replay")]
+    #[case("battle", "battle_2")]
+    fn match_record_is_suffixed(#[case] record: &str, #[case] expected: &str) {
+        assert_eq!(
+            match_record_path(&PathBuf::from(record), 2),
+            PathBuf::from(expected)
+        );
+    }
+}
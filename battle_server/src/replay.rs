@@ -0,0 +1,186 @@
+use battle_core::{
+    game::Side,
+    map::Map,
+    message::OutputMessage,
+    replay::{Replay, ReplayFrame},
+    state::battle::BattleState,
+    sync::BattleStateCopy,
+};
+
+use crate::runner::{record::RunnerSnapshot, Runner};
+
+/// Frames count between two snapshots. Seeking restarts from the nearest previous snapshot.
+pub const SNAPSHOT_EACH_FRAMES: u64 = 600;
+
+/// Replay a recorded battle by feeding recorded inputs to a headless runner
+pub struct ReplayPlayer {
+    runner: Runner,
+    frames: Vec<ReplayFrame>,
+    last_frame_i: u64,
+    // Index of next frame inputs to give to runner
+    next_frame: usize,
+    snapshots: Vec<RunnerSnapshot>,
+}
+
+impl ReplayPlayer {
+    /// Battle is replayed with the server config and catalog it was recorded with
+    pub fn new(replay: Replay, map: &Map) -> Self {
+        let header = replay.header();
+        let mut battle_state = BattleState::from_copy(header.initial(), map, header.catalog());
+        battle_state.resolve();
+        let mut runner = Runner::headless(header.config().clone(), battle_state);
+        runner.set_seed(replay.header().seed());
+
+        Self {
+            last_frame_i: replay.last_frame_i(),
+            frames: replay.frames().clone(),
+            runner,
+            next_frame: 0,
+            snapshots: vec![],
+        }
+    }
+
+    pub fn frame_i(&self) -> u64 {
+        *self.runner.battle_state().frame_i()
+    }
+
+    /// Frame of last recorded inputs
+    pub fn last_frame_i(&self) -> u64 {
+        self.last_frame_i
+    }
+
+    pub fn battle_state(&self) -> &BattleState {
+        self.runner.battle_state()
+    }
+
+    /// Compute one frame with its recorded inputs and return produced outputs (with their recipient side)
+    pub fn step(&mut self) -> Vec<(Side, OutputMessage)> {
+        let frame_i = self.frame_i();
+        let snapshot_required = match self.snapshots.iter().map(|s| s.frame_i()).max() {
+            Some(last_snapshot_frame_i) => frame_i >= last_snapshot_frame_i + SNAPSHOT_EACH_FRAMES,
+            None => true,
+        };
+        if snapshot_required {
+            self.snapshots.push(self.runner.snapshot());
+        }
+
+        let mut inputs = vec![];
+        while let Some(frame) = self.frames.get(self.next_frame) {
+            if frame.frame_i() > frame_i {
+                break;
+            }
+            inputs.extend(frame.inputs().clone());
+            self.next_frame += 1;
+        }
+
+        self.runner.frame(inputs)
+    }
+
+    /// Rebuild state at given frame from the nearest previous snapshot. Return the resulting state
+    /// (clients must load it instead of applying intermediate outputs).
+    pub fn seek(&mut self, frame_i: u64) -> BattleStateCopy {
+        if frame_i < self.frame_i() {
+            if let Some(snapshot) = self
+                .snapshots
+                .iter()
+                .filter(|s| s.frame_i() <= frame_i)
+                .max_by_key(|s| s.frame_i())
+            {
+                self.runner.restore(snapshot);
+                self.next_frame = self
+                    .frames
+                    .iter()
+                    .position(|f| f.frame_i() >= snapshot.frame_i())
+                    .unwrap_or(self.frames.len());
+            }
+        }
+
+        while self.frame_i() < frame_i {
+            self.step();
+        }
+
+        self.full_sync()
+    }
+
+    /// Return current state to give to clients. Next outputs will be relative to it.
+    pub fn full_sync(&mut self) -> BattleStateCopy {
+        self.runner.reset_replication();
+        self.runner.battle_state().copy()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::path::PathBuf;
+
+    use battle_core::{
+        config::ServerConfig,
+        deployment::DeploymentReader,
+        message::InputMessage,
+        order::Order,
+        replay::ReplayReader,
+        state::battle::{
+            builder::BattleStateBuilder,
+            message::{BattleStateMessage, SoldierMessage},
+        },
+        types::{SoldierIndex, WorldPoint},
+    };
+    use rstest::*;
+
+    use super::*;
+
+    #[fixture]
+    fn replay_path() -> PathBuf {
+        std::env::temp_dir().join(format!("replay_test_{}.bin", std::process::id()))
+    }
+
+    #[rstest]
+    fn replay_reproduce_battle(replay_path: PathBuf) {
+        // Given
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let deployment =
+            DeploymentReader::from_file(&root.join("../assets/map1_deployment.json")).unwrap();
+        let battle_state = BattleStateBuilder::new("map1", root.join("../resources"))
+            .build()
+            .unwrap();
+        let map = battle_state.map().clone();
+        let catalog = battle_state.catalog().clone();
+        // Replay must be played with recorded config, not the default one
+        let config = ServerConfig {
+            visibility_update_freq: 30,
+            ..Default::default()
+        };
+        let mut runner = Runner::headless(config.clone(), battle_state);
+        runner.set_seed(42);
+        runner.record(&replay_path).unwrap();
+        runner.step(vec![
+            InputMessage::LoadDeployment(deployment),
+            InputMessage::BattleState(BattleStateMessage::SetAReady(true)),
+            InputMessage::BattleState(BattleStateMessage::SetBReady(true)),
+        ]);
+        runner.advance(10);
+        runner.step(vec![InputMessage::BattleState(
+            BattleStateMessage::Soldier(
+                SoldierIndex(0),
+                SoldierMessage::SetOrder(Order::SuppressFire(WorldPoint::new(550., 270.))),
+            ),
+        )]);
+        runner.advance(700);
+
+        // When
+        let replay = ReplayReader::from_file(&replay_path).unwrap();
+        let mut player = ReplayPlayer::new(replay.clone(), &map);
+        let at_end = player.seek(*runner.battle_state().frame_i());
+        let at_middle = player.seek(650);
+        let at_end_again = player.seek(*runner.battle_state().frame_i());
+        std::fs::remove_file(&replay_path).unwrap();
+
+        // Then
+        assert_eq!(replay.header().config(), &config);
+        assert_eq!(replay.header().catalog(), &catalog);
+        assert_eq!(player.last_frame_i(), 11);
+        assert_eq!(at_end, runner.battle_state().copy());
+        assert_eq!(at_middle.frame_i(), 650);
+        assert_eq!(at_end_again, at_end);
+    }
+}
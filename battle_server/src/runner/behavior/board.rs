@@ -0,0 +1,107 @@
+use battle_core::{
+    behavior::{Behavior, Body},
+    entity::soldier::Soldier,
+    order::Order,
+    types::{SquadUuid, VehicleIndex},
+};
+
+use crate::runner::Runner;
+
+impl Runner {
+    pub fn board_behavior(&self, soldier: &Soldier, vehicle_index: &VehicleIndex) -> Behavior {
+        if !self.battle_state.vehicle(*vehicle_index).can_fire() {
+            return Behavior::Idle(Body::from_soldier(soldier, &self.battle_state));
+        }
+
+        Behavior::Board(*vehicle_index)
+    }
+
+    pub fn dismount_behavior(&self, _soldier: &Soldier) -> Behavior {
+        Behavior::Dismount
+    }
+
+    pub fn propagate_board(
+        &self,
+        squad_uuid: SquadUuid,
+        vehicle_index: &VehicleIndex,
+    ) -> Vec<(&Soldier, Order)> {
+        self.battle_state
+            .squad(squad_uuid)
+            .subordinates()
+            .iter()
+            .map(|i| (self.battle_state.soldier(**i), Order::Board(*vehicle_index)))
+            .collect()
+    }
+
+    pub fn propagate_dismount(&self, squad_uuid: SquadUuid) -> Vec<(&Soldier, Order)> {
+        self.battle_state
+            .squad(squad_uuid)
+            .subordinates()
+            .iter()
+            .map(|i| (self.battle_state.soldier(**i), Order::Dismount))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        deployment::Deployment,
+        entity::vehicle::OnBoardPlace,
+        order::Order,
+        state::battle::message::SoldierMessage,
+        types::{SoldierIndex, VehicleIndex},
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        test::{runner, soldier, tank_deployment},
+        Runner,
+    };
+
+    #[rstest]
+    fn squad_board_then_dismount_vehicle(mut runner: Runner, tank_deployment: Deployment) {
+        // Given
+        let members = [2, 3, 4].map(SoldierIndex);
+        runner.start_battle(tank_deployment);
+
+        // When
+        runner.step(vec![soldier(
+            0,
+            SoldierMessage::SetOrder(Order::Board(VehicleIndex(0))),
+        )]);
+        runner.advance(300);
+
+        // Then
+        for member in members {
+            assert!(matches!(
+                runner.battle_state().soldier_board(member),
+                Some((VehicleIndex(0), OnBoardPlace::Passenger(_)))
+            ));
+            assert_eq!(runner.battle_state().soldier(member).order(), &Order::Idle);
+        }
+        assert_eq!(
+            runner.battle_state().vehicle_board()[&VehicleIndex(0)].len(),
+            5
+        );
+
+        // When
+        runner.step(vec![soldier(0, SoldierMessage::SetOrder(Order::Dismount))]);
+        runner.advance(60);
+
+        // Then
+        let vehicle = runner.battle_state().vehicle(VehicleIndex(0));
+        let chassis = vehicle.chassis_shape(runner.battle_state().vehicle_definition(vehicle));
+        assert!(runner.battle_state().soldier_on_board().is_empty());
+        assert!(runner
+            .battle_state()
+            .vehicle_board()
+            .get(&VehicleIndex(0))
+            .is_none_or(|board| board.is_empty()));
+        for soldier_index in [0, 1, 2, 3, 4].map(SoldierIndex) {
+            let soldier = runner.battle_state().soldier(soldier_index);
+            assert!(!chassis.contains(&soldier.world_point()));
+            assert_eq!(soldier.order(), &Order::Idle);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use battle_core::{
+    behavior::{gesture::Gesture, Behavior},
+    entity::soldier::Soldier,
+};
+
+use crate::runner::Runner;
+
+impl Runner {
+    /// Gunner must tear down its set up weapon (until the end of the gesture) before moving
+    pub fn crew_behavior(&self, soldier: &Soldier, behavior: Behavior) -> Behavior {
+        if behavior.world_paths().is_none() {
+            return behavior;
+        }
+
+        let tearing_down = match soldier.gesture() {
+            Gesture::TearingDown(end) => end > self.battle_state.frame_i(),
+            _ => false,
+        };
+        if soldier.weapon_setup().is_some() || tearing_down {
+            return Behavior::TearDownWeapon;
+        }
+
+        behavior
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        behavior::{gesture::Gesture, Behavior},
+        deployment::Deployment,
+        message::InputMessage,
+        order::Order,
+        state::battle::message::{BattleStateMessage, SoldierMessage},
+        types::{Angle, SoldierIndex, WorldPath, WorldPaths, WorldPoint},
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        test::{crew_deployment, runner},
+        Runner,
+    };
+
+    #[rstest]
+    fn crew_served_weapon_is_teared_down_before_moving(
+        mut runner: Runner,
+        crew_deployment: Deployment,
+    ) {
+        // Given
+        let gunner = SoldierIndex(0);
+        let destination = WorldPoint::new(60., 100.);
+        let order = Order::MoveTo(
+            WorldPaths::new(vec![WorldPath::new(vec![destination])]),
+            None,
+        );
+        runner.start_battle(crew_deployment);
+        runner.step(vec![
+            InputMessage::BattleState(BattleStateMessage::Soldier(
+                gunner,
+                SoldierMessage::SetWeaponSetup(Some(Angle(0.))),
+            )),
+            InputMessage::BattleState(BattleStateMessage::Soldier(
+                gunner,
+                SoldierMessage::SetOrder(order),
+            )),
+        ]);
+        let start = runner.battle_state().soldier(gunner).world_point();
+
+        // When
+        runner.advance(600);
+
+        // Then
+        let soldier = runner.battle_state().soldier(gunner);
+        assert_eq!(soldier.world_point(), start);
+        assert_eq!(soldier.behavior(), &Behavior::TearDownWeapon);
+        assert!(matches!(soldier.gesture(), Gesture::TearingDown(_)));
+        assert_eq!(soldier.weapon_setup(), &None);
+
+        // When
+        runner.advance(600);
+
+        // Then
+        assert_ne!(runner.battle_state().soldier(gunner).world_point(), start);
+    }
+}
@@ -0,0 +1,89 @@
+use battle_core::{
+    behavior::Behavior,
+    entity::soldier::Soldier,
+    order::Order,
+    types::{SquadUuid, WorldPoint},
+};
+
+use crate::runner::Runner;
+
+impl Runner {
+    /// Rounds already fired on the same point are kept to range-in
+    pub fn indirect_fire_behavior(&self, soldier: &Soldier, point: &WorldPoint) -> Behavior {
+        let ranging_rounds = match soldier.behavior() {
+            Behavior::IndirectFire(current, ranging_rounds) if current == point => *ranging_rounds,
+            _ => 0,
+        };
+
+        Behavior::IndirectFire(*point, ranging_rounds)
+    }
+
+    pub fn propagate_indirect_fire(
+        &self,
+        squad_uuid: SquadUuid,
+        point: &WorldPoint,
+    ) -> Vec<(&Soldier, Order)> {
+        self.battle_state
+            .squad(squad_uuid)
+            .subordinates()
+            .iter()
+            .map(|i| self.battle_state.soldier(**i))
+            .map(|member| (member, Order::IndirectFire(*point)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        behavior::Behavior,
+        deployment::Deployment,
+        entity::soldier::WeaponClass,
+        game::weapon::{Weapon, WeaponType},
+        message::InputMessage,
+        state::battle::message::{BattleStateMessage, SoldierMessage},
+        types::{SoldierIndex, WorldPoint},
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        message::RunnerMessage,
+        test::{deployment, runner},
+        Runner,
+    };
+
+    #[rstest]
+    fn indirect_fire_range_in_on_same_point(mut runner: Runner, deployment: Deployment) {
+        // Given
+        let point = WorldPoint::new(500., 500.);
+        runner.step(vec![
+            InputMessage::LoadDeployment(deployment),
+            InputMessage::BattleState(BattleStateMessage::Soldier(
+                SoldierIndex(0),
+                SoldierMessage::SetBehavior(Behavior::IndirectFire(point, 2)),
+            )),
+        ]);
+        let weapon = Weapon::new(WeaponType("BrandtMle2731".to_string()), true, None);
+        let soldier = runner.battle_state().soldier(SoldierIndex(0));
+
+        // When
+        let messages =
+            runner.firing_gesture_messages(soldier, &WeaponClass::Main, &weapon, &point, &None);
+        let same_point = runner.indirect_fire_behavior(soldier, &point);
+        let other_point = runner.indirect_fire_behavior(soldier, &WorldPoint::new(10., 10.));
+
+        // Then
+        assert!(messages.iter().any(|message| matches!(
+            message,
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                SoldierIndex(0),
+                SoldierMessage::SetBehavior(Behavior::IndirectFire(_, 3))
+            ))
+        )));
+        assert_eq!(same_point, Behavior::IndirectFire(point, 2));
+        assert_eq!(
+            other_point,
+            Behavior::IndirectFire(WorldPoint::new(10., 10.), 0)
+        );
+    }
+}
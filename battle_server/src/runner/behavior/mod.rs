@@ -13,10 +13,13 @@ use battle_core::{
 use super::{fight::choose::ChooseMethod, message::RunnerMessage, Runner};
 
 mod blast;
+mod board;
 mod bullet;
+mod crew;
 mod death;
 mod defend;
 mod engage;
+mod indirect;
 mod moves;
 mod suppress;
 
@@ -35,7 +38,12 @@ impl Runner {
             Order::Hide(angle) => self.hide_behavior(soldier, angle),
             Order::EngageSquad(squad_index) => self.engage_behavior(soldier, squad_index),
             Order::SuppressFire(point) => self.suppress_fire_behavior(soldier, point),
+            Order::IndirectFire(point) => self.indirect_fire_behavior(soldier, point),
+            Order::Board(vehicle_index) => self.board_behavior(soldier, vehicle_index),
+            Order::Dismount => self.dismount_behavior(soldier),
+            Order::ReverseTo(paths, _) => self.reverse_behavior(soldier, paths),
         };
+        let behavior = self.crew_behavior(soldier, behavior);
 
         // In case of squad leader and regularly propagation
         if self.soldier_is_squad_leader(soldier.uuid())
@@ -97,9 +105,12 @@ impl Runner {
                 debug_points.extend(debug_points_);
                 orders
             }
-            Behavior::DriveTo(_) => todo!(),
+            Behavior::DriveTo(_) | Behavior::ReverseTo(_) => todo!(),
             Behavior::RotateTo(_) => todo!(),
-            Behavior::Idle(_) | Behavior::Dead | Behavior::Unconscious => {
+            Behavior::Idle(_)
+            | Behavior::Dead
+            | Behavior::Unconscious
+            | Behavior::TearDownWeapon => {
                 vec![]
             }
             Behavior::SuppressFire(point) => {
@@ -108,6 +119,13 @@ impl Runner {
             Behavior::EngageSoldier(soldier_index) => {
                 self.propagate_engage_soldier(&leader.squad_uuid(), soldier_index)
             }
+            Behavior::IndirectFire(point, _) => {
+                self.propagate_indirect_fire(leader.squad_uuid(), point)
+            }
+            Behavior::Board(vehicle_index) => {
+                self.propagate_board(leader.squad_uuid(), vehicle_index)
+            }
+            Behavior::Dismount => self.propagate_dismount(leader.squad_uuid()),
         };
 
         for (subordinate, order) in orders {
@@ -164,7 +182,20 @@ impl Runner {
                     Behavior::MoveTo(paths.clone())
                 }
             }
-            BehaviorMode::Vehicle => Behavior::DriveTo(paths.clone()),
+            BehaviorMode::Vehicle => {
+                if self.soldier_vehicle_should_reverse(soldier, paths) {
+                    Behavior::ReverseTo(paths.clone())
+                } else {
+                    Behavior::DriveTo(paths.clone())
+                }
+            }
+        }
+    }
+
+    pub fn reverse_behavior(&self, soldier: &Soldier, paths: &WorldPaths) -> Behavior {
+        match self.battle_state.soldier_behavior_mode(soldier) {
+            BehaviorMode::Ground => self.move_behavior(soldier, paths),
+            BehaviorMode::Vehicle => Behavior::ReverseTo(paths.clone()),
         }
     }
 
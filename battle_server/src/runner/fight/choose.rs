@@ -1,4 +1,4 @@
-use rand::seq::SliceRandom;
+use rand::{seq::SliceRandom, Rng};
 
 use battle_core::{
     entity::soldier::Soldier,
@@ -15,20 +15,22 @@ pub enum ChooseMethod {
     RandomFromNearest,
 }
 impl ChooseMethod {
-    fn choose(
+    fn choose<R: Rng>(
         &self,
         battle_state: &BattleState,
         visibles: Vec<&Visibility>,
+        rng: &mut R,
     ) -> Option<SoldierIndex> {
         match self {
-            Self::RandomFromNearest => self.choose_random_from_nearest(battle_state, visibles),
+            Self::RandomFromNearest => self.choose_random_from_nearest(battle_state, visibles, rng),
         }
     }
 
-    fn choose_random_from_nearest(
+    fn choose_random_from_nearest<R: Rng>(
         &self,
         battle_state: &BattleState,
         visibles: Vec<&Visibility>,
+        rng: &mut R,
     ) -> Option<SoldierIndex> {
         if let Some(visibility) = visibles.first() {
             let soldier = battle_state.soldier(
@@ -51,9 +53,7 @@ impl ChooseMethod {
                 })
                 .collect();
 
-            return near_soldiers
-                .choose(&mut rand::thread_rng())
-                .map(|s| s.uuid());
+            return near_soldiers.choose(rng).map(|s| s.uuid());
         }
 
         None
@@ -102,7 +102,7 @@ impl Runner {
         }
 
         method
-            .choose(&self.battle_state, visibles)
+            .choose(&self.battle_state, visibles, &mut *self.rng())
             .map(|i| self.battle_state.soldier(i))
     }
 }
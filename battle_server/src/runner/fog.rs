@@ -0,0 +1,339 @@
+use std::collections::{HashMap, HashSet};
+
+use battle_core::{
+    game::Side,
+    message::OutputMessage,
+    physics::visibility::Visibility,
+    state::battle::message::{BattleStateMessage, SoldierMessage, VehicleMessage},
+    types::{SoldierIndex, VehicleIndex, WorldPoint},
+};
+
+use super::Runner;
+
+pub type SidesVisibleSoldiers = HashMap<Side, HashSet<SoldierIndex>>;
+pub type SidesKnownSoldiers = HashMap<Side, HashSet<SoldierIndex>>;
+
+impl Runner {
+    /// Opponent soldiers currently visible by each side
+    pub fn sides_visible_soldiers(&self) -> SidesVisibleSoldiers {
+        let mut visibles: SidesVisibleSoldiers = HashMap::new();
+
+        for visibility in self.battle_state.visibilities().visibles_soldiers() {
+            let from_soldier = self.battle_state.soldier(
+                visibility
+                    .from_soldier
+                    .expect("visibles_soldiers implies from_soldier"),
+            );
+            visibles.entry(*from_soldier.side()).or_default().insert(
+                visibility
+                    .to_soldier
+                    .expect("visibles_soldiers implies to_soldier"),
+            );
+        }
+
+        visibles
+    }
+
+    /// Soldiers known by each player side (see `BattleState::soldiers_known_by_side`)
+    pub fn sides_known_soldiers(&self) -> SidesKnownSoldiers {
+        [Side::A, Side::B]
+            .into_iter()
+            .map(|side| (side, self.battle_state.soldiers_known_by_side(&side)))
+            .collect()
+    }
+
+    /// Route vehicle message to observers and to sides which know one of its crew members
+    pub fn vehicle_outputs(
+        &self,
+        vehicle_index: &VehicleIndex,
+        message: &BattleStateMessage,
+        known: &SidesKnownSoldiers,
+    ) -> Vec<(Side, OutputMessage)> {
+        self.known_outputs(message, known, |known| {
+            self.battle_state.vehicle_is_known(vehicle_index, known)
+        })
+    }
+
+    /// Route shot (bullet, cannon blast, etc) message to observers and to sides which know the
+    /// soldier standing at its origin
+    pub fn shot_outputs(
+        &self,
+        origin: &WorldPoint,
+        message: &BattleStateMessage,
+        known: &SidesKnownSoldiers,
+    ) -> Vec<(Side, OutputMessage)> {
+        self.known_outputs(message, known, |known| {
+            self.battle_state.shot_origin_is_known(origin, known)
+        })
+    }
+
+    fn known_outputs(
+        &self,
+        message: &BattleStateMessage,
+        known: &SidesKnownSoldiers,
+        is_known: impl Fn(&HashSet<SoldierIndex>) -> bool,
+    ) -> Vec<(Side, OutputMessage)> {
+        let message = OutputMessage::BattleState(message.clone());
+        let mut outputs = vec![(Side::Observer, message.clone())];
+
+        for side in [Side::A, Side::B] {
+            if known.get(&side).map(&is_known).unwrap_or(false) {
+                outputs.push((side, message.clone()));
+            }
+        }
+
+        outputs
+    }
+
+    /// Route soldier message to its side, to observers and, if the soldier is visible by it, to
+    /// the opponent side. Orders are never given to the opponent and behaviors are given to it
+    /// without their route (see `Behavior::without_route`).
+    pub fn soldier_outputs(
+        &self,
+        soldier_index: &SoldierIndex,
+        soldier_message: &SoldierMessage,
+    ) -> Vec<(Side, OutputMessage)> {
+        let soldier = self.battle_state.soldier(*soldier_index);
+        let side = *soldier.side();
+        let message = OutputMessage::BattleState(BattleStateMessage::Soldier(
+            *soldier_index,
+            soldier_message.clone(),
+        ));
+        let mut outputs = vec![(side, message.clone()), (Side::Observer, message)];
+
+        let opponent = side.opposite();
+        if !self
+            .battle_state
+            .soldier_is_visible_by_side(soldier, &opponent)
+        {
+            return outputs;
+        }
+
+        let opponent_message = match soldier_message {
+            SoldierMessage::SetOrder(_) => None,
+            // Opponent only know the next behavior step, so give it the resulting behavior
+            SoldierMessage::ReachBehaviorStep => Some(SoldierMessage::SetBehavior(
+                soldier.behavior().without_route(),
+            )),
+            SoldierMessage::SetBehavior(behavior) => {
+                Some(SoldierMessage::SetBehavior(behavior.without_route()))
+            }
+            _ => Some(soldier_message.clone()),
+        };
+        if let Some(opponent_message) = opponent_message {
+            outputs.push((
+                opponent,
+                OutputMessage::BattleState(BattleStateMessage::Soldier(
+                    *soldier_index,
+                    opponent_message,
+                )),
+            ));
+        }
+
+        outputs
+    }
+
+    /// Each side only receive visibilities computed from its own soldiers (observers receive all)
+    pub fn visibilities_outputs(
+        &self,
+        visibilities: &HashMap<(SoldierIndex, SoldierIndex), Visibility>,
+    ) -> Vec<(Side, OutputMessage)> {
+        let mut outputs: Vec<(Side, OutputMessage)> = [Side::A, Side::B]
+            .iter()
+            .map(|side| {
+                let side_visibilities = visibilities
+                    .iter()
+                    .filter(|((from_soldier, _), _)| {
+                        self.battle_state.soldier(*from_soldier).side() == side
+                    })
+                    .map(|(key, visibility)| (*key, visibility.clone()))
+                    .collect();
+                (
+                    *side,
+                    OutputMessage::BattleState(BattleStateMessage::SetVisibilities(
+                        side_visibilities,
+                    )),
+                )
+            })
+            .collect();
+        outputs.push((
+            Side::Observer,
+            OutputMessage::BattleState(BattleStateMessage::SetVisibilities(visibilities.clone())),
+        ));
+
+        outputs
+    }
+
+    /// Give to sides the current state of soldiers (and of their vehicles) they see since last
+    /// visibilities update (updates of these were not sent to them while they were hidden)
+    pub fn revealed_outputs(&self, before: &SidesVisibleSoldiers) -> Vec<(Side, OutputMessage)> {
+        let mut outputs = vec![];
+        let now = self.sides_visible_soldiers();
+
+        for side in [Side::A, Side::B] {
+            let previously_visibles = before.get(&side).cloned().unwrap_or_default();
+            let mut revealed_vehicles = HashSet::new();
+            for soldier_index in now.get(&side).cloned().unwrap_or_default() {
+                if previously_visibles.contains(&soldier_index) {
+                    continue;
+                }
+                if let Some(vehicle_index) = self.battle_state.soldier_vehicle(soldier_index) {
+                    revealed_vehicles.insert(vehicle_index);
+                }
+
+                let soldier = self.battle_state.soldier(soldier_index);
+                outputs.extend(
+                    [
+                        SoldierMessage::SetWorldPosition(soldier.world_point()),
+                        SoldierMessage::SetOrientation(soldier.get_looking_direction()),
+                        SoldierMessage::SetAlive(soldier.alive()),
+                        SoldierMessage::SetUnconscious(soldier.unconscious()),
+                        SoldierMessage::SetBehavior(soldier.behavior().without_route()),
+                        SoldierMessage::SetGesture(soldier.gesture().clone()),
+                    ]
+                    .into_iter()
+                    .map(|message| {
+                        (
+                            side,
+                            OutputMessage::BattleState(BattleStateMessage::Soldier(
+                                soldier_index,
+                                message,
+                            )),
+                        )
+                    }),
+                );
+            }
+
+            for vehicle_index in revealed_vehicles {
+                let vehicle = self.battle_state.vehicle(vehicle_index);
+                outputs.extend(
+                    [
+                        VehicleMessage::SetWorldPosition(vehicle.world_point()),
+                        VehicleMessage::SetChassisOrientation(*vehicle.chassis_orientation()),
+                        VehicleMessage::SetMainTurretOrientation(
+                            *vehicle.main_turret_relative_orientation(),
+                        ),
+                    ]
+                    .into_iter()
+                    .chain(
+                        vehicle
+                            .damages()
+                            .iter()
+                            .copied()
+                            .map(VehicleMessage::AddDamage),
+                    )
+                    .map(|message| {
+                        (
+                            side,
+                            OutputMessage::BattleState(BattleStateMessage::Vehicle(
+                                vehicle_index,
+                                message,
+                            )),
+                        )
+                    }),
+                );
+            }
+        }
+
+        outputs
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        behavior::Behavior,
+        deployment::Deployment,
+        types::{Distance, WorldPath, WorldPaths},
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        message::RunnerMessage,
+        test::{deployment, runner},
+    };
+
+    use super::*;
+
+    fn route_points(behavior: &Behavior) -> usize {
+        behavior
+            .world_paths()
+            .map(|paths| paths.paths.iter().map(|path| path.len()).sum())
+            .unwrap_or(0)
+    }
+
+    #[rstest]
+    fn opponent_only_know_next_point_of_visible_soldier_route(
+        mut runner: Runner,
+        deployment: Deployment,
+    ) {
+        // Given
+        let route = Behavior::MoveTo(WorldPaths::new(vec![
+            WorldPath::new(vec![WorldPoint::new(60., 100.), WorldPoint::new(80., 100.)]),
+            WorldPath::new(vec![WorldPoint::new(100., 100.)]),
+        ]));
+        runner.start_battle(deployment);
+        // Side B soldier 5 see side A soldier 0
+        let visibility = Visibility {
+            from: WorldPoint::new(0., 0.),
+            from_soldier: Some(SoldierIndex(5)),
+            to: WorldPoint::new(10., 10.),
+            to_soldier: Some(SoldierIndex(0)),
+            path_final_opacity: 0.,
+            to_scene_item_opacity: 0.,
+            opacity_segments: vec![],
+            visible: true,
+            distance: Distance::from_meters(1),
+            break_point: None,
+        };
+        runner.react(&vec![
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                SoldierIndex(0),
+                SoldierMessage::SetBehavior(route.clone()),
+            )),
+            RunnerMessage::BattleState(BattleStateMessage::SetVisibilities(HashMap::from([(
+                (SoldierIndex(5), SoldierIndex(0)),
+                visibility,
+            )]))),
+        ]);
+
+        // When
+        let mut outputs = runner.soldier_outputs(
+            &SoldierIndex(0),
+            &SoldierMessage::SetBehavior(route.clone()),
+        );
+        outputs
+            .extend(runner.soldier_outputs(&SoldierIndex(0), &SoldierMessage::ReachBehaviorStep));
+        outputs.extend(runner.revealed_outputs(&SidesVisibleSoldiers::new()));
+        let copy = runner.battle_state().side_copy(&Side::B);
+
+        // Then
+        let mut behaviors: Vec<&Behavior> = outputs
+            .iter()
+            .filter_map(|(side, output)| match (side, output) {
+                (
+                    Side::B,
+                    OutputMessage::BattleState(BattleStateMessage::Soldier(
+                        SoldierIndex(0),
+                        SoldierMessage::SetBehavior(behavior),
+                    )),
+                ) => Some(behavior),
+                _ => None,
+            })
+            .collect();
+        behaviors.push(copy.soldiers()[0].behavior());
+        assert_eq!(behaviors.len(), 4);
+        for behavior in behaviors {
+            assert_eq!(route_points(behavior), 1);
+            assert_eq!(
+                behavior.world_paths().and_then(|paths| paths.next_point()),
+                Some(WorldPoint::new(60., 100.))
+            );
+        }
+        // Own side still know the whole route
+        assert_eq!(
+            route_points(runner.battle_state().soldier(SoldierIndex(0)).behavior()),
+            3
+        );
+    }
+}
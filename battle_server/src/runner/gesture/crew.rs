@@ -0,0 +1,252 @@
+use battle_core::{
+    behavior::gesture::{Gesture, GestureContext},
+    config::CREW_ASSISTANT_DISTANCE,
+    entity::soldier::{Soldier, WeaponClass},
+    game::weapon::{Magazine, Weapon, WeaponDefinition},
+    physics::utils::distance_between_points,
+    state::{
+        battle::message::{BattleStateMessage, SoldierMessage},
+        client::ClientStateMessage,
+    },
+    types::WorldPoint,
+    utils::angle,
+};
+
+use crate::runner::{message::RunnerMessage, Runner};
+
+use super::GestureResult;
+
+impl Runner {
+    /// Crew served weapon carried by given soldier (who is then its gunner)
+    pub fn soldier_crew_weapon<'a>(
+        &'a self,
+        soldier: &'a Soldier,
+    ) -> Option<(WeaponClass, &'a Weapon, &'a WeaponDefinition)> {
+        let weapon = soldier.main_weapon().as_ref()?;
+        let definition = self.battle_state.catalog().weapon(weapon.type_())?;
+        definition
+            .crew_served()
+            .then_some((WeaponClass::Main, weapon, definition))
+    }
+
+    /// Nearest able squad member of the gunner, not carrying its own crew served weapon
+    pub fn crew_assistant(&self, gunner: &Soldier) -> Option<&Soldier> {
+        self.battle_state
+            .squad(gunner.squad_uuid())
+            .members()
+            .iter()
+            .map(|soldier_index| self.battle_state.soldier(*soldier_index))
+            .filter(|soldier| {
+                soldier.uuid() != gunner.uuid()
+                    && soldier.can_be_animated()
+                    && self.soldier_crew_weapon(soldier).is_none()
+            })
+            .map(|soldier| {
+                let distance =
+                    distance_between_points(&gunner.world_point(), &soldier.world_point());
+                (soldier, distance.millimeters())
+            })
+            .filter(|(_, distance)| *distance <= CREW_ASSISTANT_DISTANCE * 1000)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(soldier, _)| soldier)
+    }
+
+    /// Gunner, with set up weapon, assisted by given soldier
+    pub fn assisted_gunner(&self, soldier: &Soldier) -> Option<&Soldier> {
+        self.battle_state
+            .squad(soldier.squad_uuid())
+            .members()
+            .iter()
+            .map(|soldier_index| self.battle_state.soldier(*soldier_index))
+            .filter(|gunner| {
+                gunner.can_be_animated()
+                    && gunner.weapon_setup().is_some()
+                    && self.soldier_crew_weapon(gunner).is_some()
+            })
+            .find(|gunner| {
+                self.crew_assistant(gunner)
+                    .is_some_and(|assistant| assistant.uuid() == soldier.uuid())
+            })
+    }
+
+    /// Magazine to load into the gunner weapon : carried by its assistant first, then by
+    /// himself. Return the magazine owner with it.
+    pub fn crew_magazine<'a>(
+        &'a self,
+        gunner: &'a Soldier,
+        weapon: &Weapon,
+    ) -> Option<(&'a Soldier, &'a Magazine)> {
+        let definition = self.battle_state.catalog().weapon(weapon.type_())?;
+        self.crew_assistant(gunner)
+            .into_iter()
+            .chain([gunner])
+            .find_map(|owner| {
+                owner
+                    .magazines()
+                    .iter()
+                    .find(|magazine| definition.accepted_magazine(magazine))
+                    .map(|magazine| (owner, magazine))
+            })
+    }
+
+    /// Gesture to set up the crew served weapon toward given point, if it is not already
+    pub fn soldier_setup_gesture(
+        &self,
+        soldier: &Soldier,
+        point: &WorldPoint,
+        weapon: &Weapon,
+    ) -> Option<Gesture> {
+        let definition = self.battle_state.catalog().weapon(weapon.type_())?;
+        if !definition.crew_served() || soldier.weapon_setup().is_some() {
+            return None;
+        }
+
+        Some(Gesture::SettingUp(
+            self.soldier_setup_end(soldier, weapon),
+            angle(point, &soldier.world_point()),
+        ))
+    }
+
+    /// Gunner let its assistant load a new magazine
+    pub fn soldier_wait_assistant(&self, soldier: &Soldier, weapon: &Weapon) -> bool {
+        if self.soldier_crew_weapon(soldier).is_none() {
+            return false;
+        }
+
+        match self.crew_assistant(soldier) {
+            Some(assistant) => {
+                matches!(assistant.gesture(), Gesture::ReloadingAsAssistant(_, _))
+                    || (!weapon.can_fire() && !weapon.can_reload())
+            }
+            None => false,
+        }
+    }
+
+    pub fn assistant_gesture(&self, assistant: &Soldier, gunner: &Soldier) -> GestureResult {
+        let frame_i = *self.battle_state.frame_i();
+        let current = assistant.gesture();
+
+        if let Gesture::ReloadingAsAssistant(_, _) = current {
+            return GestureResult::Handled(
+                GestureContext::Idle,
+                current.next(frame_i, Gesture::Covering),
+            );
+        }
+
+        if let Some((_, weapon, _)) = self.soldier_crew_weapon(gunner) {
+            if !weapon.can_fire()
+                && !weapon.can_reload()
+                && self.crew_magazine(gunner, weapon).is_some()
+            {
+                return GestureResult::Handled(
+                    GestureContext::Idle,
+                    Gesture::ReloadingAsAssistant(
+                        self.assistant_reloading_end(assistant, weapon),
+                        gunner.uuid(),
+                    ),
+                );
+            }
+        }
+
+        GestureResult::Handled(GestureContext::Idle, Gesture::Covering)
+    }
+
+    pub fn tear_down_weapon_gesture(&self, soldier: &Soldier) -> GestureResult {
+        let current = soldier.gesture();
+
+        let gesture = match current {
+            Gesture::TearingDown(_) => current.next(*self.battle_state.frame_i(), Gesture::Idle),
+            _ => Gesture::TearingDown(self.soldier_teardown_end(soldier)),
+        };
+
+        GestureResult::Handled(GestureContext::Idle, gesture)
+    }
+
+    pub fn assistant_reloading_gesture_messages(&self, gunner: &Soldier) -> Vec<RunnerMessage> {
+        let (class, weapon, definition) = match self.soldier_crew_weapon(gunner) {
+            Some(crew_weapon) => crew_weapon,
+            None => return vec![],
+        };
+        let (owner, magazine) = match self.crew_magazine(gunner, weapon) {
+            Some(magazine) => magazine,
+            None => return vec![],
+        };
+
+        [
+            vec![
+                RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                    owner.uuid(),
+                    SoldierMessage::RemoveMagazine(magazine.clone()),
+                )),
+                RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                    gunner.uuid(),
+                    SoldierMessage::LoadWeapon(class, magazine.clone()),
+                )),
+            ],
+            definition
+                .reload_sounds(&mut *self.rng())
+                .into_iter()
+                .map(|sound| {
+                    RunnerMessage::ClientsState(ClientStateMessage::PlayWeaponSound(sound))
+                })
+                .collect(),
+        ]
+        .concat()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        behavior::gesture::Gesture,
+        deployment::Deployment,
+        message::InputMessage,
+        state::battle::message::{BattleStateMessage, SoldierMessage},
+        types::{Angle, SoldierIndex},
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        gesture::GestureResult,
+        test::{crew_deployment, runner},
+        Runner,
+    };
+
+    #[rstest]
+    fn assistant_reload_crew_served_weapon(mut runner: Runner, crew_deployment: Deployment) {
+        // Given
+        runner.step(vec![
+            InputMessage::LoadDeployment(crew_deployment),
+            InputMessage::BattleState(BattleStateMessage::Soldier(
+                SoldierIndex(0),
+                SoldierMessage::SetWeaponSetup(Some(Angle(0.))),
+            )),
+        ]);
+        let gunner = runner.battle_state().soldier(SoldierIndex(0));
+        let assistant = runner.battle_state().soldier(SoldierIndex(1));
+        assert_eq!(
+            runner
+                .assisted_gunner(assistant)
+                .map(|gunner| gunner.uuid()),
+            Some(SoldierIndex(0))
+        );
+
+        // When
+        let gesture = match runner.assistant_gesture(assistant, gunner) {
+            GestureResult::Handled(_, gesture) => gesture,
+            GestureResult::Cant(_) => unreachable!(),
+        };
+        let messages = runner.assistant_reloading_gesture_messages(gunner);
+        runner.step_messages(messages);
+
+        // Then
+        assert!(matches!(
+            gesture,
+            Gesture::ReloadingAsAssistant(_, SoldierIndex(0))
+        ));
+        let gunner = runner.battle_state().soldier(SoldierIndex(0));
+        let assistant = runner.battle_state().soldier(SoldierIndex(1));
+        assert_eq!(gunner.main_weapon().as_ref().unwrap().rounds(), 250);
+        assert!(assistant.magazines().is_empty());
+    }
+}
@@ -1,9 +1,13 @@
 use battle_core::{
-    behavior::gesture::{Gesture, GestureContext},
+    behavior::{
+        gesture::{Gesture, GestureContext},
+        Behavior,
+    },
     entity::soldier::{Soldier, WeaponClass},
     game::weapon::Weapon,
     physics::utils::distance_between_points,
     types::WorldPoint,
+    utils::angle,
 };
 use glam::Vec2;
 use rand::Rng;
@@ -26,7 +30,24 @@ impl Runner {
             return None;
         }
 
+        self.soldier_able_to_fire_unseen_point(soldier, point)
+    }
+
+    /// Like `soldier_able_to_fire_on_point`, without visibility check (ex. indirect fire)
+    pub fn soldier_able_to_fire_unseen_point<'a>(
+        &'a self,
+        soldier: &'a Soldier,
+        point: &WorldPoint,
+    ) -> Option<(WeaponClass, &'a Weapon)> {
+        if !self.main_turret_can_fire(soldier.uuid()) {
+            return None;
+        }
+
         if let Some((weapon_class, weapon)) = self.soldier_weapon_for_point(soldier, point) {
+            if !self.point_in_firing_arc(soldier, weapon, point) {
+                return None;
+            }
+
             if weapon.can_fire() || weapon.can_reload() {
                 return Some((weapon_class, weapon));
             }
@@ -34,11 +55,35 @@ impl Runner {
             if self.soldier_can_reload_with(soldier, weapon).is_some() {
                 return Some((weapon_class, weapon));
             }
+
+            if self.soldier_crew_weapon(soldier).is_some()
+                && self.crew_magazine(soldier, weapon).is_some()
+            {
+                return Some((weapon_class, weapon));
+            }
         }
 
         None
     }
 
+    /// Set up crew served weapon can only fire in its firing arc
+    pub fn point_in_firing_arc(
+        &self,
+        soldier: &Soldier,
+        weapon: &Weapon,
+        point: &WorldPoint,
+    ) -> bool {
+        match (
+            soldier.weapon_setup(),
+            self.battle_state.catalog().weapon(weapon.type_()),
+        ) {
+            (Some(setup), Some(definition)) if definition.crew_served() => {
+                definition.in_firing_arc(setup, &angle(point, &soldier.world_point()))
+            }
+            _ => true,
+        }
+    }
+
     pub fn engage_point_gesture(
         &self,
         soldier: &Soldier,
@@ -48,13 +93,21 @@ impl Runner {
         let frame_i = self.battle_state.frame_i();
         let current = soldier.gesture();
 
+        // Crew served weapon must be set up before firing
+        if let Some(gesture) = self.soldier_setup_gesture(soldier, point, weapon.1) {
+            return (GestureContext::Idle, gesture);
+        }
+
         let gesture = match current {
-            Gesture::Idle => {
-                //
-                Gesture::Reloading(
-                    self.soldier_reloading_end(soldier, weapon.1),
-                    weapon.0.clone(),
-                )
+            Gesture::Idle | Gesture::Covering => {
+                if self.soldier_wait_assistant(soldier, weapon.1) {
+                    Gesture::Idle
+                } else {
+                    Gesture::Reloading(
+                        self.soldier_reloading_end(soldier, weapon.1),
+                        weapon.0.clone(),
+                    )
+                }
             }
             Gesture::Reloading(_, _) => {
                 //
@@ -63,18 +116,25 @@ impl Runner {
                     Gesture::Aiming(self.soldier_aiming_end(soldier, weapon.1), weapon.0.clone()),
                 )
             }
+            // Main turret gunner wait the turret traverse before firing
+            Gesture::Aiming(_, _) if !self.main_turret_aimed_at(soldier.uuid(), point) => {
+                current.clone()
+            }
             Gesture::Aiming(_, _) => {
                 //
                 let end = self.soldier_firing_end(soldier, weapon.1);
                 current.next(*frame_i, Gesture::Firing(end, weapon.0.clone()))
             }
-            Gesture::Firing(_, _) => {
+            Gesture::Firing(_, _)
+            | Gesture::SettingUp(_, _)
+            | Gesture::TearingDown(_)
+            | Gesture::ReloadingAsAssistant(_, _) => {
                 //
                 current.next(*frame_i, Gesture::Idle)
             }
         };
 
-        let final_point = self.soldier_fire_point(soldier, &weapon.0, point);
+        let final_point = self.soldier_fire_point(soldier, weapon.1, point);
         (GestureContext::Firing(final_point, None), gesture)
     }
 
@@ -82,14 +142,26 @@ impl Runner {
     pub fn soldier_fire_point(
         &self,
         soldier: &Soldier,
-        _weapon_class: &WeaponClass,
+        weapon: &Weapon,
         target_point: &WorldPoint,
     ) -> WorldPoint {
-        let mut rng = rand::thread_rng();
-        // TODO : change precision according to weapon, stress, distance, etc
-        let range = 2.0
-            * (distance_between_points(&soldier.world_point(), target_point).meters() as f32
-                / 500.);
+        let mut rng = self.rng();
+        // TODO : change precision according to stress, etc
+        let distance =
+            distance_between_points(&soldier.world_point(), target_point).meters() as f32;
+        let range = self.battle_state.catalog().weapon(weapon.type_()).map_or(
+            2.0 * distance / 500.,
+            |definition| {
+                // Indirect fire rounds are less precise until ranged-in
+                match soldier.behavior() {
+                    Behavior::IndirectFire(_, ranging_rounds) if definition.indirect() => {
+                        definition.dispersion_at(distance)
+                            * definition.ranging_factor(*ranging_rounds)
+                    }
+                    _ => definition.dispersion_at(distance),
+                }
+            },
+        );
 
         if range == 0. {
             eprintln!(
@@ -104,3 +176,53 @@ impl Runner {
         target_point.apply(Vec2::new(x_change, y_change))
     }
 }
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::utils::angle;
+    use battle_core::{
+        behavior::gesture::Gesture,
+        deployment::Deployment,
+        entity::soldier::WeaponClass,
+        message::InputMessage,
+        state::battle::message::{BattleStateMessage, SoldierMessage},
+        types::SoldierIndex,
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        test::{crew_deployment, runner},
+        Runner,
+    };
+
+    #[rstest]
+    fn crew_served_weapon_is_set_up_before_firing(mut runner: Runner, crew_deployment: Deployment) {
+        // Given
+        runner.step(vec![InputMessage::LoadDeployment(crew_deployment)]);
+        let gunner = runner.battle_state().soldier(SoldierIndex(0));
+        let front = gunner.world_point().apply(glam::Vec2::new(100., 0.));
+        let behind = gunner.world_point().apply(glam::Vec2::new(-100., 0.));
+        let weapon = gunner.main_weapon().clone().unwrap();
+
+        // When
+        let (_, gesture) =
+            runner.engage_point_gesture(gunner, &front, (WeaponClass::Main, &weapon));
+
+        // Then
+        let expected_angle = angle(&front, &gunner.world_point());
+        assert!(matches!(gesture, Gesture::SettingUp(_, angle_) if angle_ == expected_angle));
+
+        // When
+        runner.step(vec![InputMessage::BattleState(
+            BattleStateMessage::Soldier(
+                SoldierIndex(0),
+                SoldierMessage::SetWeaponSetup(Some(expected_angle)),
+            ),
+        )]);
+
+        // Then
+        let gunner = runner.battle_state().soldier(SoldierIndex(0));
+        assert!(runner.point_in_firing_arc(gunner, &weapon, &front));
+        assert!(!runner.point_in_firing_arc(gunner, &weapon, &behind));
+    }
+}
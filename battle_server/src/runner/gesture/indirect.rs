@@ -0,0 +1,145 @@
+use battle_core::{
+    behavior::{
+        gesture::{Gesture, GestureContext},
+        Behavior,
+    },
+    entity::soldier::{Soldier, WeaponClass},
+    game::{
+        explosive::ExplosiveType,
+        weapon::{Weapon, WeaponDefinition},
+    },
+    physics::{
+        event::{cannon_blast::CannonBlast, indirect::IndirectFire},
+        utils::distance_between_points,
+    },
+    state::battle::message::{BattleStateMessage, SoldierMessage},
+    types::WorldPoint,
+};
+
+use crate::runner::{message::RunnerMessage, Runner};
+
+use super::{frames, GestureResult};
+
+impl Runner {
+    /// Indirect fire weapons fire on the point without seeing it. Other soldiers of the squad
+    /// wait (or assist the gunner).
+    pub fn indirect_fire_gesture(&self, soldier: &Soldier, point: &WorldPoint) -> GestureResult {
+        let indirect = soldier
+            .main_weapon()
+            .as_ref()
+            .and_then(|weapon| self.battle_state.catalog().weapon(weapon.type_()))
+            .is_some_and(|definition| definition.indirect());
+        if !indirect {
+            return GestureResult::Handled(GestureContext::Idle, Gesture::Idle);
+        }
+
+        if let Some(weapon) = self.soldier_able_to_fire_unseen_point(soldier, point) {
+            let (gesture_context, gesture) = self.engage_point_gesture(soldier, point, weapon);
+            return GestureResult::Handled(gesture_context, gesture);
+        }
+
+        GestureResult::Cant(None)
+    }
+
+    /// Fired round flies until its impact, where the server push its explosion
+    pub fn indirect_firing_gesture_messages(
+        &self,
+        soldier: &Soldier,
+        class: &WeaponClass,
+        weapon: &Weapon,
+        definition: &WeaponDefinition,
+        explosive: &ExplosiveType,
+        point: &WorldPoint,
+    ) -> Vec<RunnerMessage> {
+        let distance = distance_between_points(&soldier.world_point(), point).meters() as f32;
+        let flight = frames(definition.flight_time(distance));
+
+        let mut messages = vec![
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                soldier.uuid(),
+                SoldierMessage::WeaponShot(class.clone()),
+            )),
+            RunnerMessage::BattleState(BattleStateMessage::PushIndirectFire(IndirectFire::new(
+                soldier.world_point(),
+                *point,
+                flight,
+                explosive.clone(),
+                weapon.type_().clone(),
+            ))),
+            RunnerMessage::BattleState(BattleStateMessage::PushCannonBlast(CannonBlast::new(
+                soldier.world_point(),
+                soldier.get_looking_direction(),
+                soldier.animation_type().0,
+            ))),
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                soldier.uuid(),
+                SoldierMessage::SetLastShootFrameI(*self.battle_state.frame_i()),
+            )),
+        ];
+
+        // Each round fired on the same point improve next ones precision
+        if let Behavior::IndirectFire(target, ranging_rounds) = soldier.behavior() {
+            messages.push(RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                soldier.uuid(),
+                SoldierMessage::SetBehavior(Behavior::IndirectFire(*target, ranging_rounds + 1)),
+            )));
+        }
+
+        messages
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        deployment::Deployment,
+        entity::soldier::WeaponClass,
+        game::weapon::{Weapon, WeaponType},
+        message::InputMessage,
+        physics::utils::distance_between_points,
+        types::SoldierIndex,
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        gesture,
+        test::{deployment, runner},
+        Runner,
+    };
+
+    #[rstest]
+    fn indirect_fire_round_explode_on_impact(mut runner: Runner, deployment: Deployment) {
+        // Given
+        runner.step(vec![InputMessage::LoadDeployment(deployment)]);
+        let weapon = Weapon::new(WeaponType("BrandtMle2731".to_string()), true, None);
+        let soldier = runner.battle_state().soldier(SoldierIndex(0));
+        let point = soldier.world_point().apply(glam::Vec2::new(1000., 0.));
+        let definition = runner
+            .battle_state()
+            .catalog()
+            .weapon(weapon.type_())
+            .unwrap();
+        let distance = distance_between_points(&soldier.world_point(), &point).meters() as f32;
+        let expected_flight = gesture::frames(definition.flight_time(distance));
+
+        // When
+        let messages =
+            runner.firing_gesture_messages(soldier, &WeaponClass::Main, &weapon, &point, &None);
+        runner.step_messages(messages);
+
+        // Then
+        let indirect_fire = runner.battle_state().indirect_fires()[0].clone();
+        assert!(expected_flight > 0);
+        assert_eq!(indirect_fire.flight(), expected_flight);
+        assert!(runner.battle_state().bullet_fires().is_empty());
+        assert!(runner.battle_state().explosions().is_empty());
+
+        // When
+        runner.advance(expected_flight + 1);
+
+        // Then
+        assert!(runner.battle_state().indirect_fires().is_empty());
+        let explosion = &runner.battle_state().explosions()[0];
+        assert_eq!(explosion.point(), &point);
+    }
+}
@@ -3,9 +3,10 @@ use battle_core::{
         gesture::{Gesture, GestureContext},
         Behavior,
     },
+    config::TARGET_FPS,
     entity::soldier::{Soldier, WeaponClass},
     game::weapon::Weapon,
-    physics::event::{bullet::BulletFire, cannon_blast::CannonBlast},
+    physics::event::{bullet::BulletFire, cannon_blast::CannonBlast, explosion::Explosion},
     state::{
         battle::message::{BattleStateMessage, SoldierMessage},
         client::ClientStateMessage,
@@ -15,15 +16,22 @@ use battle_core::{
 
 use super::{message::RunnerMessage, Runner};
 
+mod crew;
 mod engage;
 mod fire;
 mod idle;
+mod indirect;
 mod soldier;
 mod suppress;
 mod weapon;
 
 pub struct FallbackBehavior(pub Behavior);
 
+/// Frames count of given duration
+pub fn frames(milliseconds: u64) -> u64 {
+    milliseconds * TARGET_FPS / 1000
+}
+
 pub enum GestureResult {
     Handled(GestureContext, Gesture),
     Cant(Option<FallbackBehavior>),
@@ -34,19 +42,39 @@ impl Runner {
         puffin::profile_scope!("soldier_gesture");
         let mut messages = vec![];
 
-        let new_gesture = match soldier.behavior() {
-            Behavior::Idle(_) => {
+        // Assistant stay by its gunner set up weapon, unless ordered to move
+        let assisted_gunner = soldier
+            .behavior()
+            .world_paths()
+            .is_none()
+            .then(|| self.assisted_gunner(soldier))
+            .flatten();
+
+        let new_gesture = match (soldier.behavior(), assisted_gunner) {
+            (_, Some(gunner)) => {
+                //
+                self.assistant_gesture(soldier, gunner)
+            }
+            (Behavior::Idle(_), None) => {
                 //
                 self.idle_gesture(soldier)
             }
-            Behavior::SuppressFire(point) => {
+            (Behavior::SuppressFire(point), None) => {
                 //
                 self.suppress_fire_gesture(soldier, point)
             }
-            Behavior::EngageSoldier(soldier_index) => {
+            (Behavior::EngageSoldier(soldier_index), None) => {
                 //
                 self.engage_soldier_gesture(soldier, soldier_index)
             }
+            (Behavior::IndirectFire(point, _), None) => {
+                //
+                self.indirect_fire_gesture(soldier, point)
+            }
+            (Behavior::TearDownWeapon, None) => {
+                //
+                self.tear_down_weapon_gesture(soldier)
+            }
             _ => GestureResult::Handled(GestureContext::Idle, Gesture::Idle),
         };
 
@@ -90,6 +118,29 @@ impl Runner {
                 }
             }
             (_, Gesture::Aiming(_, _)) => {}
+            (_, Gesture::SettingUp(_, angle)) => {
+                return vec![
+                    RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                        soldier.uuid(),
+                        SoldierMessage::SetWeaponSetup(Some(*angle)),
+                    )),
+                    RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                        soldier.uuid(),
+                        SoldierMessage::SetOrientation(*angle),
+                    )),
+                ];
+            }
+            (_, Gesture::TearingDown(_)) => {
+                return vec![RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                    soldier.uuid(),
+                    SoldierMessage::SetWeaponSetup(None),
+                ))];
+            }
+            (_, Gesture::ReloadingAsAssistant(_, gunner_index)) => {
+                return self.assistant_reloading_gesture_messages(
+                    self.battle_state.soldier(*gunner_index),
+                );
+            }
             (GestureContext::Firing(point, target), Gesture::Firing(_, class)) => {
                 if let Some(weapon) = soldier.weapon(class) {
                     return self.firing_gesture_messages(soldier, class, weapon, point, target);
@@ -107,16 +158,40 @@ impl Runner {
         class: &WeaponClass,
         weapon: &Weapon,
     ) -> Vec<RunnerMessage> {
-        [
-            vec![RunnerMessage::BattleState(BattleStateMessage::Soldier(
+        let definition = match self.battle_state.catalog().weapon(weapon.type_()) {
+            Some(definition) => definition,
+            None => return vec![],
+        };
+
+        // Weapon accepting several ammunitions load the one fitting its target
+        let reload = match (
+            weapon.magazine(),
+            definition.magazines().len() > 1,
+            self.soldier_preferred_magazine(soldier, weapon),
+        ) {
+            (None, true, Some(magazine)) => vec![
+                RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                    soldier.uuid(),
+                    SoldierMessage::RemoveMagazine(magazine.clone()),
+                )),
+                RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                    soldier.uuid(),
+                    SoldierMessage::LoadWeapon(class.clone(), magazine.clone()),
+                )),
+            ],
+            _ => vec![RunnerMessage::BattleState(BattleStateMessage::Soldier(
                 soldier.uuid(),
                 SoldierMessage::ReloadWeapon(class.clone()),
             ))],
-            weapon
-                .reload_sounds()
-                .iter()
+        };
+
+        [
+            reload,
+            definition
+                .reload_sounds(&mut *self.rng())
+                .into_iter()
                 .map(|sound| {
-                    RunnerMessage::ClientsState(ClientStateMessage::PlayBattleSound(*sound))
+                    RunnerMessage::ClientsState(ClientStateMessage::PlayWeaponSound(sound))
                 })
                 .collect(),
         ]
@@ -131,29 +206,222 @@ impl Runner {
         point: &WorldPoint,
         target: &Option<(SoldierIndex, Precision)>,
     ) -> Vec<RunnerMessage> {
-        [vec![
-            RunnerMessage::BattleState(BattleStateMessage::Soldier(
-                soldier.uuid(),
-                SoldierMessage::WeaponShot(class.clone()),
-            )),
-            RunnerMessage::BattleState(BattleStateMessage::PushBulletFire(BulletFire::new(
-                soldier.world_point(),
-                *point,
-                target.clone(),
-                weapon.ammunition(),
-                weapon.gun_fire_sound_type(),
-            ))),
+        let catalog = self.battle_state.catalog();
+        let (definition, ammunition) = match (
+            catalog.weapon(weapon.type_()),
+            catalog.weapon_ammunition(weapon),
+        ) {
+            (Some(definition), Some(ammunition)) => (definition, ammunition),
+            _ => return vec![],
+        };
+
+        if let Some(explosive) = definition.explosive() {
+            return self.indirect_firing_gesture_messages(
+                soldier, class, weapon, definition, explosive, point,
+            );
+        }
+
+        // Automatic weapons fire a burst, limited by magazine and barrel heat
+        let frame_i = *self.battle_state.frame_i();
+        let rounds = definition
+            .burst()
+            .min(weapon.rounds())
+            .min(weapon.rounds_before_overheat(definition, frame_i))
+            .max(1);
+        let shot_interval = frames(definition.shot_interval());
+
+        // Explosive shells (ex. tank gun HE) explode where they land instead of hitting
+        let explosive = catalog
+            .ammunition(&ammunition)
+            .and_then(|definition| definition.explosive().clone());
+        let target = if explosive.is_some() { &None } else { target };
+
+        let mut messages = vec![];
+        for round in 0..rounds {
+            // Next rounds deviate from the aimed point
+            let point = if round == 0 {
+                *point
+            } else {
+                self.soldier_fire_point(soldier, weapon, point)
+            };
+            messages.extend([
+                RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                    soldier.uuid(),
+                    SoldierMessage::WeaponShot(class.clone()),
+                )),
+                RunnerMessage::BattleState(BattleStateMessage::PushBulletFire(
+                    BulletFire::new(
+                        soldier.world_point(),
+                        point,
+                        target.clone(),
+                        ammunition.clone(),
+                        weapon.type_().clone(),
+                    )
+                    .delayed(round as u64 * shot_interval),
+                )),
+            ]);
+        }
+
+        if let Some(explosive) = explosive {
+            messages.push(RunnerMessage::BattleState(
+                BattleStateMessage::PushExplosion(Explosion::new(*point, explosive)),
+            ));
+        }
+
+        messages.extend([
             RunnerMessage::BattleState(BattleStateMessage::PushCannonBlast(CannonBlast::new(
                 soldier.world_point(),
                 soldier.get_looking_direction(),
-                weapon.sprite_type(),
                 soldier.animation_type().0,
             ))),
             RunnerMessage::BattleState(BattleStateMessage::Soldier(
                 soldier.uuid(),
-                SoldierMessage::SetLastShootFrameI(*self.battle_state.frame_i()),
+                SoldierMessage::SetLastShootFrameI(frame_i),
             )),
-        ]]
-        .concat()
+        ]);
+        messages
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        behavior::Behavior,
+        deployment::Deployment,
+        entity::soldier::WeaponClass,
+        game::weapon::{Magazine, MagazineType, Weapon, WeaponType},
+        message::InputMessage,
+        state::battle::message::{BattleStateMessage, SoldierMessage},
+        types::{SoldierIndex, WorldPoint},
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        message::RunnerMessage,
+        test::{deployment, runner, tank_deployment},
+        Runner,
+    };
+
+    #[rstest]
+    #[case("MosinNagantM1924", "MosinNagant", 1)]
+    #[case("MG34", "MG34Belt", 8)]
+    fn firing_gesture_fire_weapon_burst(
+        mut runner: Runner,
+        deployment: Deployment,
+        #[case] weapon_type: &str,
+        #[case] magazine_type: &str,
+        #[case] expected_rounds: usize,
+    ) {
+        // Given
+        runner.step(vec![InputMessage::LoadDeployment(deployment)]);
+        let magazine = runner
+            .battle_state()
+            .catalog()
+            .full_magazine(&MagazineType(magazine_type.to_string()))
+            .unwrap();
+        let weapon = Weapon::new(WeaponType(weapon_type.to_string()), true, Some(magazine));
+        let soldier = runner.battle_state().soldier(SoldierIndex(0));
+
+        // When
+        let messages = runner.firing_gesture_messages(
+            soldier,
+            &WeaponClass::Main,
+            &weapon,
+            &WorldPoint::new(500., 500.),
+            &None,
+        );
+
+        // Then
+        let delays: Vec<u64> = messages
+            .iter()
+            .filter_map(|message| match message {
+                RunnerMessage::BattleState(BattleStateMessage::PushBulletFire(bullet_fire)) => {
+                    Some(bullet_fire.delay())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(delays.len(), expected_rounds);
+        assert!(delays.windows(2).all(|delays| delays[0] < delays[1]));
+    }
+
+    #[rstest]
+    #[case("x45HE", true)]
+    #[case("x45AP", false)]
+    fn main_gun_shell_explode_or_hit(
+        mut runner: Runner,
+        tank_deployment: Deployment,
+        #[case] magazine_type: &str,
+        #[case] expect_explosion: bool,
+    ) {
+        // Given
+        runner.step(vec![InputMessage::LoadDeployment(tank_deployment)]);
+        let magazine = Magazine::new(MagazineType(magazine_type.to_string()), 1);
+        let weapon = Weapon::new(WeaponType("M1932Gun45mm".to_string()), true, Some(magazine));
+        let gunner = runner.battle_state().soldier(SoldierIndex(1));
+        let target = runner.battle_state().soldier(SoldierIndex(5));
+
+        // When
+        let messages = runner.firing_gesture_messages(
+            gunner,
+            &WeaponClass::Main,
+            &weapon,
+            &target.world_point(),
+            &None,
+        );
+
+        // Then
+        let explosion = messages.iter().any(|message| {
+            matches!(
+                message,
+                RunnerMessage::BattleState(BattleStateMessage::PushExplosion(_))
+            )
+        });
+        let bullet_fire = messages.iter().any(|message| {
+            matches!(
+                message,
+                RunnerMessage::BattleState(BattleStateMessage::PushBulletFire(_))
+            )
+        });
+        assert!(bullet_fire);
+        assert_eq!(explosion, expect_explosion);
+    }
+
+    #[rstest]
+    #[case(SoldierIndex(5), "x45HE")]
+    #[case(SoldierIndex(0), "x45AP")]
+    fn main_gun_load_shell_according_to_target(
+        mut runner: Runner,
+        tank_deployment: Deployment,
+        #[case] target: SoldierIndex,
+        #[case] expected_magazine_type: &str,
+    ) {
+        // Given
+        runner.step(vec![
+            InputMessage::LoadDeployment(tank_deployment),
+            InputMessage::BattleState(BattleStateMessage::Soldier(
+                SoldierIndex(1),
+                SoldierMessage::SetBehavior(Behavior::EngageSoldier(target)),
+            )),
+        ]);
+        let gunner = runner.battle_state().soldier(SoldierIndex(1));
+        let weapon = gunner.main_weapon().clone().unwrap();
+
+        // When
+        runner.step_messages(runner.reloading_gesture_messages(
+            gunner,
+            &WeaponClass::Main,
+            &weapon,
+        ));
+
+        // Then
+        let gunner = runner.battle_state().soldier(SoldierIndex(1));
+        let weapon = gunner.main_weapon().as_ref().unwrap();
+        assert!(weapon.can_fire());
+        assert_eq!(gunner.magazines().len(), 1);
+        assert_ne!(
+            gunner.magazines()[0].type_(),
+            &MagazineType(expected_magazine_type.to_string())
+        );
     }
 }
@@ -3,22 +3,81 @@ use rand::Rng;
 
 use crate::runner::Runner;
 
+use super::frames;
+
 impl Runner {
-    pub fn soldier_reloading_end(&self, _soldier: &Soldier, _weapon: &Weapon) -> u64 {
+    pub fn soldier_reloading_end(&self, _soldier: &Soldier, weapon: &Weapon) -> u64 {
         // TODO : Depending multiple factor
-        let mut rng = rand::thread_rng();
-        self.battle_state.frame_i() + TARGET_FPS + rng.gen_range(0..50)
+        let duration =
+            self.battle_state
+                .catalog()
+                .weapon(weapon.type_())
+                .map_or(TARGET_FPS, |definition| {
+                    // Chamber the last round of the magazine include its replacement
+                    if weapon
+                        .magazine()
+                        .as_ref()
+                        .is_some_and(|magazine| magazine.fill() > 1)
+                    {
+                        frames(definition.shot_interval())
+                    } else if definition.crew_served() {
+                        // Gunner without assistant handle the magazine alone
+                        frames(definition.reload_time() * 2)
+                    } else {
+                        frames(definition.reload_time())
+                    }
+                });
+        self.battle_state.frame_i() + duration + self.rng().gen_range(0..50)
     }
 
-    pub fn soldier_aiming_end(&self, _soldier: &Soldier, _weapon: &Weapon) -> u64 {
+    pub fn soldier_aiming_end(&self, _soldier: &Soldier, weapon: &Weapon) -> u64 {
         // TODO : Depending multiple factor
-        let mut rng = rand::thread_rng();
-        self.battle_state.frame_i() + TARGET_FPS + rng.gen_range(0..50)
+        let frame_i = *self.battle_state.frame_i();
+        let duration =
+            self.battle_state
+                .catalog()
+                .weapon(weapon.type_())
+                .map_or(TARGET_FPS, |definition| {
+                    // Too hot barrel must cool down before firing
+                    frames(definition.aiming_time()) + weapon.cooling_frames(definition, frame_i)
+                });
+        frame_i + duration + self.rng().gen_range(0..50)
+    }
+
+    pub fn soldier_firing_end(&self, _soldier: &Soldier, weapon: &Weapon) -> u64 {
+        // TODO : Depending multiple factor like stress
+        let burst_duration = self
+            .battle_state
+            .catalog()
+            .weapon(weapon.type_())
+            .map_or(0, |definition| {
+                (definition.burst() as u64 - 1) * frames(definition.shot_interval())
+            });
+        self.battle_state.frame_i() + 5 + burst_duration + self.rng().gen_range(0..50)
+    }
+
+    pub fn soldier_setup_end(&self, _soldier: &Soldier, weapon: &Weapon) -> u64 {
+        let duration = self
+            .battle_state
+            .catalog()
+            .weapon(weapon.type_())
+            .map_or(0, |definition| frames(definition.setup_time()));
+        self.battle_state.frame_i() + duration + self.rng().gen_range(0..50)
+    }
+
+    pub fn soldier_teardown_end(&self, soldier: &Soldier) -> u64 {
+        let duration = self
+            .soldier_crew_weapon(soldier)
+            .map_or(0, |(_, _, definition)| frames(definition.teardown_time()));
+        self.battle_state.frame_i() + duration + self.rng().gen_range(0..50)
     }
 
-    pub fn soldier_firing_end(&self, _soldier: &Soldier, _weapon: &Weapon) -> u64 {
-        // TODO : Depending multiple factor like weapon, riffle or single shot etc
-        let mut rng = rand::thread_rng();
-        self.battle_state.frame_i() + 5 + rng.gen_range(0..50)
+    pub fn assistant_reloading_end(&self, _assistant: &Soldier, weapon: &Weapon) -> u64 {
+        let duration = self
+            .battle_state
+            .catalog()
+            .weapon(weapon.type_())
+            .map_or(TARGET_FPS, |definition| frames(definition.reload_time()));
+        self.battle_state.frame_i() + duration + self.rng().gen_range(0..50)
     }
 }
@@ -1,6 +1,8 @@
 use battle_core::{
+    behavior::Behavior,
     entity::soldier::{Soldier, WeaponClass},
     game::weapon::{Magazine, Weapon},
+    physics::utils::distance_between_points,
     types::WorldPoint,
 };
 
@@ -10,12 +12,20 @@ impl Runner {
     pub fn soldier_weapon_for_point<'a>(
         &'a self,
         soldier: &'a Soldier,
-        _point: &WorldPoint,
+        point: &WorldPoint,
     ) -> Option<(WeaponClass, &Weapon)> {
         // TODO : according to distance, choose weapon
+        let distance = distance_between_points(&soldier.world_point(), point).meters() as f32;
         soldier
             .main_weapon()
             .as_ref()
+            // Weapons unknown by the catalog can't be used
+            .filter(|weapon| {
+                self.battle_state
+                    .catalog()
+                    .weapon(weapon.type_())
+                    .is_some_and(|definition| definition.in_range(distance))
+            })
             .map(|weapon| (WeaponClass::Main, weapon))
     }
 
@@ -24,9 +34,40 @@ impl Runner {
         soldier: &'a Soldier,
         weapon: &Weapon,
     ) -> Option<&Magazine> {
+        let definition = self.battle_state.catalog().weapon(weapon.type_())?;
         soldier
             .magazines()
             .iter()
-            .find(|&magazine| weapon.accepted_magazine(magazine))
+            .find(|&magazine| definition.accepted_magazine(magazine))
+    }
+
+    /// Magazine to load in a weapon accepting several ammunitions (ex. HE or AP shells) :
+    /// armor piercing one against a target on board a vehicle, explosive one otherwise
+    pub fn soldier_preferred_magazine<'a>(
+        &'a self,
+        soldier: &'a Soldier,
+        weapon: &Weapon,
+    ) -> Option<&'a Magazine> {
+        let catalog = self.battle_state.catalog();
+        let definition = catalog.weapon(weapon.type_())?;
+        let armored_target = match soldier.behavior() {
+            Behavior::EngageSoldier(target) => self.battle_state.soldier_vehicle(*target).is_some(),
+            _ => false,
+        };
+        let explosive = |magazine: &Magazine| {
+            catalog
+                .magazine(magazine.type_())
+                .and_then(|magazine| catalog.ammunition(magazine.ammunition()))
+                .is_some_and(|ammunition| ammunition.explosive().is_some())
+        };
+        let accepted = soldier
+            .magazines()
+            .iter()
+            .filter(|magazine| definition.accepted_magazine(magazine));
+
+        accepted
+            .clone()
+            .find(|magazine| explosive(magazine) != armored_target)
+            .or_else(|| accepted.clone().next())
     }
 }
@@ -1,60 +1,376 @@
 use battle_core::{
+    game::Side,
     message::{InputMessage, OutputMessage},
-    state::battle::BattleState,
+    save::SaveWriter,
+    state::battle::{message::BattleStateMessage, BattleState},
 };
 use crossbeam_channel::TryRecvError;
 
 use super::{Runner, RunnerError};
 
 impl Runner {
-    pub fn inputs(&mut self) -> Result<(), RunnerError> {
-        puffin::profile_scope!("inputs");
+    pub fn receive_inputs(&self) -> Result<Vec<InputMessage>, RunnerError> {
+        puffin::profile_scope!("receive_inputs");
+        let input = self.input.as_ref().ok_or(RunnerError::Headless)?;
+        let mut inputs = vec![];
+
         loop {
-            let inputs = match self.input.try_recv() {
-                Ok(message) => message,
+            match input.try_recv() {
+                Ok(messages) => inputs.extend(messages),
                 Err(error) => match error {
                     TryRecvError::Empty => break,
                     TryRecvError::Disconnected => return Err(RunnerError::InputChannelClosed),
                 },
             };
-            log::debug!("Received {} inputs : {:?}", inputs.len(), &inputs);
+        }
 
-            let mut side_effects = vec![];
-            for input in inputs {
-                match input {
-                    InputMessage::LoadDeployment(deployment) => {
-                        self.battle_state.inject(&deployment)
-                    }
-                    InputMessage::LoadControl((a_control, b_control)) => {
-                        //
-                        self.battle_state
-                            .update_flags_from_control(a_control, b_control);
+        Ok(inputs)
+    }
+
+    /// Unwrap remote players messages, dropping ones this player is not allowed to send. Return
+    /// accepted messages and rejections to report to their senders.
+    pub fn authorized_inputs(
+        &self,
+        inputs: Vec<InputMessage>,
+    ) -> (Vec<InputMessage>, Vec<(Side, OutputMessage)>) {
+        let mut authorized = vec![];
+        let mut rejections = vec![];
+
+        for input in inputs {
+            match input {
+                InputMessage::FromPlayer(player, messages) => {
+                    for message in messages {
+                        match self.validate(&player, &message) {
+                            Ok(()) => authorized.push(message),
+                            Err(rejection) => {
+                                log::warn!(
+                                    "Player {} ({}) message {:?} rejected : {}",
+                                    player.name(),
+                                    player.side(),
+                                    message,
+                                    rejection
+                                );
+                                rejections.push((
+                                    *player.side(),
+                                    OutputMessage::InputRejected(player.clone(), rejection),
+                                ));
+                            }
+                        }
                     }
-                    InputMessage::RequireCompleteSync => {
-                        self.output
-                            .send(vec![OutputMessage::LoadFromCopy(self.battle_state.copy())])?;
+                }
+                _ => authorized.push(input),
+            }
+        }
+
+        (authorized, rejections)
+    }
+
+    pub fn inputs(&mut self, inputs: Vec<InputMessage>) -> Vec<(Side, OutputMessage)> {
+        puffin::profile_scope!("inputs");
+        log::debug!("Received {} inputs : {:?}", inputs.len(), &inputs);
+        let mut outputs = vec![];
+        // Loaded soldiers and controls must be known by already synchronized clients
+        let mut sync_required = false;
+
+        let mut side_effects = vec![];
+        for input in inputs {
+            match input {
+                InputMessage::LoadDeployment(_) | InputMessage::LoadControl(_)
+                    if !self.battle_state.phase().is_placement() =>
+                {
+                    log::info!("Battle already started, ignore deployment and control")
+                }
+                InputMessage::LoadDeployment(deployment) => {
+                    // Sides can already be deployed by another client or by a loaded save
+                    if deployment
+                        .sides()
+                        .iter()
+                        .any(|side| self.battle_state.side_is_deployed(side))
+                    {
+                        log::info!("Side already deployed, ignore deployment");
+                        continue;
                     }
-                    InputMessage::BattleState(battle_state_message) => {
-                        side_effects.extend(
-                            self.battle_state
-                                .react(&battle_state_message, *self.battle_state.frame_i()),
-                        );
+
+                    match deployment.vehicles().iter().find(|vehicle| {
+                        self.battle_state
+                            .catalog()
+                            .vehicle(vehicle.type_())
+                            .is_none()
+                    }) {
+                        Some(vehicle) => log::error!(
+                            "Deployment refers to unknown vehicle type {}, ignore it",
+                            vehicle.type_()
+                        ),
+                        None => {
+                            self.battle_state.inject(&deployment);
+                            sync_required = true;
+                        }
                     }
-                    InputMessage::ChangeConfig(change_config) => {
-                        self.output
-                            .send(vec![OutputMessage::ChangeConfig(change_config.clone())])?;
-                        self.config.react(&change_config);
+                }
+                InputMessage::LoadControl((a_control, b_control)) => {
+                    self.battle_state.load_controls(a_control, b_control);
+                    sync_required = true;
+                }
+                InputMessage::RequireCompleteSync(side) => {
+                    outputs.extend(self.complete_sync_outputs(&side));
+                }
+                InputMessage::BattleState(battle_state_message) => {
+                    if let BattleStateMessage::Soldier(soldier_index, _) = &battle_state_message {
+                        self.replication.forget_soldier(soldier_index);
                     }
-                    InputMessage::SetBattleState(copy) => {
-                        //
-                        self.battle_state = BattleState::from_copy(&copy, self.battle_state.map());
-                        self.battle_state.resolve();
-                        self.output.send(vec![OutputMessage::LoadFromCopy(copy)])?;
+                    side_effects.extend(
+                        self.battle_state
+                            .react(&battle_state_message, *self.battle_state.frame_i()),
+                    );
+                }
+                InputMessage::ChangeConfig(change_config) => {
+                    outputs.push((
+                        Side::All,
+                        OutputMessage::ChangeConfig(change_config.clone()),
+                    ));
+                    self.config.react(&change_config);
+                }
+                InputMessage::SetBattleState(copy) => {
+                    //
+                    self.battle_state = BattleState::from_copy(
+                        &copy,
+                        self.battle_state.map(),
+                        self.battle_state.catalog(),
+                    );
+                    self.battle_state.resolve();
+                    outputs.extend(self.complete_sync_outputs(&Side::All));
+                }
+                InputMessage::RequestSave(side) => {
+                    match SaveWriter::to_bytes(
+                        self.battle_state.map().name(),
+                        &self.battle_state.copy(),
+                    ) {
+                        Ok(bytes) => outputs.push((side, OutputMessage::SaveData(bytes))),
+                        Err(error) => log::error!("Error while making save : {}", error),
                     }
-                };
+                }
+                InputMessage::Handshake(_) => {
+                    // Handshakes are answered by network server
+                }
+                InputMessage::FromPlayer(_, _) => {
+                    // Players messages are unwrapped by authorized_inputs
+                }
+            };
+        }
+
+        if sync_required {
+            outputs.extend(self.complete_sync_outputs(&Side::All));
+        }
+
+        outputs
+    }
+
+    /// Complete state of the battle, as known by given side (or by each side for `Side::All`)
+    fn complete_sync_outputs(&self, side: &Side) -> Vec<(Side, OutputMessage)> {
+        let sides = match side {
+            Side::All => vec![Side::A, Side::B, Side::Observer],
+            _ => vec![*side],
+        };
+
+        sides
+            .into_iter()
+            .map(|side| {
+                (
+                    side,
+                    OutputMessage::LoadFromCopy(Box::new(self.battle_state.side_copy(&side))),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        behavior::{Behavior, Body},
+        deployment::{Deployment, VehicleDeployment},
+        entity::vehicle::VehicleType,
+        game::{control::MapControl, player::Player, weapon::WeaponType, Side},
+        message::{rejection::InputRejection, InputMessage, OutputMessage},
+        order::Order,
+        save::SaveReader,
+        types::{PlayerName, SoldierIndex, VehicleIndex, WorldPoint},
+    };
+    use oc_core::spawn::SpawnZoneName;
+    use rstest::*;
+
+    use crate::runner::{
+        test::{deployment, rejections, runner, tank_deployment},
+        Runner,
+    };
+
+    #[rstest]
+    fn deployed_tank_gunner_is_armed_with_its_main_gun(
+        mut runner: Runner,
+        tank_deployment: Deployment,
+    ) {
+        // When
+        runner.step(vec![InputMessage::LoadDeployment(tank_deployment)]);
+
+        // Then
+        let gunner = runner.battle_state().soldier(SoldierIndex(1));
+        assert_eq!(
+            gunner.main_weapon().as_ref().map(|weapon| weapon.type_()),
+            Some(&WeaponType("M1932Gun45mm".to_string()))
+        );
+        let driver = runner.battle_state().soldier(SoldierIndex(0));
+        assert!(driver.main_weapon().is_none());
+    }
+
+    #[rstest]
+    fn deployment_with_unknown_vehicle_type_is_ignored(
+        mut runner: Runner,
+        tank_deployment: Deployment,
+    ) {
+        // Given
+        let vehicles = vec![VehicleDeployment::new(
+            VehicleIndex(0),
+            VehicleType("Unknown".to_string()),
+            WorldPoint::new(0., 0.),
+        )];
+        let deployment = Deployment::from((
+            tank_deployment.soldiers().to_vec(),
+            vehicles,
+            tank_deployment.boards().clone(),
+        ));
+
+        // When
+        runner.step(vec![InputMessage::LoadDeployment(deployment)]);
+
+        // Then
+        assert!(runner.battle_state().soldiers().is_empty());
+        assert!(runner.battle_state().vehicles().is_empty());
+    }
+
+    #[rstest]
+    fn save_is_sent_to_requester(mut runner: Runner, deployment: Deployment) {
+        // Given
+        runner.step(vec![InputMessage::LoadDeployment(deployment.clone())]);
+        runner.advance(10);
+
+        // When
+        let outputs = runner.frame(vec![
+            InputMessage::RequestSave(Side::B),
+            // Already deployed battle must be kept as is
+            InputMessage::LoadDeployment(deployment),
+        ]);
+
+        // Then
+        let saves: Vec<&(Side, OutputMessage)> = outputs
+            .iter()
+            .filter(|(_, output)| matches!(output, OutputMessage::SaveData(_)))
+            .collect();
+        assert_eq!(saves.len(), 1);
+        let (side, OutputMessage::SaveData(save)) = saves[0] else {
+            unreachable!()
+        };
+        let copy = SaveReader::from_bytes(save, "map1").unwrap();
+        assert_eq!(side, &Side::B);
+        assert_eq!(copy.frame_i(), 11);
+        assert_eq!(copy.soldiers().len(), 10);
+        assert_eq!(runner.battle_state().soldiers().len(), 10);
+    }
+
+    #[rstest]
+    fn complete_sync_is_filtered_for_requester(mut runner: Runner, deployment: Deployment) {
+        // Given
+        runner.start_battle(deployment);
+        runner.advance(60);
+        let known = runner.battle_state().soldiers_known_by_side(&Side::A);
+
+        // When
+        let outputs = runner.frame(vec![InputMessage::RequireCompleteSync(Side::A)]);
+
+        // Then
+        let copies: Vec<&(Side, OutputMessage)> = outputs
+            .iter()
+            .filter(|(_, output)| matches!(output, OutputMessage::LoadFromCopy(_)))
+            .collect();
+        assert_eq!(copies.len(), 1);
+        let (side, OutputMessage::LoadFromCopy(copy)) = copies[0] else {
+            unreachable!()
+        };
+        assert_eq!(side, &Side::A);
+        assert!(known.len() < copy.soldiers().len());
+        for soldier in copy.soldiers() {
+            let real = runner.battle_state().soldier(soldier.uuid());
+            if known.contains(&soldier.uuid()) {
+                assert_eq!(soldier.world_point(), real.world_point());
+            } else {
+                assert_eq!(soldier.world_point(), WorldPoint::new(0., 0.));
+                assert_eq!(soldier.behavior(), &Behavior::Idle(Body::StandUp));
+            }
+            if soldier.side() == &Side::B {
+                assert_eq!(soldier.order(), &Order::Idle);
             }
         }
+        assert_eq!(
+            runner.battle_state().side_copy(&Side::Observer),
+            runner.battle_state().copy()
+        );
+    }
+
+    #[rstest]
+    fn players_deploy_their_own_side(mut runner: Runner, deployment: Deployment) {
+        // Given
+        let player_a = Player::new(Side::A, PlayerName("Player1".to_string()));
+        let player_b = Player::new(Side::B, PlayerName("Player2".to_string()));
+        let north = MapControl::new(vec![SpawnZoneName::North]);
+        let south = MapControl::new(vec![SpawnZoneName::South]);
+
+        // When
+        let (_, outputs) = runner.authorized_inputs(vec![
+            InputMessage::FromPlayer(
+                player_a.clone(),
+                vec![
+                    InputMessage::LoadDeployment(deployment.clone()),
+                    InputMessage::LoadControl((north.clone(), south.clone())),
+                ],
+            ),
+            InputMessage::FromPlayer(
+                player_b.clone(),
+                vec![
+                    InputMessage::LoadDeployment(deployment.side(&Side::B)),
+                    InputMessage::LoadControl((MapControl::empty(), south.clone())),
+                ],
+            ),
+        ]);
+        runner.step(vec![
+            InputMessage::FromPlayer(
+                player_b,
+                vec![InputMessage::LoadDeployment(deployment.side(&Side::B))],
+            ),
+            InputMessage::FromPlayer(
+                player_a,
+                vec![
+                    InputMessage::LoadDeployment(deployment.side(&Side::A)),
+                    InputMessage::LoadControl((north.clone(), MapControl::empty())),
+                ],
+            ),
+        ]);
 
-        Ok(())
+        // Then
+        let rejections = rejections(&outputs);
+        assert_eq!(
+            rejections,
+            vec![&InputRejection::Side, &InputRejection::Side]
+        );
+        let soldiers = runner.battle_state().soldiers();
+        assert_eq!(soldiers.len(), 10);
+        assert!(soldiers
+            .iter()
+            .enumerate()
+            .all(|(i, soldier)| soldier.uuid() == SoldierIndex(i)));
+        assert_eq!(soldiers[0].side(), &Side::B);
+        assert_eq!(soldiers[5].side(), &Side::A);
+        assert_eq!(
+            runner.battle_state().zone_controls(&Side::A),
+            (&north, &MapControl::empty())
+        );
     }
 }
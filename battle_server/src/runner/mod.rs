@@ -1,14 +1,18 @@
 use battle_core::{
     config::ServerConfig,
+    game::Side,
     message::{InputMessage, OutputMessage},
+    replay::{ReplayError, ReplayWriter},
     state::battle::BattleState,
 };
 use crossbeam_channel::{Receiver, SendError, Sender};
+use rand::{rngs::StdRng, SeedableRng};
+use replication::Replication;
 use std::{
     fmt::Display,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex, MutexGuard,
     },
     thread,
     time::{Duration, Instant},
@@ -18,6 +22,7 @@ mod behavior;
 mod engage;
 mod fight;
 mod flag;
+mod fog;
 mod gesture;
 mod input;
 mod message;
@@ -27,10 +32,15 @@ mod output;
 mod phase;
 mod physics;
 mod react;
+pub mod record;
+mod replication;
 mod soldier;
+#[cfg(test)]
+pub mod test;
 mod tick;
 mod update;
 mod utils;
+mod validation;
 mod vehicle;
 mod victory;
 mod visibility;
@@ -39,38 +49,111 @@ const TARGET_CYCLE_DURATION_US: u64 = 16666;
 
 pub struct Runner {
     config: ServerConfig,
-    input: Receiver<Vec<InputMessage>>,
-    output: Sender<Vec<OutputMessage>>,
+    // Channels are absent when runner is driven by hand (see `Runner::headless`)
+    input: Option<Receiver<Vec<InputMessage>>>,
+    output: Option<Sender<Vec<(Side, OutputMessage)>>>,
     stop_required: Arc<AtomicBool>,
     last: Instant,
     battle_state: BattleState,
+    // All simulation randomness must come from this generator to be able to reproduce a battle
+    seed: u64,
+    rng: Mutex<StdRng>,
+    recorder: Option<ReplayWriter>,
+    replication: Replication,
 }
 
 impl Runner {
     pub fn new(
         config: ServerConfig,
         input: Receiver<Vec<InputMessage>>,
-        output: Sender<Vec<OutputMessage>>,
+        output: Sender<Vec<(Side, OutputMessage)>>,
         stop_required: Arc<AtomicBool>,
         state: BattleState,
     ) -> Self {
+        let seed = rand::random();
         Self {
             config,
-            input,
-            output,
+            input: Some(input),
+            output: Some(output),
             stop_required,
             last: Instant::now(),
             battle_state: state,
+            seed,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+            recorder: None,
+            replication: Replication::default(),
         }
     }
 
+    /// Build a runner without channels. It must be driven with `Runner::step`
+    /// or `Runner::advance` (scripted runs, tests, ...) instead of `Runner::run`.
+    pub fn headless(config: ServerConfig, state: BattleState) -> Self {
+        let seed = rand::random();
+        Self {
+            config,
+            input: None,
+            output: None,
+            stop_required: Arc::new(AtomicBool::new(false)),
+            last: Instant::now(),
+            battle_state: state,
+            seed,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+            recorder: None,
+            replication: Replication::default(),
+        }
+    }
+
+    pub fn battle_state(&self) -> &BattleState {
+        &self.battle_state
+    }
+
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restart random generator from given seed. Two runners with same seed, same
+    /// initial state and same inputs produce the same battle.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = Mutex::new(StdRng::seed_from_u64(seed));
+    }
+
+    /// Don't keep returned guard while calling other methods which could require it
+    pub fn rng(&self) -> MutexGuard<'_, StdRng> {
+        self.rng.lock().expect("Rng mutex must not be poisoned")
+    }
+
     pub fn run(&mut self) -> Result<(), RunnerError> {
+        self.run_while(|_| true)
+    }
+
+    /// Run until the battle ended since given delay (or stop is required)
+    pub fn run_match(&mut self, end_delay: Duration) -> Result<(), RunnerError> {
+        let mut ended_at = None;
+        self.run_while(|runner| {
+            if !runner.battle_state.phase().is_end() {
+                return true;
+            }
+
+            ended_at.get_or_insert_with(Instant::now).elapsed() < end_delay
+        })
+    }
+
+    fn run_while(&mut self, mut running: impl FnMut(&Runner) -> bool) -> Result<(), RunnerError> {
         loop {
             if self.stop_required.load(Ordering::Relaxed) {
                 println!("Stopping runner ...");
                 break;
             }
 
+            if !running(self) {
+                break;
+            }
+
             let frame_i = self.battle_state.frame_i();
             puffin::profile_scope!("run", format!("frame {frame_i}"));
             puffin::GlobalProfiler::lock().new_frame();
@@ -95,20 +178,30 @@ impl Runner {
 
 #[derive(Debug)]
 pub enum RunnerError {
+    Headless,
     InputChannelClosed,
-    Output(SendError<Vec<OutputMessage>>),
+    Replay(ReplayError),
+    Output(SendError<Vec<(Side, OutputMessage)>>),
 }
 
-impl From<SendError<Vec<OutputMessage>>> for RunnerError {
-    fn from(error: SendError<Vec<OutputMessage>>) -> Self {
+impl From<SendError<Vec<(Side, OutputMessage)>>> for RunnerError {
+    fn from(error: SendError<Vec<(Side, OutputMessage)>>) -> Self {
         Self::Output(error)
     }
 }
 
+impl From<ReplayError> for RunnerError {
+    fn from(error: ReplayError) -> Self {
+        Self::Replay(error)
+    }
+}
+
 impl Display for RunnerError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            RunnerError::Headless => f.write_str("Runner have no channels"),
             RunnerError::InputChannelClosed => f.write_str("Input channel closed"),
+            RunnerError::Replay(error) => f.write_str(&format!("Replay error : {}", error)),
             RunnerError::Output(error) => f.write_str(&format!("Output error : {}", error)),
         }
     }
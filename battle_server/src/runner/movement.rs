@@ -71,3 +71,48 @@ impl Runner {
         messages
     }
 }
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        deployment::Deployment,
+        message::InputMessage,
+        order::Order,
+        physics::utils::distance_between_points,
+        state::battle::message::{BattleStateMessage, SoldierMessage},
+        types::{SoldierIndex, WorldPath, WorldPaths, WorldPoint},
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        test::{deployment, runner},
+        Runner,
+    };
+
+    #[rstest]
+    fn move_order_is_executed(mut runner: Runner, deployment: Deployment) {
+        // Given
+        let leader = SoldierIndex(0);
+        let destination = WorldPoint::new(60., 100.);
+        let order = Order::MoveTo(
+            WorldPaths::new(vec![WorldPath::new(vec![destination])]),
+            None,
+        );
+        runner.start_battle(deployment);
+        runner.step(vec![InputMessage::BattleState(
+            BattleStateMessage::Soldier(leader, SoldierMessage::SetOrder(order)),
+        )]);
+        let start = runner.battle_state().soldier(leader).world_point();
+
+        // When
+        runner.advance(600);
+
+        // Then
+        let end = runner.battle_state().soldier(leader).world_point();
+        assert!(runner.battle_state().phase().is_battle());
+        assert!(
+            distance_between_points(&end, &destination).millimeters()
+                < distance_between_points(&start, &destination).millimeters()
+        );
+    }
+}
@@ -1,16 +1,42 @@
-use battle_core::{game::Side, message::OutputMessage};
+use std::cell::OnceCell;
+
+use battle_core::{game::Side, message::OutputMessage, state::battle::message::BattleStateMessage};
 
 use super::{message::RunnerMessage, Runner, RunnerError};
 
 impl Runner {
-    pub fn outputs(&self, messages: &Vec<RunnerMessage>) -> Result<(), RunnerError> {
+    pub fn outputs(&self, messages: &Vec<RunnerMessage>) -> Vec<(Side, OutputMessage)> {
         let mut outputs = vec![];
+        // Only computed if some messages need it
+        let known = OnceCell::new();
+        let known = || known.get_or_init(|| self.sides_known_soldiers());
 
         for message in messages {
             // WARNING : Be careful here. Here, messages are send to client for replication.
             // Change what is send or not will change gui battle state
             match message {
-                // TODO : We can filter message where content is really a change ?
+                // Sides must not know what they can't see
+                RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                    soldier_index,
+                    soldier_message,
+                )) => outputs.extend(self.soldier_outputs(soldier_index, soldier_message)),
+                RunnerMessage::BattleState(BattleStateMessage::SetVisibilities(visibilities)) => {
+                    outputs.extend(self.visibilities_outputs(visibilities))
+                }
+                RunnerMessage::BattleState(
+                    message @ BattleStateMessage::Vehicle(vehicle_index, _),
+                ) => outputs.extend(self.vehicle_outputs(vehicle_index, message, known())),
+                RunnerMessage::BattleState(message @ BattleStateMessage::PushBulletFire(fire)) => {
+                    outputs.extend(self.shot_outputs(fire.from(), message, known()))
+                }
+                RunnerMessage::BattleState(
+                    message @ BattleStateMessage::PushIndirectFire(fire),
+                ) => outputs.extend(self.shot_outputs(fire.from(), message, known())),
+                RunnerMessage::BattleState(
+                    message @ BattleStateMessage::PushCannonBlast(blast),
+                ) => outputs.extend(self.shot_outputs(blast.point(), message, known())),
+                // Explosions are seen and heard from far away and only reveal their impact point.
+                // Messages which change nothing are dropped later by replication
                 RunnerMessage::BattleState(message) => {
                     //
                     outputs.push((Side::All, OutputMessage::BattleState(message.clone())))
@@ -32,17 +58,80 @@ impl Runner {
             }
         }
 
-        self.send(outputs)?;
-
-        Ok(())
+        outputs
     }
 
-    fn send(&self, outputs: Vec<(Side, OutputMessage)>) -> Result<(), RunnerError> {
-        // TODO : send to correct side (for now, all is send to all)
-        let messages = outputs.iter().map(|o| o.1.clone()).collect();
-        match self.output.send(messages) {
+    pub fn send(&self, outputs: Vec<(Side, OutputMessage)>) -> Result<(), RunnerError> {
+        let output = self.output.as_ref().ok_or(RunnerError::Headless)?;
+
+        match output.send(outputs) {
             Ok(_) => Ok(()),
             Err(error) => Result::Err(RunnerError::Output(error)),
         }
     }
 }
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        deployment::Deployment,
+        game::{
+            weapon::{AmmunitionType, WeaponType},
+            Side,
+        },
+        message::OutputMessage,
+        physics::event::bullet::BulletFire,
+        state::battle::message::{BattleStateMessage, VehicleMessage},
+        types::{Angle, SoldierIndex, VehicleIndex, WorldPoint},
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        message::RunnerMessage,
+        test::{runner, tank_deployment},
+        Runner,
+    };
+
+    #[rstest]
+    fn vehicle_and_shots_are_only_sent_to_sides_knowing_them(
+        mut runner: Runner,
+        tank_deployment: Deployment,
+    ) {
+        // Given
+        runner.start_battle(tank_deployment);
+        runner.advance(60);
+        // Side B don't see the tank crew
+        assert!(!runner
+            .battle_state()
+            .soldiers_known_by_side(&Side::B)
+            .contains(&SoldierIndex(1)));
+        let gunner_point = runner.battle_state().soldier(SoldierIndex(1)).world_point();
+        let sides = |outputs: Vec<(Side, OutputMessage)>| {
+            outputs
+                .into_iter()
+                .map(|(side, _)| side)
+                .collect::<Vec<Side>>()
+        };
+
+        // When
+        let vehicle_outputs = runner.outputs(&vec![RunnerMessage::BattleState(
+            BattleStateMessage::Vehicle(
+                VehicleIndex(0),
+                VehicleMessage::SetMainTurretOrientation(Angle(1.)),
+            ),
+        )]);
+        let shot_outputs = runner.outputs(&vec![RunnerMessage::BattleState(
+            BattleStateMessage::PushBulletFire(BulletFire::new(
+                gunner_point,
+                WorldPoint::new(0., 0.),
+                None,
+                AmmunitionType("x45HE".to_string()),
+                WeaponType("M1932Gun45mm".to_string()),
+            )),
+        )]);
+
+        // Then
+        assert_eq!(sides(vehicle_outputs), vec![Side::Observer, Side::A]);
+        assert_eq!(sides(shot_outputs), vec![Side::Observer, Side::A]);
+    }
+}
@@ -40,7 +40,7 @@ impl Runner {
                 continue;
             }
 
-            // Simple for now, but if in vehicle, don't be affected
+            // Soldiers in vehicle are only affected through vehicle hit
             if self
                 .battle_state
                 .soldier_vehicle_place(soldier.uuid())
@@ -51,13 +51,13 @@ impl Runner {
 
             let distance = distance_between_points(&soldier.world_point(), point);
             if distance.meters() < 1
-                && SoldierCovered::new(self.battle_state.map(), bullet_fire, soldier).compute()
+                && SoldierCovered::new(self.battle_state.map(), bullet_fire, soldier)
+                    .compute(&mut *self.rng())
             {
                 messages.extend(self.covered_bullet_effects(soldier));
                 messages.extend(self.proximity_bullet_effects(soldier, &distance))
             } else if distance.millimeters() < 500 {
-                let mut rng = rand::thread_rng();
-                let value: u8 = rng.gen();
+                let value: u8 = self.rng().gen();
                 if value < 10 {
                     messages.extend(self.killing_bullet_effects(soldier))
                 } else if value < 50 {
@@ -70,6 +70,24 @@ impl Runner {
             }
         }
 
+        // Explosive rounds hit vehicles by their explosion
+        let ammunition = self
+            .battle_state
+            .catalog()
+            .ammunition(bullet_fire.ammunition());
+        if let Some(ammunition) = ammunition.filter(|ammunition| ammunition.explosive().is_none()) {
+            for vehicle in self.battle_state.vehicles() {
+                let definition = self.battle_state.vehicle_definition(vehicle);
+                if vehicle.chassis_shape(definition).contains(point) {
+                    messages.extend(self.vehicle_hit_effects(
+                        vehicle,
+                        bullet_fire.from(),
+                        ammunition.penetration(),
+                    ));
+                }
+            }
+        }
+
         messages
     }
 
@@ -97,7 +115,7 @@ impl Runner {
             messages.push(RunnerMessage::ClientsState(
                 ClientStateMessage::PlayBattleSound(
                     *pick_from
-                        .choose(&mut rand::thread_rng())
+                        .choose(&mut *self.rng())
                         .expect("Must one be chosen"),
                 ),
             ))
@@ -129,7 +147,7 @@ impl Runner {
             messages.push(RunnerMessage::ClientsState(
                 ClientStateMessage::PlayBattleSound(
                     *pick_from
-                        .choose(&mut rand::thread_rng())
+                        .choose(&mut *self.rng())
                         .expect("Must one be chosen"),
                 ),
             ))
@@ -150,7 +168,7 @@ impl Runner {
         vec![RunnerMessage::ClientsState(
             ClientStateMessage::PlayBattleSound(
                 *pick_from
-                    .choose(&mut rand::thread_rng())
+                    .choose(&mut *self.rng())
                     .expect("Must one be chosen"),
             ),
         )]
@@ -1,7 +1,5 @@
 use battle_core::audio::Sound;
 use battle_core::entity::soldier::Soldier;
-use battle_core::entity::vehicle::Vehicle;
-use battle_core::game::explosive::ExplosiveType;
 use battle_core::physics::event::explosion::Explosion;
 use battle_core::physics::utils::distance_between_points;
 use battle_core::state::client::ClientStateMessage;
@@ -42,7 +40,7 @@ impl Runner {
                 continue;
             }
 
-            // Simple for now, but if in vehicle, don't be affected
+            // Soldiers in vehicle are only affected through vehicle hit
             if self
                 .battle_state
                 .soldier_vehicle_place(soldier.uuid())
@@ -74,11 +72,10 @@ impl Runner {
                 } else if &distance <= regressive_death_rayon
                     || &distance <= regressive_injured_rayon
                 {
-                    let mut rng = rand::thread_rng();
                     let percent = 1.0
                         - (distance.millimeters() as f32
                             / regressive_death_rayon.millimeters() as f32);
-                    let roll = rng.gen_range(0.0..1.0);
+                    let roll = self.rng().gen_range(0.0..1.0);
 
                     if roll <= percent {
                         messages.extend(self.killing_blast_effects(soldier));
@@ -86,7 +83,7 @@ impl Runner {
                         let percent = 1.0
                             - (distance.millimeters() as f32
                                 / regressive_injured_rayon.millimeters() as f32);
-                        let roll = rng.gen_range(0.0..1.0);
+                        let roll = self.rng().gen_range(0.0..1.0);
 
                         if roll <= percent {
                             messages.extend(self.stunning_blast_effects(soldier));
@@ -101,8 +98,13 @@ impl Runner {
         }
 
         for vehicle in self.battle_state.vehicles() {
-            if vehicle.chassis_shape().contains(point) {
-                messages.extend(self.vehicle_shell_impact_effects(vehicle, explosive_type));
+            let definition = self.battle_state.vehicle_definition(vehicle);
+            if vehicle.chassis_shape(definition).contains(point) {
+                messages.extend(self.vehicle_hit_effects(
+                    vehicle,
+                    point,
+                    explosive_type.armor_penetration(),
+                ));
             }
         }
 
@@ -132,7 +134,7 @@ impl Runner {
             messages.push(RunnerMessage::ClientsState(
                 ClientStateMessage::PlayBattleSound(
                     *pick_from
-                        .choose(&mut rand::thread_rng())
+                        .choose(&mut *self.rng())
                         .expect("Must one be chosen"),
                 ),
             ))
@@ -150,21 +152,4 @@ impl Runner {
         puffin::profile_scope!("proximity_blast_effects", soldier.uuid().to_string());
         self.soldier_blast(soldier.uuid(), distance)
     }
-
-    fn vehicle_shell_impact_effects(
-        &self,
-        vehicle: &Vehicle,
-        _explosive: &ExplosiveType,
-    ) -> Vec<RunnerMessage> {
-        puffin::profile_scope!("vehicle_shell_impact_effects", vehicle.uuid().to_string());
-        // TODO effects on soldiers (with a real explosive algorithm)
-        let pick_from = vec![Sound::MetalHit1];
-        vec![RunnerMessage::ClientsState(
-            ClientStateMessage::PlayBattleSound(
-                *pick_from
-                    .choose(&mut rand::thread_rng())
-                    .expect("Must one be chosen"),
-            ),
-        )]
-    }
 }
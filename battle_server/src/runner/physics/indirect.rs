@@ -0,0 +1,26 @@
+use battle_core::{
+    physics::event::explosion::Explosion, state::battle::message::BattleStateMessage,
+};
+
+use crate::runner::{message::RunnerMessage, Runner};
+
+impl Runner {
+    /// Indirect fire rounds explode where they hit the ground
+    pub fn tick_indirect_fires(&self) -> Vec<RunnerMessage> {
+        puffin::profile_scope!("tick_indirect_fires");
+        let mut messages = vec![];
+
+        for indirect_fire in self.battle_state.indirect_fires() {
+            if indirect_fire.impact(*self.battle_state.frame_i()) {
+                messages.push(RunnerMessage::BattleState(
+                    BattleStateMessage::PushExplosion(Explosion::new(
+                        *indirect_fire.to(),
+                        indirect_fire.explosive().clone(),
+                    )),
+                ));
+            }
+        }
+
+        messages
+    }
+}
@@ -2,6 +2,8 @@ use super::{message::RunnerMessage, Runner};
 
 mod bullet;
 mod explosion;
+mod indirect;
+mod vehicle;
 
 impl Runner {
     pub fn tick_physics(&mut self) -> Vec<RunnerMessage> {
@@ -11,6 +13,7 @@ impl Runner {
         if self.battle_state.frame_i() % self.config.physics_update_freq() == 0 {
             messages.extend(self.tick_bullet_fires());
             messages.extend(self.tick_explosions());
+            messages.extend(self.tick_indirect_fires());
         }
 
         messages
@@ -0,0 +1,134 @@
+use battle_core::{
+    audio::Sound,
+    config::{
+        VEHICLE_KNOCKED_OUT_BURNING_CHANCE, VEHICLE_PENETRATION_COMPONENT_DAMAGE_CHANCE,
+        VEHICLE_PENETRATION_CREW_KILL_CHANCE, VEHICLE_PENETRATION_KNOCK_OUT_CHANCE,
+    },
+    entity::vehicle::{Vehicle, VehicleDamage},
+    state::{
+        battle::message::{BattleStateMessage, VehicleMessage},
+        client::ClientStateMessage,
+    },
+    types::WorldPoint,
+};
+use rand::Rng;
+
+use crate::runner::{message::RunnerMessage, Runner};
+
+impl Runner {
+    /// Effects of a round (or explosion) coming from given point and piercing given armor
+    /// thickness (millimeters) on the vehicle
+    pub fn vehicle_hit_effects(
+        &self,
+        vehicle: &Vehicle,
+        from: &WorldPoint,
+        penetration: f32,
+    ) -> Vec<RunnerMessage> {
+        puffin::profile_scope!("vehicle_hit_effects", vehicle.uuid().to_string());
+        let mut messages = vec![RunnerMessage::ClientsState(
+            ClientStateMessage::PlayBattleSound(Sound::MetalHit1),
+        )];
+
+        let armor = self
+            .battle_state
+            .vehicle_definition(vehicle)
+            .armor()
+            .facing(&vehicle.facing(from));
+        if penetration >= armor {
+            messages.extend(self.vehicle_penetration_effects(vehicle));
+        }
+
+        messages
+    }
+
+    fn vehicle_penetration_effects(&self, vehicle: &Vehicle) -> Vec<RunnerMessage> {
+        puffin::profile_scope!("vehicle_penetration_effects", vehicle.uuid().to_string());
+        let mut messages = vec![];
+        let mut damages = vec![];
+        let turret_hit = self.rng().gen_bool(0.5);
+
+        if self.rng().gen_range(0.0..1.0) < VEHICLE_PENETRATION_COMPONENT_DAMAGE_CHANCE {
+            damages.push(if turret_hit {
+                VehicleDamage::TurretJammed
+            } else {
+                VehicleDamage::Immobilized
+            });
+        }
+
+        let mut burning = false;
+        if self.rng().gen_range(0.0..1.0) < VEHICLE_PENETRATION_KNOCK_OUT_CHANCE {
+            damages.push(VehicleDamage::KnockedOut);
+            if self.rng().gen_range(0.0..1.0) < VEHICLE_KNOCKED_OUT_BURNING_CHANCE {
+                damages.push(VehicleDamage::Burning);
+                burning = true;
+            }
+        }
+
+        // Crew of the hit part (or whole crew if the vehicle burn) is killed or wounded
+        let board = self
+            .battle_state
+            .vehicle_board()
+            .get(vehicle.uuid())
+            .cloned()
+            .unwrap_or_default();
+        for (place, soldier_index) in board {
+            let soldier = self.battle_state.soldier(soldier_index);
+            if !soldier.alive() || (!burning && place.in_turret() != turret_hit) {
+                continue;
+            }
+
+            if burning || self.rng().gen_range(0.0..1.0) < VEHICLE_PENETRATION_CREW_KILL_CHANCE {
+                messages.extend(self.killing_bullet_effects(soldier));
+            } else {
+                messages.extend(self.soldier_blast_stunned(soldier_index));
+            }
+        }
+
+        messages.extend(damages.into_iter().map(|damage| {
+            RunnerMessage::BattleState(BattleStateMessage::Vehicle(
+                *vehicle.uuid(),
+                VehicleMessage::AddDamage(damage),
+            ))
+        }));
+
+        messages
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{deployment::Deployment, message::InputMessage, types::VehicleIndex};
+    use rstest::*;
+
+    use crate::runner::{
+        message::RunnerMessage,
+        test::{runner, tank_deployment},
+        Runner,
+    };
+
+    #[rstest]
+    #[case(glam::Vec2::new(0., -100.), 12., false)]
+    #[case(glam::Vec2::new(0., 100.), 12., true)]
+    #[case(glam::Vec2::new(0., -100.), 35., true)]
+    fn vehicle_armor_stop_or_is_pierced(
+        mut runner: Runner,
+        tank_deployment: Deployment,
+        #[case] from: glam::Vec2,
+        #[case] penetration: f32,
+        #[case] expect_pierced: bool,
+    ) {
+        // Given
+        runner.step(vec![InputMessage::LoadDeployment(tank_deployment)]);
+        let vehicle = runner.battle_state().vehicle(VehicleIndex(0));
+        let from = vehicle.world_point().apply(from);
+
+        // When
+        let messages = runner.vehicle_hit_effects(vehicle, &from, penetration);
+
+        // Then
+        let pierced = messages
+            .iter()
+            .any(|message| matches!(message, RunnerMessage::BattleState(_)));
+        assert_eq!(pierced, expect_pierced);
+    }
+}
@@ -0,0 +1,123 @@
+use std::{path::Path, sync::Mutex};
+
+use battle_core::{
+    config::ServerConfig,
+    message::InputMessage,
+    replay::{ReplayFrame, ReplayHeader, ReplayWriter},
+    state::battle::BattleState,
+    sync::BattleStateCopy,
+};
+use rand::rngs::StdRng;
+
+use super::{Runner, RunnerError};
+
+/// Runner state at frame start, used to restart a replay from a given frame
+#[derive(Clone)]
+pub struct RunnerSnapshot {
+    copy: BattleStateCopy,
+    config: ServerConfig,
+    rng: StdRng,
+}
+
+impl RunnerSnapshot {
+    pub fn frame_i(&self) -> u64 {
+        self.copy.frame_i()
+    }
+}
+
+impl Runner {
+    /// Record all next received inputs into given replay file
+    pub fn record(&mut self, destination: &Path) -> Result<(), RunnerError> {
+        let header = ReplayHeader::new(
+            self.battle_state.map().name().to_string(),
+            self.seed,
+            self.config.clone(),
+            self.battle_state.catalog().clone(),
+            self.battle_state.copy(),
+        );
+        self.recorder = Some(ReplayWriter::new(destination, &header)?);
+        Ok(())
+    }
+
+    pub fn record_inputs(&mut self, inputs: &[InputMessage]) {
+        // Save requests don't change the battle
+        let inputs: Vec<InputMessage> = inputs
+            .iter()
+            .filter(|input| !matches!(input, InputMessage::RequestSave(_)))
+            .cloned()
+            .collect();
+        if inputs.is_empty() {
+            return;
+        }
+
+        if let Some(recorder) = self.recorder.as_mut() {
+            let frame = ReplayFrame::new(*self.battle_state.frame_i(), inputs);
+            if let Err(error) = recorder.write(&frame) {
+                log::error!("Error while recording inputs : {}", error);
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> RunnerSnapshot {
+        RunnerSnapshot {
+            copy: self.battle_state.copy(),
+            config: self.config.clone(),
+            rng: self.rng().clone(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &RunnerSnapshot) {
+        self.battle_state = BattleState::from_copy(
+            &snapshot.copy,
+            self.battle_state.map(),
+            self.battle_state.catalog(),
+        );
+        self.battle_state.resolve();
+        self.config = snapshot.config.clone();
+        self.rng = Mutex::new(snapshot.rng.clone());
+        self.replication.reset();
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        deployment::Deployment,
+        message::InputMessage,
+        order::Order,
+        state::battle::message::{BattleStateMessage, SoldierMessage},
+        types::{SoldierIndex, WorldPoint},
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        test::{deployment, runner},
+        Runner,
+    };
+
+    #[rstest]
+    fn restored_battle_continue_identically(mut runner: Runner, deployment: Deployment) {
+        // Given
+        let order = Order::SuppressFire(WorldPoint::new(550., 270.));
+        runner.step(vec![
+            InputMessage::LoadDeployment(deployment),
+            InputMessage::BattleState(BattleStateMessage::SetAReady(true)),
+            InputMessage::BattleState(BattleStateMessage::SetBReady(true)),
+            InputMessage::BattleState(BattleStateMessage::Soldier(
+                SoldierIndex(0),
+                SoldierMessage::SetOrder(order),
+            )),
+        ]);
+        runner.advance(300);
+        let snapshot = runner.snapshot();
+        runner.advance(300);
+        let expected = runner.battle_state().copy();
+
+        // When
+        runner.restore(&snapshot);
+        runner.advance(300);
+
+        // Then
+        assert_eq!(runner.battle_state().copy(), expected);
+    }
+}
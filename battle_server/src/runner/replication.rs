@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+
+use battle_core::{
+    game::Side,
+    message::OutputMessage,
+    physics::visibility::Visibility,
+    state::battle::message::{BattleStateMessage, SoldierMessage, VehicleMessage},
+    types::{Angle, SoldierIndex, VehicleIndex, WorldPoint},
+};
+
+use super::Runner;
+
+/// Replicated positions precision (world pixels)
+pub const POSITION_QUANTUM: f32 = 0.25;
+/// Replicated orientations precision (radians)
+pub const ORIENTATION_QUANTUM: f32 = 0.01;
+
+type VisibilitiesMap = HashMap<(SoldierIndex, SoldierIndex), Visibility>;
+
+/// Remember, by side, what clients received to only send them what changed.
+/// Must be reset each time clients receive a complete state.
+#[derive(Default)]
+pub struct Replication {
+    soldiers_positions: HashMap<(Side, SoldierIndex), WorldPoint>,
+    soldiers_orientations: HashMap<(Side, SoldierIndex), Angle>,
+    vehicles_positions: HashMap<(Side, VehicleIndex), WorldPoint>,
+    vehicles_orientations: HashMap<(Side, VehicleIndex), Angle>,
+    vehicles_turrets_orientations: HashMap<(Side, VehicleIndex), Angle>,
+    visibilities: HashMap<Side, VisibilitiesMap>,
+}
+
+impl Replication {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Given side received a complete state (`Side::All` meaning all sides did)
+    pub fn reset_side(&mut self, side: &Side) {
+        if side == &Side::All {
+            return self.reset();
+        }
+
+        self.soldiers_positions
+            .retain(|(side_, _), _| side_ != side);
+        self.soldiers_orientations
+            .retain(|(side_, _), _| side_ != side);
+        self.vehicles_positions
+            .retain(|(side_, _), _| side_ != side);
+        self.vehicles_orientations
+            .retain(|(side_, _), _| side_ != side);
+        self.vehicles_turrets_orientations
+            .retain(|(side_, _), _| side_ != side);
+        self.visibilities.remove(side);
+    }
+
+    /// Client changed this soldier by itself (ex. placement), its last values are unknown
+    pub fn forget_soldier(&mut self, soldier_index: &SoldierIndex) {
+        self.soldiers_positions
+            .retain(|(_, soldier_index_), _| soldier_index_ != soldier_index);
+        self.soldiers_orientations
+            .retain(|(_, soldier_index_), _| soldier_index_ != soldier_index);
+    }
+
+    /// Quantize positions and orientations, drop ones which don't change what side clients
+    /// already have and replace visibilities by their changes.
+    pub fn replicate(&mut self, outputs: Vec<(Side, OutputMessage)>) -> Vec<(Side, OutputMessage)> {
+        let mut replicated = vec![];
+
+        for (side, output) in outputs {
+            let output = match output {
+                OutputMessage::LoadFromCopy(_) => {
+                    self.reset_side(&side);
+                    Some(output)
+                }
+                OutputMessage::BattleState(message) => self
+                    .battle_state_message(&side, message)
+                    .map(OutputMessage::BattleState),
+                _ => Some(output),
+            };
+
+            if let Some(output) = output {
+                replicated.push((side, output));
+            }
+        }
+
+        replicated
+    }
+
+    fn battle_state_message(
+        &mut self,
+        side: &Side,
+        message: BattleStateMessage,
+    ) -> Option<BattleStateMessage> {
+        match message {
+            BattleStateMessage::Soldier(soldier_index, SoldierMessage::SetWorldPosition(point)) => {
+                changed(
+                    &mut self.soldiers_positions,
+                    (*side, soldier_index),
+                    quantized_point(point),
+                )
+                .map(|point| {
+                    BattleStateMessage::Soldier(
+                        soldier_index,
+                        SoldierMessage::SetWorldPosition(point),
+                    )
+                })
+            }
+            BattleStateMessage::Soldier(soldier_index, SoldierMessage::SetOrientation(angle)) => {
+                changed(
+                    &mut self.soldiers_orientations,
+                    (*side, soldier_index),
+                    quantized_angle(angle),
+                )
+                .map(|angle| {
+                    BattleStateMessage::Soldier(
+                        soldier_index,
+                        SoldierMessage::SetOrientation(angle),
+                    )
+                })
+            }
+            BattleStateMessage::Vehicle(vehicle_index, VehicleMessage::SetWorldPosition(point)) => {
+                changed(
+                    &mut self.vehicles_positions,
+                    (*side, vehicle_index),
+                    quantized_point(point),
+                )
+                .map(|point| {
+                    BattleStateMessage::Vehicle(
+                        vehicle_index,
+                        VehicleMessage::SetWorldPosition(point),
+                    )
+                })
+            }
+            BattleStateMessage::Vehicle(
+                vehicle_index,
+                VehicleMessage::SetChassisOrientation(angle),
+            ) => changed(
+                &mut self.vehicles_orientations,
+                (*side, vehicle_index),
+                quantized_angle(angle),
+            )
+            .map(|angle| {
+                BattleStateMessage::Vehicle(
+                    vehicle_index,
+                    VehicleMessage::SetChassisOrientation(angle),
+                )
+            }),
+            BattleStateMessage::Vehicle(
+                vehicle_index,
+                VehicleMessage::SetMainTurretOrientation(angle),
+            ) => changed(
+                &mut self.vehicles_turrets_orientations,
+                (*side, vehicle_index),
+                quantized_angle(angle),
+            )
+            .map(|angle| {
+                BattleStateMessage::Vehicle(
+                    vehicle_index,
+                    VehicleMessage::SetMainTurretOrientation(angle),
+                )
+            }),
+            BattleStateMessage::SetVisibilities(visibilities) => {
+                self.visibilities_message(side, visibilities)
+            }
+            _ => Some(message),
+        }
+    }
+
+    fn visibilities_message(
+        &mut self,
+        side: &Side,
+        visibilities: VisibilitiesMap,
+    ) -> Option<BattleStateMessage> {
+        // Clients visibilities are unknown, they must be replaced
+        let Some(previous) = self.visibilities.get(side) else {
+            self.visibilities.insert(*side, visibilities.clone());
+            return Some(BattleStateMessage::SetVisibilities(visibilities));
+        };
+
+        let changes: VisibilitiesMap = visibilities
+            .iter()
+            .filter(|(key, visibility)| previous.get(key) != Some(visibility))
+            .map(|(key, visibility)| (*key, visibility.clone()))
+            .collect();
+        let removed: Vec<(SoldierIndex, SoldierIndex)> = previous
+            .keys()
+            .filter(|key| !visibilities.contains_key(key))
+            .cloned()
+            .collect();
+        self.visibilities.insert(*side, visibilities);
+
+        if changes.is_empty() && removed.is_empty() {
+            return None;
+        }
+
+        Some(BattleStateMessage::UpdateVisibilities(changes, removed))
+    }
+}
+
+/// Return the new value if it is not the last replicated one
+fn changed<K: std::hash::Hash + Eq, V: PartialEq + Copy>(
+    last: &mut HashMap<K, V>,
+    key: K,
+    value: V,
+) -> Option<V> {
+    if last.get(&key) == Some(&value) {
+        return None;
+    }
+
+    last.insert(key, value);
+    Some(value)
+}
+
+fn quantized(value: f32, quantum: f32) -> f32 {
+    (value / quantum).round() * quantum
+}
+
+fn quantized_point(point: WorldPoint) -> WorldPoint {
+    WorldPoint::new(
+        quantized(point.x, POSITION_QUANTUM),
+        quantized(point.y, POSITION_QUANTUM),
+    )
+}
+
+fn quantized_angle(angle: Angle) -> Angle {
+    Angle(quantized(angle.0, ORIENTATION_QUANTUM))
+}
+
+impl Runner {
+    /// Clients will receive complete state by another way than runner outputs (ex. replay seek)
+    pub fn reset_replication(&mut self) {
+        self.replication.reset();
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::types::Distance;
+    use rstest::*;
+
+    use super::*;
+
+    fn position(soldier_index: usize, x: f32, y: f32) -> (Side, OutputMessage) {
+        (
+            Side::A,
+            OutputMessage::BattleState(BattleStateMessage::Soldier(
+                SoldierIndex(soldier_index),
+                SoldierMessage::SetWorldPosition(WorldPoint::new(x, y)),
+            )),
+        )
+    }
+
+    #[rstest]
+    fn unchanged_positions_are_dropped() {
+        // Given
+        let mut replication = Replication::default();
+        replication.replicate(vec![position(0, 10., 10.), position(1, 10., 10.)]);
+
+        // When
+        let outputs = replication.replicate(vec![position(0, 10.01, 10.), position(1, 11., 10.)]);
+
+        // Then
+        assert_eq!(outputs.len(), 1);
+        assert!(matches!(
+            &outputs[0].1,
+            OutputMessage::BattleState(BattleStateMessage::Soldier(SoldierIndex(1), _))
+        ));
+    }
+
+    #[rstest]
+    fn only_visibilities_changes_are_sent() {
+        // Given
+        let visibility = Visibility {
+            from: WorldPoint::new(0., 0.),
+            from_soldier: Some(SoldierIndex(0)),
+            to: WorldPoint::new(10., 10.),
+            to_soldier: Some(SoldierIndex(1)),
+            path_final_opacity: 0.,
+            to_scene_item_opacity: 0.,
+            opacity_segments: vec![],
+            visible: true,
+            distance: Distance::from_meters(1),
+            break_point: None,
+        };
+        let mut replication = Replication::default();
+        let visibilities1 = HashMap::from([
+            ((SoldierIndex(0), SoldierIndex(1)), visibility.clone()),
+            ((SoldierIndex(0), SoldierIndex(2)), visibility.clone()),
+        ]);
+        let mut visibilities2 = visibilities1.clone();
+        visibilities2.remove(&(SoldierIndex(0), SoldierIndex(2)));
+        let message = |visibilities| {
+            (
+                Side::A,
+                OutputMessage::BattleState(BattleStateMessage::SetVisibilities(visibilities)),
+            )
+        };
+
+        // When
+        let first = replication.replicate(vec![message(visibilities1)]);
+        let second = replication.replicate(vec![message(visibilities2.clone())]);
+        let third = replication.replicate(vec![message(visibilities2)]);
+
+        // Then
+        assert!(matches!(
+            &first[0].1,
+            OutputMessage::BattleState(BattleStateMessage::SetVisibilities(v)) if v.len() == 2
+        ));
+        assert!(matches!(
+            &second[0].1,
+            OutputMessage::BattleState(BattleStateMessage::UpdateVisibilities(c, r))
+                if c.is_empty() && r == &vec![(SoldierIndex(0), SoldierIndex(2))]
+        ));
+        assert!(third.is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+//! Fixtures and helpers shared by runner modules tests
+use std::path::PathBuf;
+
+use battle_core::{
+    config::ServerConfig,
+    deployment::{Deployment, DeploymentReader, SoldierDeployment, VehicleDeployment},
+    entity::vehicle::{OnBoardPlace, VehicleType},
+    game::{
+        weapon::{Magazine, MagazineType, Weapon, WeaponType},
+        Side,
+    },
+    message::{rejection::InputRejection, InputMessage, OutputMessage},
+    state::battle::{
+        builder::BattleStateBuilder,
+        message::{BattleStateMessage, SoldierMessage},
+    },
+    types::{SoldierIndex, SoldiersOnBoard, VehicleIndex},
+};
+use rstest::*;
+
+use super::{message::RunnerMessage, Runner};
+
+#[fixture]
+pub fn deployment() -> Deployment {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../assets/map1_deployment.json");
+    DeploymentReader::from_file(&path).unwrap()
+}
+
+/// Soldier 0 is the gunner of an unloaded crew served weapon, soldier 1 (next to him) carry
+/// its belt
+#[fixture]
+pub fn crew_deployment(deployment: Deployment) -> Deployment {
+    let soldiers = deployment
+        .soldiers()
+        .iter()
+        .map(|soldier| {
+            let (main_weapon, magazines) = match soldier.uuid() {
+                SoldierIndex(0) => (
+                    Some(Weapon::new(
+                        WeaponType("MaximM1910".to_string()),
+                        false,
+                        None,
+                    )),
+                    vec![],
+                ),
+                SoldierIndex(1) => (
+                    soldier.main_weapon().cloned(),
+                    vec![Magazine::new(MagazineType("MaximBelt".to_string()), 250)],
+                ),
+                _ => (soldier.main_weapon().cloned(), soldier.magazines().to_vec()),
+            };
+            SoldierDeployment::new(
+                soldier.uuid(),
+                soldier.side(),
+                soldier.world_point(),
+                soldier.squad_uuid(),
+                main_weapon,
+                magazines,
+            )
+        })
+        .collect();
+    Deployment::from((
+        soldiers,
+        deployment.vehicles().to_vec(),
+        deployment.boards().clone(),
+    ))
+}
+
+/// Soldier 0 drive a T-26 tank of which soldier 1 is the gunner (armed with the tank main gun),
+/// with HE and AP shells
+#[fixture]
+pub fn tank_deployment(deployment: Deployment) -> Deployment {
+    let soldiers = deployment
+        .soldiers()
+        .iter()
+        .map(|soldier| {
+            let (main_weapon, magazines) = match soldier.uuid() {
+                SoldierIndex(0) => (None, vec![]),
+                SoldierIndex(1) => (
+                    None,
+                    vec![
+                        Magazine::new(MagazineType("x45HE".to_string()), 1),
+                        Magazine::new(MagazineType("x45AP".to_string()), 1),
+                    ],
+                ),
+                _ => (soldier.main_weapon().cloned(), soldier.magazines().to_vec()),
+            };
+            SoldierDeployment::new(
+                soldier.uuid(),
+                soldier.side(),
+                soldier.world_point(),
+                soldier.squad_uuid(),
+                main_weapon,
+                magazines,
+            )
+        })
+        .collect();
+    let vehicles = vec![VehicleDeployment::new(
+        VehicleIndex(0),
+        VehicleType("T26".to_string()),
+        deployment.soldiers()[0].world_point(),
+    )];
+    let boards: SoldiersOnBoard = [
+        (SoldierIndex(0), (VehicleIndex(0), OnBoardPlace::Driver)),
+        (
+            SoldierIndex(1),
+            (VehicleIndex(0), OnBoardPlace::MainTurretGunner),
+        ),
+    ]
+    .into();
+    Deployment::from((soldiers, vehicles, boards))
+}
+
+#[fixture]
+pub fn runner() -> Runner {
+    let resources = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources");
+    let battle_state = BattleStateBuilder::new("map1", resources).build().unwrap();
+    Runner::headless(ServerConfig::default(), battle_state)
+}
+
+pub fn soldier(soldier_index: usize, message: SoldierMessage) -> InputMessage {
+    InputMessage::BattleState(BattleStateMessage::Soldier(
+        SoldierIndex(soldier_index),
+        message,
+    ))
+}
+
+/// Rejections of given outputs (which must all be rejections)
+pub fn rejections(outputs: &[(Side, OutputMessage)]) -> Vec<&InputRejection> {
+    outputs
+        .iter()
+        .map(|(_, output)| match output {
+            OutputMessage::InputRejected(_, rejection) => rejection,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+impl Runner {
+    /// Load given deployment and set both sides ready, so battle begins at next frame
+    pub fn start_battle(&mut self, deployment: Deployment) -> Vec<OutputMessage> {
+        self.step(vec![
+            InputMessage::LoadDeployment(deployment),
+            InputMessage::BattleState(BattleStateMessage::SetAReady(true)),
+            InputMessage::BattleState(BattleStateMessage::SetBReady(true)),
+        ])
+    }
+
+    /// Step one frame with battle state messages produced by the runner (ex. by a gesture), as
+    /// a running server would apply them
+    pub fn step_messages(&mut self, messages: Vec<RunnerMessage>) -> Vec<OutputMessage> {
+        self.step(
+            messages
+                .into_iter()
+                .filter_map(|message| match message {
+                    RunnerMessage::BattleState(message) => Some(InputMessage::BattleState(message)),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+
+    /// Step frames with messages produced by given function until it produce no more battle
+    /// state messages, or until `max_frames` frames
+    pub fn step_until<F>(&mut self, max_frames: usize, messages: F)
+    where
+        F: Fn(&Runner) -> Vec<RunnerMessage>,
+    {
+        for _ in 0..max_frames {
+            let messages = messages(self);
+            if !messages
+                .iter()
+                .any(|message| matches!(message, RunnerMessage::BattleState(_)))
+            {
+                break;
+            }
+            self.step_messages(messages);
+        }
+    }
+}
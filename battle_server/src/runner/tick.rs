@@ -1,4 +1,8 @@
-use battle_core::state::battle::message::BattleStateMessage;
+use battle_core::{
+    game::Side,
+    message::{InputMessage, OutputMessage},
+    state::battle::message::BattleStateMessage,
+};
 
 use crate::runner::message::RunnerMessage;
 
@@ -6,9 +10,30 @@ use super::{Runner, RunnerError};
 
 impl Runner {
     pub fn tick(&mut self) -> Result<(), RunnerError> {
+        let inputs = self.receive_inputs()?;
+        let outputs = self.frame(inputs);
+        self.send(outputs)?;
+        Ok(())
+    }
+
+    /// Apply given inputs then compute one frame, without any sleep or channel.
+    /// Return output messages which would be sent to clients.
+    pub fn step(&mut self, inputs: Vec<InputMessage>) -> Vec<OutputMessage> {
+        self.frame(inputs).into_iter().map(|o| o.1).collect()
+    }
+
+    /// Compute given frames count (without inputs). Return all produced output messages.
+    pub fn advance(&mut self, frames: u64) -> Vec<OutputMessage> {
+        (0..frames).flat_map(|_| self.step(vec![])).collect()
+    }
+
+    /// Like `step`, but keep the side each output message is addressed to
+    pub fn frame(&mut self, inputs: Vec<InputMessage>) -> Vec<(Side, OutputMessage)> {
         let frame_i = self.battle_state.frame_i();
         puffin::profile_scope!("tick", format!("frame {frame_i}"));
-        self.inputs()?;
+        let (inputs, mut outputs) = self.authorized_inputs(inputs);
+        self.record_inputs(&inputs);
+        outputs.extend(self.inputs(inputs));
 
         let mut messages = vec![RunnerMessage::BattleState(
             BattleStateMessage::IncrementFrameI,
@@ -22,14 +47,89 @@ impl Runner {
         messages.extend(self.tick_feeling_decreasing_soldiers());
         messages.extend(self.tick_visibilities());
         messages.extend(self.tick_physics());
+        let visibles_before = self
+            .visibilities_changes(&messages)
+            .then(|| self.sides_visible_soldiers());
         self.react(&messages);
         self.clean();
 
-        self.outputs(&messages)?;
-        Ok(())
+        outputs.extend(self.outputs(&messages));
+        if let Some(visibles_before) = visibles_before {
+            outputs.extend(self.revealed_outputs(&visibles_before));
+        }
+        self.replication.replicate(outputs)
+    }
+
+    fn visibilities_changes(&self, messages: &[RunnerMessage]) -> bool {
+        messages.iter().any(|message| {
+            matches!(
+                message,
+                RunnerMessage::BattleState(BattleStateMessage::SetVisibilities(_))
+            )
+        })
     }
 
     pub fn clean(&mut self) {
         self.battle_state.clean(None);
     }
 }
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        deployment::Deployment,
+        message::InputMessage,
+        order::Order,
+        state::battle::message::{BattleStateMessage, SoldierMessage},
+        types::{SoldierIndex, WorldPoint},
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        test::{deployment, runner},
+        Runner, RunnerError,
+    };
+
+    #[rstest]
+    fn step_without_channels(mut runner: Runner, deployment: Deployment) {
+        // Given
+        runner.step(vec![InputMessage::LoadDeployment(deployment)]);
+
+        // When
+        runner.advance(9);
+
+        // Then
+        assert_eq!(runner.battle_state().frame_i(), &10);
+        assert_eq!(runner.battle_state().soldiers().len(), 10);
+        assert!(runner.battle_state().phase().is_placement());
+        assert!(matches!(runner.run(), Err(RunnerError::Headless)));
+    }
+
+    #[rstest]
+    fn same_seed_same_battle(deployment: Deployment) {
+        // Given
+        let order = Order::SuppressFire(WorldPoint::new(550., 270.));
+        let inputs = vec![
+            InputMessage::LoadDeployment(deployment),
+            InputMessage::BattleState(BattleStateMessage::SetAReady(true)),
+            InputMessage::BattleState(BattleStateMessage::SetBReady(true)),
+            InputMessage::BattleState(BattleStateMessage::Soldier(
+                SoldierIndex(0),
+                SoldierMessage::SetOrder(order),
+            )),
+        ];
+        let mut runner1 = runner();
+        let mut runner2 = runner();
+        runner1.set_seed(42);
+        runner2.set_seed(42);
+
+        // When
+        runner1.step(inputs.clone());
+        runner2.step(inputs);
+        runner1.advance(600);
+        runner2.advance(600);
+
+        // Then
+        assert_eq!(runner1.battle_state().copy(), runner2.battle_state().copy());
+    }
+}
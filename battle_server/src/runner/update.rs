@@ -49,14 +49,24 @@ impl Runner {
             Behavior::Hide(_) => {
                 vec![]
             }
-            Behavior::DriveTo(paths) => self.drive_update(soldier_index, paths),
+            Behavior::DriveTo(paths) => self.drive_update(soldier_index, paths, false),
+            Behavior::ReverseTo(paths) => self.drive_update(soldier_index, paths, true),
             Behavior::RotateTo(angle) => self.rotate_update(soldier_index, angle),
-            Behavior::SuppressFire(_) => {
-                vec![]
-            }
-            Behavior::EngageSoldier(target) => self.engage_update(&soldier_index, target),
+            Behavior::SuppressFire(point) => self.turret_update(soldier_index, point),
+            Behavior::EngageSoldier(target) => [
+                self.engage_update(&soldier_index, target),
+                self.turret_update(
+                    soldier_index,
+                    &self.battle_state.soldier(*target).world_point(),
+                ),
+            ]
+            .concat(),
             Behavior::Dead => vec![],
             Behavior::Unconscious => vec![],
+            Behavior::TearDownWeapon => vec![],
+            Behavior::IndirectFire(_, _) => vec![],
+            Behavior::Board(vehicle_index) => self.board_update(soldier_index, *vehicle_index),
+            Behavior::Dismount => self.dismount_update(soldier_index),
         });
 
         messages
@@ -23,10 +23,17 @@ impl Runner {
             }
             Behavior::Defend(angle) => Some(*angle),
             Behavior::Hide(angle) => Some(*angle),
-            Behavior::DriveTo(_) => None,
+            Behavior::DriveTo(_) | Behavior::ReverseTo(_) => None,
             Behavior::RotateTo(_) => None,
             Behavior::SuppressFire(point) => Some(angle(point, reference_point)),
             Behavior::EngageSoldier(_) => None,
+            Behavior::TearDownWeapon => None,
+            Behavior::IndirectFire(point, _) => Some(angle(point, reference_point)),
+            Behavior::Board(vehicle_index) => Some(angle(
+                &self.battle_state.vehicle(*vehicle_index).world_point(),
+                reference_point,
+            )),
+            Behavior::Dismount => None,
             // TODO: keep angle for dead/unconscious soldiers
             Behavior::Dead | Behavior::Unconscious => None,
         }
@@ -0,0 +1,545 @@
+use std::collections::HashSet;
+
+use battle_core::{
+    behavior::Behavior,
+    deployment::Deployment,
+    game::{control::MapControl, player::Player, Side},
+    message::{rejection::InputRejection, InputMessage},
+    order::Order,
+    state::battle::message::{BattleStateMessage, SoldierMessage, VehicleMessage},
+    types::{SoldierIndex, SquadUuid, VehicleIndex, WorldPoint},
+};
+
+use super::Runner;
+
+impl Runner {
+    /// Check if given player (remote client) is allowed to send this message now. Messages
+    /// which are not wrapped into `InputMessage::FromPlayer` (ex. embedded server gui) are
+    /// trusted and not checked.
+    pub fn validate(&self, player: &Player, message: &InputMessage) -> Result<(), InputRejection> {
+        match message {
+            // Each player deploy its own side, before the battle begins
+            InputMessage::LoadDeployment(deployment) => {
+                self.validate_placement()?;
+                self.validate_deployment(player, deployment)
+            }
+            InputMessage::LoadControl(controls) => {
+                self.validate_placement()?;
+                self.validate_control(player, controls)
+            }
+            InputMessage::RequireCompleteSync(side) => self.validate_side(player, side),
+            // A save contains the complete battle state (opponent deployment included), which
+            // remote players must not see before the battle end (embedded server gui can always
+            // save)
+            InputMessage::RequestSave(side) => {
+                self.validate_side(player, side)?;
+                self.validate_ended()
+            }
+            InputMessage::BattleState(message) => self.validate_battle_state(player, message),
+            InputMessage::SetBattleState(_)
+            | InputMessage::ChangeConfig(_)
+            | InputMessage::Handshake(_)
+            | InputMessage::FromPlayer(_, _) => Err(InputRejection::Forbidden),
+        }
+    }
+
+    fn validate_battle_state(
+        &self,
+        player: &Player,
+        message: &BattleStateMessage,
+    ) -> Result<(), InputRejection> {
+        match message {
+            BattleStateMessage::Soldier(soldier_index, soldier_message) => {
+                self.validate_soldier(player, soldier_index)?;
+                match soldier_message {
+                    SoldierMessage::SetOrder(order) => {
+                        self.validate_order(order)?;
+                        self.validate_not_ended()
+                    }
+                    SoldierMessage::SetWorldPosition(point) => {
+                        self.validate_placement()?;
+                        self.validate_placement_point(player, point)
+                    }
+                    SoldierMessage::SetOrientation(_) => self.validate_placement(),
+                    SoldierMessage::SetBehavior(behavior) => {
+                        self.validate_placement()?;
+                        self.validate_placement_behavior(behavior)
+                    }
+                    _ => Err(InputRejection::Forbidden),
+                }
+            }
+            BattleStateMessage::Vehicle(vehicle_index, vehicle_message) => {
+                self.validate_vehicle(player, vehicle_index)?;
+                match vehicle_message {
+                    VehicleMessage::SetWorldPosition(point) => {
+                        self.validate_placement()?;
+                        self.validate_placement_point(player, point)
+                    }
+                    VehicleMessage::SetChassisOrientation(_) => self.validate_placement(),
+                    VehicleMessage::SetMainTurretOrientation(_) | VehicleMessage::AddDamage(_) => {
+                        Err(InputRejection::Forbidden)
+                    }
+                }
+            }
+            BattleStateMessage::SetAReady(_) => {
+                self.validate_side(player, &Side::A)?;
+                self.validate_placement()
+            }
+            BattleStateMessage::SetBReady(_) => {
+                self.validate_side(player, &Side::B)?;
+                self.validate_placement()
+            }
+            // Players can claim a squad of their side not owned by another player, or release
+            // their own squads
+            BattleStateMessage::SetSquadOwner(squad_uuid, owner) => {
+                self.validate_squad(player, squad_uuid)?;
+                if matches!(owner, Some(owner) if owner != player.name()) {
+                    return Err(InputRejection::Forbidden);
+                }
+                self.validate_placement()
+            }
+            // Connected sides are given by network server
+            BattleStateMessage::SetAConnected(_) | BattleStateMessage::SetBConnected(_) => {
+                Err(InputRejection::Forbidden)
+            }
+            // Phase (abort included) is only changed by the server or its embedding gui
+            _ => Err(InputRejection::Forbidden),
+        }
+    }
+
+    fn validate_soldier(
+        &self,
+        player: &Player,
+        soldier_index: &SoldierIndex,
+    ) -> Result<(), InputRejection> {
+        self.battle_state
+            .soldiers()
+            .get(soldier_index.0)
+            .filter(|soldier| self.battle_state.player_controls(player, soldier))
+            .map(|_| ())
+            .ok_or(InputRejection::Soldier(*soldier_index))
+    }
+
+    fn validate_squad(
+        &self,
+        player: &Player,
+        squad_uuid: &SquadUuid,
+    ) -> Result<(), InputRejection> {
+        self.battle_state
+            .squads()
+            .get(squad_uuid)
+            .map(|squad| self.battle_state.soldier(squad.leader()))
+            .filter(|leader| self.battle_state.player_controls(player, leader))
+            .map(|_| ())
+            .ok_or(InputRejection::Squad(*squad_uuid))
+    }
+
+    /// Vehicle is controlled by the player who control one of its crew members
+    fn validate_vehicle(
+        &self,
+        player: &Player,
+        vehicle_index: &VehicleIndex,
+    ) -> Result<(), InputRejection> {
+        let controlled = self
+            .battle_state
+            .vehicle_board()
+            .get(vehicle_index)
+            .map(|board| {
+                board
+                    .iter()
+                    .any(|(_, soldier_index)| self.validate_soldier(player, soldier_index).is_ok())
+            })
+            .unwrap_or(false);
+
+        if !controlled {
+            return Err(InputRejection::Vehicle(*vehicle_index));
+        }
+
+        Ok(())
+    }
+
+    /// Deployment must only contain player side soldiers (in squads not already deployed),
+    /// their squads owners and vehicles they are on board
+    fn validate_deployment(
+        &self,
+        player: &Player,
+        deployment: &Deployment,
+    ) -> Result<(), InputRejection> {
+        if deployment
+            .soldiers()
+            .iter()
+            .any(|soldier| &soldier.side() != player.side())
+        {
+            return Err(InputRejection::Side);
+        }
+
+        let squads: HashSet<SquadUuid> = deployment
+            .soldiers()
+            .iter()
+            .map(|soldier| soldier.squad_uuid())
+            .collect();
+        if let Some(squad_uuid) = squads
+            .iter()
+            .find(|squad_uuid| self.battle_state.squads().contains_key(squad_uuid))
+        {
+            return Err(InputRejection::Squad(*squad_uuid));
+        }
+        if let Some(squad_uuid) = deployment
+            .squads_owners()
+            .keys()
+            .find(|squad_uuid| !squads.contains(squad_uuid))
+        {
+            return Err(InputRejection::Squad(*squad_uuid));
+        }
+
+        for vehicle in deployment.vehicles() {
+            let crewed = deployment.boards().iter().any(|(soldier_index, board)| {
+                board.0 == vehicle.uuid()
+                    && deployment
+                        .soldiers()
+                        .iter()
+                        .any(|soldier| &soldier.uuid() == soldier_index)
+            });
+            if !crewed {
+                return Err(InputRejection::Vehicle(vehicle.uuid()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Player can only give its side control
+    fn validate_control(
+        &self,
+        player: &Player,
+        (a_control, b_control): &(MapControl, MapControl),
+    ) -> Result<(), InputRejection> {
+        let opponent_control = match player.side() {
+            Side::A => b_control,
+            Side::B => a_control,
+            Side::All | Side::Observer => return Err(InputRejection::Side),
+        };
+        if !opponent_control.spawn_zone_names().is_empty() {
+            return Err(InputRejection::Side);
+        }
+
+        Ok(())
+    }
+
+    fn validate_order(&self, order: &Order) -> Result<(), InputRejection> {
+        if let Order::Board(vehicle_index) = order {
+            if self.battle_state.vehicles().get(vehicle_index.0).is_none() {
+                return Err(InputRejection::Vehicle(*vehicle_index));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_side(&self, player: &Player, side: &Side) -> Result<(), InputRejection> {
+        if player.side() != side {
+            return Err(InputRejection::Side);
+        }
+
+        Ok(())
+    }
+
+    fn validate_placement(&self) -> Result<(), InputRejection> {
+        let phase = self.battle_state.phase();
+        if !phase.is_placement() {
+            return Err(InputRejection::Phase(phase.clone()));
+        }
+
+        Ok(())
+    }
+
+    fn validate_placement_point(
+        &self,
+        player: &Player,
+        point: &WorldPoint,
+    ) -> Result<(), InputRejection> {
+        if !self
+            .battle_state
+            .allowed_placement_point(player.side(), point)
+        {
+            return Err(InputRejection::SpawnZone);
+        }
+
+        Ok(())
+    }
+
+    /// Soldiers can only be placed waiting or in defensive positions
+    fn validate_placement_behavior(&self, behavior: &Behavior) -> Result<(), InputRejection> {
+        match behavior {
+            Behavior::Idle(_) | Behavior::Defend(_) | Behavior::Hide(_) => Ok(()),
+            _ => Err(InputRejection::Behavior),
+        }
+    }
+
+    fn validate_ended(&self) -> Result<(), InputRejection> {
+        let phase = self.battle_state.phase();
+        if !phase.is_end() {
+            return Err(InputRejection::Phase(phase.clone()));
+        }
+
+        Ok(())
+    }
+
+    fn validate_not_ended(&self) -> Result<(), InputRejection> {
+        let phase = self.battle_state.phase();
+        if phase.is_end() {
+            return Err(InputRejection::Phase(phase.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        behavior::Behavior,
+        deployment::Deployment,
+        game::{control::MapControl, player::Player, Side},
+        message::{rejection::InputRejection, InputMessage, OutputMessage},
+        order::Order,
+        state::battle::{
+            message::{BattleStateMessage, SoldierMessage},
+            phase::{EndReason, Phase, Victorious},
+        },
+        types::{Angle, PlayerName, SoldierIndex, SquadUuid, VehicleIndex, WorldPaths, WorldPoint},
+    };
+    use oc_core::spawn::SpawnZoneName;
+    use rstest::*;
+
+    use crate::runner::{
+        test::{deployment, rejections, runner, soldier},
+        Runner,
+    };
+
+    #[rstest]
+    fn placement_is_restricted_to_side_zones_and_waiting_behaviors(
+        mut runner: Runner,
+        deployment: Deployment,
+    ) {
+        // Given
+        runner.step(vec![
+            InputMessage::LoadDeployment(deployment),
+            InputMessage::LoadControl((
+                MapControl::new(vec![SpawnZoneName::NorthWest]),
+                MapControl::new(vec![SpawnZoneName::SouthWest]),
+            )),
+        ]);
+        let player = Player::new(Side::A, PlayerName("Player1".to_string()));
+
+        // When
+        let (inputs, outputs) = runner.authorized_inputs(vec![InputMessage::FromPlayer(
+            player,
+            vec![
+                soldier(
+                    0,
+                    SoldierMessage::SetWorldPosition(WorldPoint::new(50., 50.)),
+                ),
+                soldier(
+                    0,
+                    SoldierMessage::SetWorldPosition(WorldPoint::new(50., 250.)),
+                ),
+                soldier(0, SoldierMessage::SetBehavior(Behavior::Hide(Angle(0.)))),
+                soldier(
+                    0,
+                    SoldierMessage::SetBehavior(Behavior::MoveTo(WorldPaths::new(vec![]))),
+                ),
+            ],
+        )]);
+
+        // Then
+        assert_eq!(inputs.len(), 2);
+        let rejections = rejections(&outputs);
+        assert_eq!(
+            rejections,
+            vec![&InputRejection::SpawnZone, &InputRejection::Behavior]
+        );
+    }
+
+    #[rstest]
+    fn remote_players_can_only_save_ended_battle(mut runner: Runner, deployment: Deployment) {
+        // Given
+        let player = Player::new(Side::A, PlayerName("Player1".to_string()));
+        let request_save = || {
+            vec![InputMessage::FromPlayer(
+                player.clone(),
+                vec![InputMessage::RequestSave(Side::A)],
+            )]
+        };
+        runner.step(vec![InputMessage::LoadDeployment(deployment)]);
+        let (_, during_placement) = runner.authorized_inputs(request_save());
+        runner.step(vec![InputMessage::BattleState(
+            BattleStateMessage::SetPhase(Phase::End(Victorious(Side::A), EndReason::Flags)),
+        )]);
+
+        // When
+        let (inputs, after_end) = runner.authorized_inputs(request_save());
+
+        // Then
+        assert_eq!(
+            rejections(&during_placement),
+            vec![&InputRejection::Phase(Phase::Placement)]
+        );
+        assert!(after_end.is_empty());
+        assert!(matches!(
+            inputs.as_slice(),
+            [InputMessage::RequestSave(Side::A)]
+        ));
+    }
+
+    #[rstest]
+    fn player_only_controls_its_squads(mut runner: Runner, deployment: Deployment) {
+        // Given
+        let owner = PlayerName("Player1".to_string());
+        let claim = |name: &PlayerName| {
+            vec![InputMessage::BattleState(
+                BattleStateMessage::SetSquadOwner(SquadUuid(0), Some(name.clone())),
+            )]
+        };
+        runner.step(vec![InputMessage::LoadDeployment(deployment)]);
+        runner.step(vec![InputMessage::FromPlayer(
+            Player::new(Side::A, owner.clone()),
+            claim(&owner),
+        )]);
+        let order = |soldier_index| {
+            vec![InputMessage::BattleState(BattleStateMessage::Soldier(
+                SoldierIndex(soldier_index),
+                SoldierMessage::SetOrder(Order::Idle),
+            ))]
+        };
+        let other = PlayerName("Player2".to_string());
+        let (_, rejections) = runner.authorized_inputs(vec![InputMessage::FromPlayer(
+            Player::new(Side::A, other.clone()),
+            claim(&other),
+        )]);
+        assert_eq!(
+            runner.battle_state().squads_owners().get(&SquadUuid(0)),
+            Some(&owner)
+        );
+        assert!(matches!(
+            rejections.as_slice(),
+            [(
+                _,
+                OutputMessage::InputRejected(_, InputRejection::Squad(SquadUuid(0)))
+            )]
+        ));
+
+        // When
+        let (inputs, _) = runner.authorized_inputs(vec![
+            InputMessage::FromPlayer(Player::new(Side::A, owner.clone()), order(0)),
+            InputMessage::FromPlayer(Player::new(Side::A, other.clone()), order(1)),
+            InputMessage::FromPlayer(Player::new(Side::B, owner), order(2)),
+            InputMessage::FromPlayer(Player::new(Side::B, other), order(5)),
+        ]);
+
+        // Then
+        let soldiers: Vec<SoldierIndex> = inputs
+            .iter()
+            .map(|input| match input {
+                InputMessage::BattleState(BattleStateMessage::Soldier(soldier_index, _)) => {
+                    *soldier_index
+                }
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(soldiers, vec![SoldierIndex(0), SoldierIndex(5)]);
+    }
+
+    #[rstest]
+    #[case(soldier(0, SoldierMessage::SetOrder(Order::Idle)), Ok(()))]
+    #[case(
+        soldier(5, SoldierMessage::SetOrder(Order::Idle)),
+        Err(InputRejection::Soldier(SoldierIndex(5)))
+    )]
+    #[case(
+        soldier(0, SoldierMessage::SetWorldPosition(WorldPoint::new(0., 0.))),
+        Err(InputRejection::Phase(Phase::Battle))
+    )]
+    #[case(
+        soldier(0, SoldierMessage::SetAlive(false)),
+        Err(InputRejection::Forbidden)
+    )]
+    #[case(
+        InputMessage::BattleState(BattleStateMessage::SetBReady(false)),
+        Err(InputRejection::Side)
+    )]
+    #[case(
+        InputMessage::BattleState(BattleStateMessage::SetPhase(Phase::Placement)),
+        Err(InputRejection::Forbidden)
+    )]
+    #[case(
+        InputMessage::BattleState(BattleStateMessage::SetPhase(Phase::End(
+            Victorious(Side::All),
+            EndReason::Aborted
+        ))),
+        Err(InputRejection::Forbidden)
+    )]
+    #[case(
+        InputMessage::BattleState(BattleStateMessage::SetAConnected(true)),
+        Err(InputRejection::Forbidden)
+    )]
+    #[case(
+        soldier(0, SoldierMessage::SetOrder(Order::Board(VehicleIndex(0)))),
+        Err(InputRejection::Vehicle(VehicleIndex(0)))
+    )]
+    #[case(InputMessage::RequestSave(Side::B), Err(InputRejection::Side))]
+    #[case(
+        InputMessage::RequestSave(Side::A),
+        Err(InputRejection::Phase(Phase::Battle))
+    )]
+    #[case(InputMessage::RequireCompleteSync(Side::B), Err(InputRejection::Side))]
+    #[case(
+        InputMessage::LoadControl((MapControl::empty(), MapControl::empty())),
+        Err(InputRejection::Phase(Phase::Battle))
+    )]
+    #[case(
+        InputMessage::BattleState(BattleStateMessage::SetSquadOwner(SquadUuid(1), None)),
+        Err(InputRejection::Squad(SquadUuid(1)))
+    )]
+    #[case(
+        InputMessage::BattleState(BattleStateMessage::SetSquadOwner(SquadUuid(0), None)),
+        Err(InputRejection::Phase(Phase::Battle))
+    )]
+    fn players_messages_are_validated(
+        mut runner: Runner,
+        deployment: Deployment,
+        #[case] message: InputMessage,
+        #[case] expected: Result<(), InputRejection>,
+    ) {
+        // Given
+        runner.start_battle(deployment);
+        runner.advance(1);
+        let player = Player::new(Side::A, PlayerName("Player1".to_string()));
+
+        // When
+        let outputs = runner.frame(vec![InputMessage::FromPlayer(
+            player.clone(),
+            vec![message],
+        )]);
+
+        // Then
+        assert!(runner.battle_state().phase().is_battle());
+        let rejections: Vec<&(Side, OutputMessage)> = outputs
+            .iter()
+            .filter(|(_, output)| matches!(output, OutputMessage::InputRejected(_, _)))
+            .collect();
+        match expected {
+            Ok(()) => assert!(rejections.is_empty()),
+            Err(rejection) => {
+                assert_eq!(rejections.len(), 1);
+                let (side, OutputMessage::InputRejected(player_, rejection_)) = rejections[0]
+                else {
+                    unreachable!()
+                };
+                assert_eq!(side, &Side::A);
+                assert_eq!(player_, &player);
+                assert_eq!(rejection_, &rejection);
+            }
+        }
+    }
+}
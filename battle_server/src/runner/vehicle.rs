@@ -1,22 +1,33 @@
+use std::f32::consts::PI;
+
 use battle_core::{
     behavior::{Behavior, Body},
     config::{
-        VEHICLE_DRIVE_ORIENTATION_ADVANCE_TOLERANCE_COEFFICIENT,
+        VEHICLE_AUTO_REVERSE_DISTANCE, VEHICLE_DRIVE_ORIENTATION_ADVANCE_TOLERANCE_COEFFICIENT,
         VEHICLE_DRIVE_ORIENTATION_ADVANCE_TOLERANCE_DIFF,
-        VEHICLE_DRIVE_ORIENTATION_TARGET_TOLERANCE_COEFFICIENT,
+        VEHICLE_DRIVE_ORIENTATION_TARGET_TOLERANCE_COEFFICIENT, VEHICLE_REVERSE_SPEED_COEFFICIENT,
+    },
+    entity::{
+        soldier::Soldier,
+        vehicle::{OnBoardPlace, VehicleFacing},
     },
+    order::Order,
+    physics::utils::distance_between_points,
     state::battle::message::{BattleStateMessage, SoldierMessage, VehicleMessage},
     types::*,
-    utils::{angle, short_angle, short_angle_way, AngleWay},
+    utils::{angle, short_angle_way, AngleWay},
 };
 
 use super::{message::RunnerMessage, Runner};
 
 impl Runner {
+    /// Driver drive its vehicle toward the next path point, front first or, when `reverse`,
+    /// rear first (at reduced speed)
     pub fn drive_update(
         &self,
         soldier_index: SoldierIndex,
         paths: &WorldPaths,
+        reverse: bool,
     ) -> Vec<RunnerMessage> {
         let vehicle_index = self
             .battle_state
@@ -24,46 +35,50 @@ impl Runner {
             .expect("this code must be called only when soldier is on board")
             .0;
         let vehicle = self.battle_state.vehicle(vehicle_index);
+        if !vehicle.can_move() {
+            return vec![];
+        }
+        let definition = self.battle_state.vehicle_definition(vehicle);
         let vehicle_position = vehicle.world_point();
         let move_target_point = paths
             .next_point()
             .expect("Execute drive update imply move path is filled");
         let angle = angle(&move_target_point, &vehicle_position);
-        let move_vector = (move_target_point.to_vec2() - vehicle_position.to_vec2()).normalize()
-            * vehicle.type_().drive_speed();
-
-        let rounded_chassis_orientation = (vehicle.chassis_orientation().0
-            * VEHICLE_DRIVE_ORIENTATION_TARGET_TOLERANCE_COEFFICIENT)
-            .round()
-            .abs();
-        let target_vehicle_orientation = (angle.0
-            * VEHICLE_DRIVE_ORIENTATION_TARGET_TOLERANCE_COEFFICIENT)
-            .round()
-            .abs();
+        // In reverse gear, the chassis rear is pointed toward the next point
+        let (chassis_angle, speed) = if reverse {
+            (
+                Angle(angle.0 + PI),
+                definition.drive_speed() * VEHICLE_REVERSE_SPEED_COEFFICIENT,
+            )
+        } else {
+            (angle, definition.drive_speed())
+        };
+        let move_vector =
+            (move_target_point.to_vec2() - vehicle_position.to_vec2()).normalize() * speed;
+        let rotation = vehicle.chassis_rotation(&chassis_angle);
+        let reached = (vehicle_position.to_vec2() - move_target_point.to_vec2()).length()
+            <= move_vector.length();
 
         let mut messages = vec![];
 
-        // Need to rotate chassis ?
-        if rounded_chassis_orientation != target_vehicle_orientation {
-            let new_orientation = match short_angle_way(vehicle.chassis_orientation(), &angle) {
-                AngleWay::ClockWise => {
-                    *vehicle.chassis_orientation() + vehicle.type_().chassis_rotation_speed()
-                }
-                AngleWay::CounterClockWise => {
-                    *vehicle.chassis_orientation() + (-vehicle.type_().chassis_rotation_speed())
-                }
-            };
+        // Need to rotate chassis ? (not once on the point, where its angle is meaningless)
+        if !reached
+            && (rotation.0 * VEHICLE_DRIVE_ORIENTATION_TARGET_TOLERANCE_COEFFICIENT).round() != 0.
+        {
+            let rotation_speed = definition.chassis_rotation_speed().0;
             messages.push(RunnerMessage::BattleState(BattleStateMessage::Vehicle(
                 vehicle_index,
-                VehicleMessage::SetChassisOrientation(new_orientation),
+                VehicleMessage::SetChassisOrientation(Angle(
+                    vehicle.chassis_orientation().0
+                        + rotation.0.clamp(-rotation_speed, rotation_speed),
+                )),
             )));
         }
 
         // Can advance ?
-        if (short_angle(vehicle.chassis_orientation(), &angle).0
-            * VEHICLE_DRIVE_ORIENTATION_ADVANCE_TOLERANCE_COEFFICIENT)
-            .abs()
-            < VEHICLE_DRIVE_ORIENTATION_ADVANCE_TOLERANCE_DIFF
+        if !reached
+            && (rotation.0 * VEHICLE_DRIVE_ORIENTATION_ADVANCE_TOLERANCE_COEFFICIENT).abs()
+                < VEHICLE_DRIVE_ORIENTATION_ADVANCE_TOLERANCE_DIFF
         {
             let new_point = vehicle_position.apply(move_vector);
             messages.push(RunnerMessage::BattleState(BattleStateMessage::Vehicle(
@@ -73,9 +88,7 @@ impl Runner {
         }
 
         // Next point reached ?
-        if (vehicle_position.to_vec2() - move_target_point.to_vec2()).length()
-            <= move_vector.length()
-        {
+        if reached {
             // If it is the last point, move is finished
             if paths.is_last_point().expect("Must contain points") {
                 messages.push(RunnerMessage::BattleState(BattleStateMessage::Soldier(
@@ -100,6 +113,10 @@ impl Runner {
             .expect("this code must be called only when soldier is on board")
             .0;
         let vehicle = self.battle_state.vehicle(vehicle_index);
+        if !vehicle.can_move() {
+            return vec![];
+        }
+        let definition = self.battle_state.vehicle_definition(vehicle);
 
         let mut messages = vec![];
 
@@ -107,10 +124,10 @@ impl Runner {
         if !vehicle.chassis_orientation_match(angle) {
             let new_orientation = match short_angle_way(vehicle.chassis_orientation(), angle) {
                 AngleWay::ClockWise => {
-                    *vehicle.chassis_orientation() + vehicle.type_().chassis_rotation_speed()
+                    *vehicle.chassis_orientation() + definition.chassis_rotation_speed()
                 }
                 AngleWay::CounterClockWise => {
-                    *vehicle.chassis_orientation() + (-vehicle.type_().chassis_rotation_speed())
+                    *vehicle.chassis_orientation() + (-definition.chassis_rotation_speed())
                 }
             };
             messages.push(RunnerMessage::BattleState(BattleStateMessage::Vehicle(
@@ -126,4 +143,332 @@ impl Runner {
 
         messages
     }
+
+    /// Is the soldier driving a vehicle and the paths end short behind it (so it is quicker to
+    /// back up than to pivot)
+    pub fn soldier_vehicle_should_reverse(&self, soldier: &Soldier, paths: &WorldPaths) -> bool {
+        let (vehicle_index, place) = match self.battle_state.soldier_board(soldier.uuid()) {
+            Some(board) => board,
+            None => return false,
+        };
+        let last_point = match paths.last_point() {
+            Some(point) => point,
+            None => return false,
+        };
+        let vehicle = self.battle_state.vehicle(*vehicle_index);
+
+        place == &OnBoardPlace::Driver
+            && vehicle.facing(&last_point) == VehicleFacing::Rear
+            && distance_between_points(&vehicle.world_point(), &last_point).meters()
+                <= VEHICLE_AUTO_REVERSE_DISTANCE
+    }
+
+    /// Soldier walk to the vehicle then take one of its free places
+    pub fn board_update(
+        &self,
+        soldier_index: SoldierIndex,
+        vehicle_index: VehicleIndex,
+    ) -> Vec<RunnerMessage> {
+        let soldier = self.battle_state.soldier(soldier_index);
+        if self.battle_state.soldier_board(soldier_index).is_some() {
+            return self.board_finished(soldier_index);
+        }
+
+        let vehicle = self.battle_state.vehicle(vehicle_index);
+        let definition = self.battle_state.vehicle_definition(vehicle);
+        if vehicle
+            .chassis_shape(definition)
+            .contains(&soldier.world_point())
+        {
+            return match self.battle_state.vehicle_free_place(vehicle_index) {
+                Some(place) => vec![RunnerMessage::BattleState(
+                    BattleStateMessage::SetSoldierBoard(
+                        soldier_index,
+                        Some((vehicle_index, place)),
+                    ),
+                )],
+                None => self.board_finished(soldier_index),
+            };
+        }
+
+        let velocity = self
+            .config
+            .behavior_velocity(soldier.behavior())
+            .expect("Entity behavior must have velocity when move code called");
+        let vector = (vehicle.world_point().to_vec2() - soldier.world_point().to_vec2())
+            .normalize()
+            * velocity;
+        vec![RunnerMessage::BattleState(BattleStateMessage::Soldier(
+            soldier_index,
+            SoldierMessage::SetWorldPosition(soldier.world_point().apply(vector)),
+        ))]
+    }
+
+    /// Soldier leave its vehicle and stand beside its hull
+    pub fn dismount_update(&self, soldier_index: SoldierIndex) -> Vec<RunnerMessage> {
+        let mut messages = vec![];
+
+        if let Some((vehicle_index, place)) = self.battle_state.soldier_board(soldier_index) {
+            let vehicle = self.battle_state.vehicle(*vehicle_index);
+            let point =
+                vehicle.dismount_point(self.battle_state.vehicle_definition(vehicle), place);
+            messages.extend(vec![
+                RunnerMessage::BattleState(BattleStateMessage::SetSoldierBoard(
+                    soldier_index,
+                    None,
+                )),
+                RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                    soldier_index,
+                    SoldierMessage::SetWorldPosition(point),
+                )),
+            ]);
+        }
+
+        messages.extend(self.board_finished(soldier_index));
+        messages
+    }
+
+    fn board_finished(&self, soldier_index: SoldierIndex) -> Vec<RunnerMessage> {
+        let soldier = self.battle_state.soldier(soldier_index);
+        vec![
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                soldier_index,
+                SoldierMessage::SetBehavior(Behavior::Idle(Body::from_soldier(
+                    soldier,
+                    &self.battle_state,
+                ))),
+            )),
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                soldier_index,
+                SoldierMessage::SetOrder(Order::Idle),
+            )),
+        ]
+    }
+
+    /// Vehicle of which given soldier is the main turret gunner
+    pub fn soldier_main_turret(&self, soldier_index: SoldierIndex) -> Option<VehicleIndex> {
+        match self.battle_state.soldier_board(soldier_index) {
+            Some((vehicle_index, OnBoardPlace::MainTurretGunner)) => Some(*vehicle_index),
+            _ => None,
+        }
+    }
+
+    /// Is given soldier not a main turret gunner, or its vehicle is not knocked out
+    pub fn main_turret_can_fire(&self, soldier_index: SoldierIndex) -> bool {
+        self.soldier_main_turret(soldier_index)
+            .is_none_or(|vehicle_index| self.battle_state.vehicle(vehicle_index).can_fire())
+    }
+
+    /// Is given soldier not a main turret gunner, or its turret is pointing given point
+    pub fn main_turret_aimed_at(&self, soldier_index: SoldierIndex, point: &WorldPoint) -> bool {
+        match self.soldier_main_turret(soldier_index) {
+            Some(vehicle_index) => {
+                let vehicle = self.battle_state.vehicle(vehicle_index);
+                vehicle.main_turret_aimed(&angle(point, &vehicle.world_point()))
+            }
+            None => true,
+        }
+    }
+
+    /// Main turret gunner traverse the turret (independently of the chassis) toward its target
+    pub fn turret_update(
+        &self,
+        soldier_index: SoldierIndex,
+        point: &WorldPoint,
+    ) -> Vec<RunnerMessage> {
+        let vehicle_index = match self.soldier_main_turret(soldier_index) {
+            Some(vehicle_index) => vehicle_index,
+            None => return vec![],
+        };
+        let vehicle = self.battle_state.vehicle(vehicle_index);
+        if !vehicle.can_traverse() {
+            return vec![];
+        }
+        let traverse = vehicle.main_turret_traverse(&angle(point, &vehicle.world_point()));
+        if traverse.0 == 0. {
+            return vec![];
+        }
+
+        let speed = self
+            .battle_state
+            .vehicle_definition(vehicle)
+            .main_turret_rotation_speed()
+            .0;
+        let step = traverse.0.clamp(-speed, speed);
+        vec![RunnerMessage::BattleState(BattleStateMessage::Vehicle(
+            vehicle_index,
+            VehicleMessage::SetMainTurretOrientation(Angle(
+                vehicle.main_turret_relative_orientation().0 + step,
+            )),
+        ))]
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use battle_core::{
+        behavior::{gesture::Gesture, Behavior},
+        deployment::Deployment,
+        entity::{soldier::WeaponClass, vehicle::VehicleDamage},
+        message::InputMessage,
+        order::Order,
+        physics::utils::distance_between_points,
+        state::battle::message::{BattleStateMessage, SoldierMessage, VehicleMessage},
+        types::{SoldierIndex, VehicleIndex, WorldPath, WorldPaths, WorldPoint},
+    };
+    use rstest::*;
+
+    use crate::runner::{
+        test::{runner, soldier, tank_deployment},
+        Runner,
+    };
+
+    #[rstest]
+    fn main_turret_traverse_before_firing(mut runner: Runner, tank_deployment: Deployment) {
+        // Given
+        runner.step(vec![
+            InputMessage::LoadDeployment(tank_deployment),
+            InputMessage::BattleState(BattleStateMessage::Soldier(
+                SoldierIndex(1),
+                SoldierMessage::SetGesture(Gesture::Aiming(0, WeaponClass::Main)),
+            )),
+        ]);
+        let chassis_orientation = *runner
+            .battle_state()
+            .vehicle(VehicleIndex(0))
+            .chassis_orientation();
+        let gunner = runner.battle_state().soldier(SoldierIndex(1));
+        let weapon = gunner.main_weapon().clone().unwrap();
+        let point = gunner.world_point().apply(glam::Vec2::new(-100., -100.));
+
+        // When
+        let (_, gesture) =
+            runner.engage_point_gesture(gunner, &point, (WeaponClass::Main, &weapon));
+
+        // Then
+        assert!(!runner.main_turret_aimed_at(SoldierIndex(1), &point));
+        assert!(matches!(gesture, Gesture::Aiming(_, _)));
+
+        // When
+        runner.step_until(1000, |runner| runner.turret_update(SoldierIndex(1), &point));
+        let gunner = runner.battle_state().soldier(SoldierIndex(1));
+        let (_, gesture) =
+            runner.engage_point_gesture(gunner, &point, (WeaponClass::Main, &weapon));
+
+        // Then
+        let vehicle = runner.battle_state().vehicle(VehicleIndex(0));
+        assert!(runner.main_turret_aimed_at(SoldierIndex(1), &point));
+        assert_eq!(vehicle.chassis_orientation(), &chassis_orientation);
+        assert!(matches!(gesture, Gesture::Firing(_, _)));
+    }
+
+    #[rstest]
+    fn damaged_vehicle_can_not_move_traverse_or_fire(
+        mut runner: Runner,
+        tank_deployment: Deployment,
+    ) {
+        // Given
+        runner.step(vec![InputMessage::LoadDeployment(tank_deployment)]);
+        let gunner = runner.battle_state().soldier(SoldierIndex(1));
+        let point = gunner.world_point().apply(glam::Vec2::new(-100., -100.));
+        let paths = WorldPaths::new(vec![WorldPath::new(vec![point])]);
+        assert!(!runner
+            .drive_update(SoldierIndex(0), &paths, false)
+            .is_empty());
+        assert!(!runner.turret_update(SoldierIndex(1), &point).is_empty());
+        assert!(runner
+            .soldier_able_to_fire_unseen_point(gunner, &point)
+            .is_some());
+
+        // When
+        runner.step(
+            [
+                VehicleDamage::Immobilized,
+                VehicleDamage::TurretJammed,
+                VehicleDamage::KnockedOut,
+            ]
+            .into_iter()
+            .map(|damage| {
+                InputMessage::BattleState(BattleStateMessage::Vehicle(
+                    VehicleIndex(0),
+                    VehicleMessage::AddDamage(damage),
+                ))
+            })
+            .collect(),
+        );
+
+        // Then
+        let gunner = runner.battle_state().soldier(SoldierIndex(1));
+        assert!(runner
+            .drive_update(SoldierIndex(0), &paths, false)
+            .is_empty());
+        assert!(runner.turret_update(SoldierIndex(1), &point).is_empty());
+        assert!(runner
+            .soldier_able_to_fire_unseen_point(gunner, &point)
+            .is_none());
+    }
+
+    /// Point at given distance (pixels) straight behind the tank of the tank deployment
+    fn point_behind_tank(runner: &Runner, distance: f32) -> WorldPoint {
+        let vehicle = runner.battle_state().vehicle(VehicleIndex(0));
+        let orientation = vehicle.chassis_orientation().0;
+        vehicle.world_point().apply(glam::Vec2::new(
+            -orientation.sin() * distance,
+            orientation.cos() * distance,
+        ))
+    }
+
+    #[rstest]
+    fn vehicle_reverse_to_keep_its_front_toward_the_enemy(
+        mut runner: Runner,
+        tank_deployment: Deployment,
+    ) {
+        // Given
+        runner.start_battle(tank_deployment);
+        let destination = point_behind_tank(&runner, 25.);
+        let orientation = *runner
+            .battle_state()
+            .vehicle(VehicleIndex(0))
+            .chassis_orientation();
+        let order = Order::ReverseTo(
+            WorldPaths::new(vec![WorldPath::new(vec![destination])]),
+            None,
+        );
+
+        // When
+        runner.step(vec![soldier(0, SoldierMessage::SetOrder(order))]);
+        runner.advance(900);
+
+        // Then
+        let vehicle = runner.battle_state().vehicle(VehicleIndex(0));
+        assert!(distance_between_points(&vehicle.world_point(), &destination).meters() < 1);
+        assert!(vehicle.chassis_rotation(&orientation).0.abs() < 0.01);
+    }
+
+    #[rstest]
+    #[case(25., true)]
+    #[case(100., false)]
+    fn vehicle_reverse_only_short_moves_behind(
+        mut runner: Runner,
+        tank_deployment: Deployment,
+        #[case] distance: f32,
+        #[case] expected_reverse: bool,
+    ) {
+        // Given
+        runner.start_battle(tank_deployment);
+        let destination = point_behind_tank(&runner, distance);
+        let order = Order::MoveTo(
+            WorldPaths::new(vec![WorldPath::new(vec![destination])]),
+            None,
+        );
+
+        // When
+        runner.step(vec![soldier(0, SoldierMessage::SetOrder(order))]);
+        runner.advance(40);
+
+        // Then
+        let behavior = runner.battle_state().soldier(SoldierIndex(0)).behavior();
+        assert_eq!(matches!(behavior, Behavior::ReverseTo(_)), expected_reverse);
+        assert_eq!(matches!(behavior, Behavior::DriveTo(_)), !expected_reverse);
+    }
 }
@@ -110,9 +110,13 @@ impl Runner {
                     | Order::MoveTo(_, _)
                     | Order::MoveFastTo(_, _)
                     | Order::SneakTo(_, _)
+                    | Order::ReverseTo(_, _)
                     | Order::Defend(_)
                     | Order::Hide(_)
-                    | Order::SuppressFire(_) => {}
+                    | Order::SuppressFire(_)
+                    | Order::IndirectFire(_)
+                    | Order::Board(_)
+                    | Order::Dismount => {}
                     Order::EngageSquad(squad_uuid) => {
                         let engaged_squad = self.battle_state.squad(*squad_uuid);
                         if !engaged_squad
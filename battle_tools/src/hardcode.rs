@@ -2,7 +2,7 @@ use battle_core::{
     deployment::{Deployment, SoldierDeployment, VehicleDeployment},
     entity::vehicle::{OnBoardPlace, VehicleType},
     game::{
-        weapon::{Magazine, Weapon},
+        weapon::{Magazine, MagazineType, Weapon, WeaponType},
         Side,
     },
     types::{SoldierIndex, SoldiersOnBoard, SquadUuid, VehicleIndex, WorldPoint},
@@ -10,6 +10,14 @@ use battle_core::{
 };
 use glam::Vec2;
 
+fn mosin_nagant_magazine() -> Magazine {
+    Magazine::new(MagazineType("MosinNagant".to_string()), 5)
+}
+
+fn shell_magazine(type_: &str) -> Magazine {
+    Magazine::new(MagazineType(type_.to_string()), 1)
+}
+
 pub fn demo1_deployment() -> Deployment {
     let mut soldiers = vec![];
     let mut vehicles = vec![];
@@ -26,14 +34,12 @@ pub fn demo1_deployment() -> Deployment {
                 Side::A,
                 WorldPoint::from(Vec2::new(x as f32 * 10. + 20.0, y as f32 * 10. + 100.)),
                 SquadUuid(squad),
-                Some(Weapon::MosinNagantM1924(
+                Some(Weapon::new(
+                    WeaponType("MosinNagantM1924".to_string()),
                     false,
-                    Some(Magazine::full(Magazine::MosinNagant(0))),
+                    Some(mosin_nagant_magazine()),
                 )),
-                vec![
-                    Magazine::full(Magazine::MosinNagant(0)),
-                    Magazine::full(Magazine::MosinNagant(0)),
-                ],
+                vec![mosin_nagant_magazine(), mosin_nagant_magazine()],
             );
             soldiers.push(soldier);
             soldiers_index += 1;
@@ -50,14 +56,12 @@ pub fn demo1_deployment() -> Deployment {
                 Side::B,
                 WorldPoint::from(Vec2::new(x as f32 * 10. + 550., y as f32 * 10. + 250.)),
                 SquadUuid(squad),
-                Some(Weapon::MosinNagantM1924(
+                Some(Weapon::new(
+                    WeaponType("MosinNagantM1924".to_string()),
                     false,
-                    Some(Magazine::full(Magazine::MosinNagant(0))),
+                    Some(mosin_nagant_magazine()),
                 )),
-                vec![
-                    Magazine::full(Magazine::MosinNagant(0)),
-                    Magazine::full(Magazine::MosinNagant(0)),
-                ],
+                vec![mosin_nagant_magazine(), mosin_nagant_magazine()],
             );
             soldiers.push(soldier);
             soldiers_index += 1;
@@ -66,7 +70,7 @@ pub fn demo1_deployment() -> Deployment {
 
     let tank = VehicleDeployment::new(
         VehicleIndex(0),
-        VehicleType::T26,
+        VehicleType("T26".to_string()),
         WorldPoint::from(Vec2::new(100., 100.)),
     );
     vehicles.push(tank);
@@ -91,8 +95,13 @@ pub fn demo1_deployment() -> Deployment {
         Side::A,
         WorldPoint::from(Vec2::new(0., 0.)),
         SquadUuid(tank1_squad),
+        // Armed with the tank main gun at deployment
         None,
-        vec![],
+        [
+            vec![shell_magazine("x45HE"); 20],
+            vec![shell_magazine("x45AP"); 10],
+        ]
+        .concat(),
     );
     soldiers.push(tank_gunner);
     boards.insert(
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::health::Health;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Morale(pub f32);
 
 impl Morale {